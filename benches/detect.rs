@@ -0,0 +1,72 @@
+//! Benchmarks for detecting real formats, using the fixtures in `tests/fixtures`.
+//!
+//! Run with `cargo bench --bench detect`. Save a baseline before a change with
+//! `cargo bench --bench detect -- --save-baseline main`, then compare against it with
+//! `cargo bench --bench detect -- --baseline main`.
+//!
+//! Baseline at aa02b91, criterion 0.5.1 (`cargo bench` builds with the release
+//! profile), rustc 1.95.0, one core of an x86_64 Xeon VM, memchr on, median of
+//! `--warm-up-time 1 --measurement-time 3`:
+//! - from_bytes mkv.mkv: ~1.95 µs
+//! - from_bytes mp4.mp4: ~3.7 µs
+//! - from_bytes ts.ts: ~3.8 µs
+//! - from_bytes mxf.mxf: ~4.8 µs
+//! - from_bytes mxf 60 KiB run-in: ~13.9 µs
+//! - from_file_path ts.ts: ~32 µs
+//! - from_file_path mxf.mxf: ~34 µs
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use media_infer::ContainerType;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+fn from_bytes_fixtures(c: &mut Criterion) {
+    for name in ["mkv.mkv", "mp4.mp4", "ts.ts", "mxf.mxf"] {
+        let buffer = fs::read(fixture(name)).unwrap();
+        assert!(ContainerType::from_bytes(&buffer).is_ok());
+        c.bench_function(&format!("from_bytes {}", name), |b| {
+            b.iter(|| ContainerType::from_bytes(black_box(&buffer)))
+        });
+    }
+}
+
+/// MXF header partition behind 60 KiB of run-in, close to the 64 KiB SMPTE 377 allows.
+fn from_bytes_mxf_run_in(c: &mut Criterion) {
+    let mut buffer = vec![0x55; 60 * 1024];
+    buffer.extend(fs::read(fixture("mxf.mxf")).unwrap());
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::MXF));
+    c.bench_function("from_bytes mxf 60 KiB run-in", |b| {
+        b.iter(|| ContainerType::from_bytes(black_box(&buffer)))
+    });
+}
+
+/// Includes opening and reading the file, from tmpfs when there is one so the disk
+/// does not dominate.
+fn from_file_path(c: &mut Criterion) {
+    let dir = if Path::new("/dev/shm").is_dir() {
+        tempfile::tempdir_in("/dev/shm").unwrap()
+    } else {
+        tempfile::tempdir().unwrap()
+    };
+    for name in ["ts.ts", "mxf.mxf"] {
+        let path = dir.path().join(name);
+        fs::copy(fixture(name), &path).unwrap();
+        c.bench_function(&format!("from_file_path {}", name), |b| {
+            b.iter(|| ContainerType::from_file_path(black_box(&path)))
+        });
+    }
+}
+
+criterion_group!(
+    benches,
+    from_bytes_fixtures,
+    from_bytes_mxf_run_in,
+    from_file_path
+);
+criterion_main!(benches);
@@ -0,0 +1,27 @@
+//! Compares `ContainerType::from_file_path` with ffprobe over a directory.
+//!
+//! `cargo run --example ffprobe_diff -- <dir>`
+
+#[path = "../tests/support/mod.rs"]
+mod support;
+
+use std::env;
+use std::path::PathBuf;
+use std::process;
+
+fn main() {
+    let dir = match env::args_os().nth(1) {
+        Some(x) => PathBuf::from(x),
+        None => {
+            eprintln!("usage: ffprobe_diff <dir>");
+            process::exit(2);
+        }
+    };
+    match support::Report::run(&dir) {
+        Ok(report) => print!("{}", report),
+        Err(e) => {
+            eprintln!("ffprobe_diff: {}", e);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! Writes a minimal sample of every container into `tests/fixtures/generated/`.
+//!
+//! `cargo run --example generate_samples --features samples`
+
+use media_infer::samples::generate_sample;
+use media_infer::DEFAULT_PRIORITY;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/generated");
+    fs::create_dir_all(&dir).unwrap();
+    for kind in DEFAULT_PRIORITY {
+        let path = dir.join(format!("{}.bin", kind.short_name()));
+        fs::write(&path, generate_sample(*kind)).unwrap();
+        println!("{}", path.display());
+    }
+}
@@ -0,0 +1,7 @@
+//! Prints the signature table as a magic(5) file.
+//!
+//! `cargo run --example magic > media_infer.magic && file -m media_infer.magic <file>`
+
+fn main() {
+    print!("{}", media_infer::magic_definitions());
+}
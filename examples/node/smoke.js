@@ -0,0 +1,32 @@
+// Smoke test for the Node.js bindings.
+//
+//   cargo rustc --lib --release --features napi --crate-type cdylib
+//   cp target/release/libmedia_infer.so examples/node/media_infer.node
+//   node examples/node/smoke.js
+//
+// On macOS copy libmedia_infer.dylib, on Windows media_infer.dll.
+
+const assert = require('assert');
+const path = require('path');
+const { detectBytes, detectFile } = require('./media_infer.node');
+
+const fixtures = path.join(__dirname, '..', '..', 'tests', 'fixtures');
+
+async function main() {
+  assert.strictEqual(detectBytes(Buffer.from([0x1a, 0x45, 0xdf, 0xa3, 0, 1])), 'mkv');
+  assert.strictEqual(detectBytes(Buffer.alloc(64, 0x42)), null);
+
+  assert.strictEqual(await detectFile(path.join(fixtures, 'ts.ts')), 'ts');
+  assert.strictEqual(await detectFile(path.join(fixtures, 'tivo.ty')), 'tivops');
+  await assert.rejects(detectFile(path.join(fixtures, 'missing.ts')), {
+    code: 'ERR_MEDIA_INFER_OPEN',
+  });
+  await assert.rejects(detectFile(fixtures), { code: 'ERR_MEDIA_INFER_IS_DIRECTORY' });
+
+  console.log('ok');
+}
+
+main().catch((e) => {
+  console.error(e);
+  process.exit(1);
+});
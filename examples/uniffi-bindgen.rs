@@ -0,0 +1,12 @@
+//! Generates the Kotlin and Swift bindings from the built library.
+//!
+//! ```sh
+//! cargo rustc --lib --release --features uniffi --crate-type cdylib
+//! cargo run --features uniffi-cli --example uniffi-bindgen -- generate \
+//!     --library target/release/libmedia_infer.so --language kotlin --out-dir out
+//! ```
+//! Use `--language swift` for iOS, and the `.dylib`/`.a` built for the Apple targets.
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -0,0 +1,34 @@
+//! Probes files given on the command line, also works under WASI.
+//!
+//! ```sh
+//! cargo build --target wasm32-wasip1 --example wasi_probe
+//! wasmtime --dir media target/wasm32-wasip1/debug/examples/wasi_probe.wasm media/clip.ts
+//! ```
+//! WASI programs only see preopened directories, hence `--dir`.
+
+use media_infer::ContainerType;
+use std::env;
+use std::path::Path;
+use std::process;
+
+fn main() {
+    let paths: Vec<_> = env::args_os().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage: wasi_probe <file>...");
+        process::exit(2);
+    }
+    let mut failed = false;
+    for path in &paths {
+        let path = Path::new(path);
+        match ContainerType::from_file_path(path) {
+            Ok(kind) => println!("{}: {}", path.display(), kind),
+            Err(e) => {
+                eprintln!("{}: {}", path.display(), e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
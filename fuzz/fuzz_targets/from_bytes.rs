@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use media_infer::ContainerType;
+
+fuzz_target!(|data: &[u8]| {
+    // Must not panic, and whatever is returned must match on its own.
+    if let Ok(kind) = ContainerType::from_bytes(data) {
+        assert!(kind.matches(data), "{:?} returned but does not match", kind);
+    }
+    for kind in ContainerType::from_bytes_all(data) {
+        assert!(kind.matches(data), "{:?} listed but does not match", kind);
+    }
+});
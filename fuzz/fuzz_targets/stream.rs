@@ -0,0 +1,45 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use media_infer::{ContainerType, Detector, StreamDetector};
+
+/// Most split offsets taken from the end of the input.
+const MAX_SPLITS: usize = 16;
+
+fuzz_target!(|input: &[u8]| {
+    // The last byte is the number of splits, the 2 byte offsets before it say where.
+    // The rest is the stream, so fixtures still start with their own header.
+    let (count, input) = match input.split_last() {
+        Some((x, rest)) => (usize::from(*x) % (MAX_SPLITS + 1), rest),
+        None => return,
+    };
+    if input.len() < count * 2 {
+        return;
+    }
+    let (data, offsets) = input.split_at(input.len() - count * 2);
+    let expected = match ContainerType::try_from_bytes(data) {
+        Ok(x) => x,
+        Err(_) => return,
+    };
+
+    let mut splits: Vec<usize> = offsets
+        .chunks(2)
+        .map(|x| usize::from(u16::from_le_bytes([x[0], x[1]])) % (data.len() + 1))
+        .collect();
+    splits.sort_unstable();
+
+    let mut stream = StreamDetector::new(Detector::default()).with_cap(data.len());
+    let mut start = 0;
+    for end in splits.into_iter().chain(Some(data.len())) {
+        stream.push(&data[start..end]);
+        start = end;
+    }
+    let t = stream.finish();
+    assert_eq!(
+        t.result.as_ref().ok(),
+        expected.as_ref(),
+        "chunked {:?} whole {:?}",
+        t.result,
+        expected
+    );
+});
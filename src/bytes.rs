@@ -0,0 +1,45 @@
+/// Returns `len` bytes at `pos`, or `None` if they are not all in `buffer`.
+/// Never overflows, whatever `pos` and `len` are.
+pub(crate) fn slice_at(buffer: &[u8], pos: usize, len: usize) -> Option<&[u8]> {
+    buffer.get(pos..pos.checked_add(len)?)
+}
+
+/// Position of packet `index` in a run of packets `stride` bytes apart starting at `start`.
+/// `None` if it does not fit in `usize`.
+pub(crate) fn stride_pos(start: usize, index: usize, stride: usize) -> Option<usize> {
+    index.checked_mul(stride)?.checked_add(start)
+}
+
+/// Big endian u32 at `pos`.
+pub(crate) fn be_u32_at(buffer: &[u8], pos: usize) -> Option<u32> {
+    slice_at(buffer, pos, 4).map(|x| u32::from_be_bytes([x[0], x[1], x[2], x[3]]))
+}
+
+/// Big endian u64 at `pos`.
+pub(crate) fn be_u64_at(buffer: &[u8], pos: usize) -> Option<u64> {
+    let x = slice_at(buffer, pos, 8)?;
+    Some(u64::from_be_bytes([
+        x[0], x[1], x[2], x[3], x[4], x[5], x[6], x[7],
+    ]))
+}
+
+/// Unsigned LEB128 value at `pos`, as AV1 codes OBU sizes, and its length.
+/// `None` if it is cut off by the end of `buffer` or longer than 8 bytes.
+pub(crate) fn leb128_at(buffer: &[u8], pos: usize) -> Option<(u64, usize)> {
+    const MAX_LEN: usize = 8;
+
+    let mut value = 0;
+    for i in 0..MAX_LEN {
+        let byte = *buffer.get(pos.checked_add(i)?)?;
+        value |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// Whether `x` is a four character code of printable ASCII, like ISO BMFF box types.
+pub(crate) fn is_fourcc(x: &[u8]) -> bool {
+    x.len() == 4 && x.iter().all(|x| x.is_ascii_graphic() || *x == b' ')
+}
@@ -0,0 +1,326 @@
+//! C ABI matching CCExtractor's stream detection, so it can replace its
+//! `detect_stream_type`.
+//!
+//! Return values are CCExtractor's `enum ccx_stream_mode_enum`
+//! (`src/lib_ccx/ccx_common_constants.h`):
+//!
+//! | Value | CCExtractor                      | ContainerType          |
+//! |-------|----------------------------------|------------------------|
+//! | 0     | `CCX_SM_ELEMENTARY_OR_NOT_FOUND` | `ES`, `H264ES`, `H265ES`, `MPEG4ES`, `AV1OBU`, `AACADTS`, `AACLATM`, `MP3`, `FLAC`, `AC3`, `EAC3`, `DTS`, `DTSHD`, `TrueHD`, `AVI`, `WAV`, `RF64`, `W64`, `AIFF`, `AU`, `AMR`, `AMRWB`, `CAF`, `WavPack`, `APE`, `Musepack`, `TTA`, `Shorten`, `MIDI`, `DSF`, `DFF`, `TrackerModule`, `FLV`, `OGG`, `RealMedia`, `IVF`, `Y4M`, `WebVTT`, `SCC`, `MCC`, `TTML`, `EbuStl`, `SAMI`, `MicroDVD`, `SubViewer`, `LRC`, `PGS`, `VobSubIdx`, `CheetahCAP`, `PAC`, `SpruceSTL`, `HEIF`, `AVIF`, `AVIFSequence`, or nothing found |
+//! | 1     | `CCX_SM_TRANSPORT`               | `TS`, `M2TS`           |
+//! | 2     | `CCX_SM_PROGRAM`                 | `PS`, `TivoPS`, `VobSubPS`, `CDXA`, `VOB` |
+//! | 3     | `CCX_SM_ASF`                     | `ASF`                  |
+//! | 4     | `CCX_SM_MCPOODLESRAW`            | `McPoodlesRaw`         |
+//! | 5     | `CCX_SM_RCWT`                    | `RCWT`                 |
+//! | 6     | `CCX_SM_MYTH`                    | never, user selected   |
+//! | 7     | `CCX_SM_MP4`                     | `MP4`, `MOV`, `ThreeGP`, `ThreeG2`, `M4A`, `FragmentedMP4` |
+//! | 8     | `CCX_SM_HEX_DUMP`                | never, user selected   |
+//! | 9     | `CCX_SM_WTV`                     | `WTV`                  |
+//! | 10    | `CCX_SM_FFMPEG`                  | never, user selected   |
+//! | 11    | `CCX_SM_GXF`                     | `GXF`                  |
+//! | 12    | `CCX_SM_MKV`                     | `MKV`, `WEBM`, `MKA`   |
+//! | 13    | `CCX_SM_MXF`                     | `MXF`                  |
+//!
+//! CCExtractor has no AVI, WAV, RF64, Wave64, AIFF, AU, CAF, FLV, Ogg, RealMedia, IVF,
+//! Y4M, WebVTT, SCC, MCC, TTML, EBU STL, SAMI, MicroDVD, SubViewer, LRC, PGS, VobSub
+//! index, Cheetah CAP, PAC or Spruce STL mode, without FFmpeg it cannot read them, so
+//! they are "not found". Audio only streams (AAC, MP3, FLAC, WavPack, Monkey's Audio,
+//! Musepack, TTA, Shorten, DSF, DSDIFF, AC-3, E-AC-3, DTS, DTS-HD, TrueHD and AMR),
+//! MIDI files, tracker modules and HEIF and AVIF images carry no captions and are "not
+//! found" too.
+//! Like CCExtractor, an elementary stream and "not found" share 0, and M2TS and TiVo
+//! are told apart from TS and PS by flags (`ctx->m2ts`, `ctx->strangeheader`) rather
+//! than their own modes. VobSub, Video CD and DVD VOB files are PS to CCExtractor, it
+//! finds the pack headers in them.
+
+use crate::{ContainerType, Detector};
+use std::os::raw::c_int;
+use std::slice;
+
+/// `CCX_SM_ELEMENTARY_OR_NOT_FOUND`
+pub const CCX_SM_ELEMENTARY_OR_NOT_FOUND: c_int = 0;
+/// `CCX_SM_TRANSPORT`
+pub const CCX_SM_TRANSPORT: c_int = 1;
+/// `CCX_SM_PROGRAM`
+pub const CCX_SM_PROGRAM: c_int = 2;
+/// `CCX_SM_ASF`
+pub const CCX_SM_ASF: c_int = 3;
+/// `CCX_SM_MCPOODLESRAW`
+pub const CCX_SM_MCPOODLESRAW: c_int = 4;
+/// `CCX_SM_RCWT`
+pub const CCX_SM_RCWT: c_int = 5;
+/// `CCX_SM_MYTH`, only selected by the user.
+pub const CCX_SM_MYTH: c_int = 6;
+/// `CCX_SM_MP4`
+pub const CCX_SM_MP4: c_int = 7;
+/// `CCX_SM_WTV`
+pub const CCX_SM_WTV: c_int = 9;
+/// `CCX_SM_GXF`
+pub const CCX_SM_GXF: c_int = 11;
+/// `CCX_SM_MKV`
+pub const CCX_SM_MKV: c_int = 12;
+/// `CCX_SM_MXF`
+pub const CCX_SM_MXF: c_int = 13;
+
+/// `STARTBYTESLENGTH`, how many starting bytes CCExtractor buffers for detection.
+/// Bytes past it are ignored, the same amount `from_file` reads.
+pub const CCX_STARTBYTESLENGTH: usize = 1024 * 1024;
+
+/// CCExtractor stream mode for a detection result, `None` being nothing found.
+pub fn stream_mode(kind: Option<ContainerType>) -> c_int {
+    match kind {
+        None
+        | Some(ContainerType::ES)
+        | Some(ContainerType::H264ES)
+        | Some(ContainerType::H265ES)
+        | Some(ContainerType::MPEG4ES)
+        | Some(ContainerType::AV1OBU)
+        | Some(ContainerType::AACADTS)
+        | Some(ContainerType::AACLATM)
+        | Some(ContainerType::MP3)
+        | Some(ContainerType::FLAC)
+        | Some(ContainerType::AC3)
+        | Some(ContainerType::EAC3)
+        | Some(ContainerType::DTS)
+        | Some(ContainerType::DTSHD)
+        | Some(ContainerType::TrueHD)
+        | Some(ContainerType::AVI)
+        | Some(ContainerType::WAV)
+        | Some(ContainerType::RF64)
+        | Some(ContainerType::W64)
+        | Some(ContainerType::AIFF)
+        | Some(ContainerType::AU)
+        | Some(ContainerType::AMR)
+        | Some(ContainerType::AMRWB)
+        | Some(ContainerType::CAF)
+        | Some(ContainerType::WavPack)
+        | Some(ContainerType::APE)
+        | Some(ContainerType::Musepack)
+        | Some(ContainerType::TTA)
+        | Some(ContainerType::Shorten)
+        | Some(ContainerType::MIDI)
+        | Some(ContainerType::DSF)
+        | Some(ContainerType::DFF)
+        | Some(ContainerType::TrackerModule)
+        | Some(ContainerType::FLV)
+        | Some(ContainerType::OGG)
+        | Some(ContainerType::RealMedia)
+        | Some(ContainerType::IVF)
+        | Some(ContainerType::Y4M)
+        | Some(ContainerType::WebVTT)
+        | Some(ContainerType::SCC)
+        | Some(ContainerType::MCC)
+        | Some(ContainerType::TTML)
+        | Some(ContainerType::EbuStl)
+        | Some(ContainerType::SAMI)
+        | Some(ContainerType::MicroDVD)
+        | Some(ContainerType::SubViewer)
+        | Some(ContainerType::LRC)
+        | Some(ContainerType::PGS)
+        | Some(ContainerType::VobSubIdx)
+        | Some(ContainerType::CheetahCAP)
+        | Some(ContainerType::PAC)
+        | Some(ContainerType::SpruceSTL)
+        | Some(ContainerType::HEIF)
+        | Some(ContainerType::AVIF)
+        | Some(ContainerType::AVIFSequence) => CCX_SM_ELEMENTARY_OR_NOT_FOUND,
+        Some(ContainerType::TS) | Some(ContainerType::M2TS) => CCX_SM_TRANSPORT,
+        Some(ContainerType::PS)
+        | Some(ContainerType::TivoPS)
+        | Some(ContainerType::VobSubPS)
+        | Some(ContainerType::CDXA)
+        | Some(ContainerType::VOB) => CCX_SM_PROGRAM,
+        Some(ContainerType::ASF) => CCX_SM_ASF,
+        Some(ContainerType::McPoodlesRaw) => CCX_SM_MCPOODLESRAW,
+        Some(ContainerType::RCWT) => CCX_SM_RCWT,
+        Some(ContainerType::MP4)
+        | Some(ContainerType::MOV)
+        | Some(ContainerType::ThreeGP)
+        | Some(ContainerType::ThreeG2)
+        | Some(ContainerType::M4A)
+        | Some(ContainerType::FragmentedMP4) => CCX_SM_MP4,
+        Some(ContainerType::WTV) => CCX_SM_WTV,
+        Some(ContainerType::GXF) => CCX_SM_GXF,
+        Some(ContainerType::MKV) | Some(ContainerType::WEBM) | Some(ContainerType::MKA) => {
+            CCX_SM_MKV
+        }
+        Some(ContainerType::MXF) => CCX_SM_MXF,
+    }
+}
+
+/// Detects the stream mode of `startbytes`, CCExtractor's `ctx->startbytes` and
+/// `ctx->startbytes_avail`.
+/// `m2ts` and `strangeheader`, when not null, are set to 1 for M2TS and TiVo and 0
+/// otherwise, like the `ctx` fields of the same names.
+///
+/// # Safety
+/// `startbytes` must point to `avail` readable bytes, or be null (nothing found).
+/// `m2ts` and `strangeheader` must each be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn media_infer_ccx_detect_stream_type(
+    startbytes: *const u8,
+    avail: usize,
+    m2ts: *mut c_int,
+    strangeheader: *mut c_int,
+) -> c_int {
+    let kind = if startbytes.is_null() {
+        None
+    } else {
+        let buffer = slice::from_raw_parts(startbytes, avail.min(CCX_STARTBYTESLENGTH));
+        Detector::default().detect(buffer).ok()
+    };
+    if !m2ts.is_null() {
+        *m2ts = (kind == Some(ContainerType::M2TS)) as c_int;
+    }
+    if !strangeheader.is_null() {
+        *strangeheader = (kind == Some(ContainerType::TivoPS)) as c_int;
+    }
+    stream_mode(kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samples::generate_sample;
+    use crate::DEFAULT_PRIORITY;
+    use std::ptr;
+
+    #[test]
+    fn stream_mode() {
+        let expected = [
+            (ContainerType::ASF, 3),
+            (ContainerType::MKV, 12),
+            (ContainerType::GXF, 11),
+            (ContainerType::WTV, 9),
+            (ContainerType::RCWT, 5),
+            (ContainerType::MP4, 7),
+            (ContainerType::TivoPS, 2),
+            (ContainerType::VobSubPS, 2),
+            (ContainerType::CDXA, 2),
+            (ContainerType::VOB, 2),
+            (ContainerType::ES, 0),
+            (ContainerType::TS, 1),
+            (ContainerType::M2TS, 1),
+            (ContainerType::MXF, 13),
+            (ContainerType::PS, 2),
+            (ContainerType::McPoodlesRaw, 4),
+            (ContainerType::H264ES, 0),
+            (ContainerType::H265ES, 0),
+            (ContainerType::AVI, 0),
+            (ContainerType::WAV, 0),
+            (ContainerType::RF64, 0),
+            (ContainerType::W64, 0),
+            (ContainerType::AIFF, 0),
+            (ContainerType::AU, 0),
+            (ContainerType::AMR, 0),
+            (ContainerType::AMRWB, 0),
+            (ContainerType::CAF, 0),
+            (ContainerType::WavPack, 0),
+            (ContainerType::APE, 0),
+            (ContainerType::Musepack, 0),
+            (ContainerType::TTA, 0),
+            (ContainerType::Shorten, 0),
+            (ContainerType::MIDI, 0),
+            (ContainerType::DSF, 0),
+            (ContainerType::DFF, 0),
+            (ContainerType::TrackerModule, 0),
+            (ContainerType::FLV, 0),
+            (ContainerType::OGG, 0),
+            (ContainerType::WEBM, 12),
+            (ContainerType::MKA, 12),
+            (ContainerType::MOV, 7),
+            (ContainerType::ThreeGP, 7),
+            (ContainerType::ThreeG2, 7),
+            (ContainerType::M4A, 7),
+            (ContainerType::FragmentedMP4, 7),
+            (ContainerType::HEIF, 0),
+            (ContainerType::AVIF, 0),
+            (ContainerType::AVIFSequence, 0),
+            (ContainerType::RealMedia, 0),
+            (ContainerType::IVF, 0),
+            (ContainerType::Y4M, 0),
+            (ContainerType::WebVTT, 0),
+            (ContainerType::SCC, 0),
+            (ContainerType::MCC, 0),
+            (ContainerType::TTML, 0),
+            (ContainerType::EbuStl, 0),
+            (ContainerType::SAMI, 0),
+            (ContainerType::MicroDVD, 0),
+            (ContainerType::SubViewer, 0),
+            (ContainerType::LRC, 0),
+            (ContainerType::PGS, 0),
+            (ContainerType::VobSubIdx, 0),
+            (ContainerType::CheetahCAP, 0),
+            (ContainerType::PAC, 0),
+            (ContainerType::SpruceSTL, 0),
+            (ContainerType::MPEG4ES, 0),
+            (ContainerType::AV1OBU, 0),
+            (ContainerType::AACADTS, 0),
+            (ContainerType::AACLATM, 0),
+            (ContainerType::MP3, 0),
+            (ContainerType::FLAC, 0),
+            (ContainerType::AC3, 0),
+            (ContainerType::EAC3, 0),
+            (ContainerType::DTS, 0),
+            (ContainerType::DTSHD, 0),
+            (ContainerType::TrueHD, 0),
+        ];
+        assert_eq!(expected.len(), DEFAULT_PRIORITY.len());
+        for (kind, mode) in expected {
+            assert_eq!(super::stream_mode(Some(kind)), mode, "{:?}", kind);
+        }
+        assert_eq!(super::stream_mode(None), 0);
+    }
+
+    #[test]
+    fn detect_stream_type() {
+        let mut m2ts = -1;
+        let mut strangeheader = -1;
+        for kind in DEFAULT_PRIORITY {
+            let sample = generate_sample(*kind);
+            let t = unsafe {
+                media_infer_ccx_detect_stream_type(
+                    sample.as_ptr(),
+                    sample.len(),
+                    &mut m2ts,
+                    &mut strangeheader,
+                )
+            };
+            assert_eq!(t, super::stream_mode(Some(*kind)), "{:?}", kind);
+            assert_eq!(m2ts, (*kind == ContainerType::M2TS) as c_int);
+            assert_eq!(strangeheader, (*kind == ContainerType::TivoPS) as c_int);
+        }
+
+        let garbage = [0x42; 64];
+        let t = unsafe {
+            media_infer_ccx_detect_stream_type(
+                garbage.as_ptr(),
+                garbage.len(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(t, CCX_SM_ELEMENTARY_OR_NOT_FOUND);
+        let t = unsafe {
+            media_infer_ccx_detect_stream_type(ptr::null(), 10, ptr::null_mut(), ptr::null_mut())
+        };
+        assert_eq!(t, CCX_SM_ELEMENTARY_OR_NOT_FOUND);
+    }
+
+    #[test]
+    fn startbytes_length() {
+        // A PS pack past CCExtractor's buffer is not seen.
+        let mut buffer = vec![0; CCX_STARTBYTESLENGTH + 64];
+        buffer[CCX_STARTBYTESLENGTH..CCX_STARTBYTESLENGTH + 4]
+            .copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+        let t = unsafe {
+            media_infer_ccx_detect_stream_type(
+                buffer.as_ptr(),
+                buffer.len(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        assert_eq!(t, CCX_SM_ELEMENTARY_OR_NOT_FOUND);
+    }
+}
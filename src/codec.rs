@@ -0,0 +1,142 @@
+//! tokio-util codec that detects the container at the start of a byte stream.
+
+use crate::{Detection, Detector};
+use bytes::BytesMut;
+use std::io;
+use tokio_util::codec::Decoder;
+
+/// Item of [`ContainerSniffCodec`].
+#[derive(Debug)]
+pub enum SniffFrame {
+    /// The container, always the first item and only sent once.
+    Detection(Detection),
+    /// Stream bytes, starting from the first one. Sent after the detection.
+    Data(BytesMut),
+}
+
+/// Decoder that buffers the start of a stream until its container is known (or the
+/// cap is hit), yields a single [`SniffFrame::Detection`] and then passes every byte,
+/// including the buffered ones, through untouched as [`SniffFrame::Data`].
+///
+/// Uses the same logic as [`StreamDetector`](crate::StreamDetector): the detection
+/// is what `Detector::detect` returns for the first `cap` bytes. Callers that want to
+/// swap codecs once the container is known can check [`Self::is_detected`] and take
+/// the buffer back with `FramedRead::into_parts`, nothing has been consumed from it.
+#[derive(Debug)]
+pub struct ContainerSniffCodec {
+    detector: Detector,
+    cap: usize,
+    detected: bool,
+}
+
+impl ContainerSniffCodec {
+    /// Buffers up to `detector.recommended_probe_len()` bytes.
+    pub fn new(detector: Detector) -> Self {
+        let cap = detector.recommended_probe_len();
+        Self {
+            detector,
+            cap,
+            detected: false,
+        }
+    }
+
+    /// Buffer at most `cap` bytes before deciding.
+    pub fn with_cap(mut self, cap: usize) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Whether the detection has been yielded.
+    pub fn is_detected(&self) -> bool {
+        self.detected
+    }
+
+    fn sniff(&mut self, src: &mut BytesMut, end: bool) -> Option<SniffFrame> {
+        if self.detected {
+            if src.is_empty() {
+                return None;
+            }
+            return Some(SniffFrame::Data(src.split()));
+        }
+        let result = self.detector.detect_prefix(src, self.cap, end)?;
+        self.detected = true;
+        Some(SniffFrame::Detection(Detection {
+            result,
+            probed: src.len().min(self.cap),
+        }))
+    }
+}
+
+impl Decoder for ContainerSniffCodec {
+    type Item = SniffFrame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(self.sniff(src, false))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(self.sniff(src, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samples::generate_sample;
+    use crate::ContainerType;
+    use tokio_stream::StreamExt;
+    use tokio_util::codec::FramedRead;
+    use tokio_util::io::StreamReader;
+
+    /// Reads `data` in `size` byte chunks through the codec.
+    async fn read_frames(data: &[u8], size: usize, codec: ContainerSniffCodec) -> Vec<SniffFrame> {
+        let chunks: Vec<io::Result<&[u8]>> = data.chunks(size).map(Ok).collect();
+        let reader = StreamReader::new(tokio_stream::iter(chunks));
+        let framed = FramedRead::new(reader, codec);
+        framed.map(|x| x.unwrap()).collect().await
+    }
+
+    #[tokio::test]
+    async fn ts() {
+        let mut data = generate_sample(ContainerType::TS);
+        data.resize(data.len() * 4, 0);
+
+        for size in [1, 188, 1000] {
+            let codec = ContainerSniffCodec::new(Detector::default()).with_cap(4096);
+            let frames = read_frames(&data, size, codec).await;
+            match &frames[0] {
+                SniffFrame::Detection(x) => {
+                    assert_eq!(x.result.as_ref().ok(), Some(&ContainerType::TS));
+                    assert_eq!(x.probed, 4096);
+                }
+                x => panic!("expected detection, got {:?}", x),
+            }
+            let mut passed = Vec::new();
+            for frame in &frames[1..] {
+                match frame {
+                    SniffFrame::Data(x) => passed.extend_from_slice(x),
+                    x => panic!("expected data, got {:?}", x),
+                }
+            }
+            assert_eq!(passed, data);
+        }
+    }
+
+    #[tokio::test]
+    async fn short_stream() {
+        let data = generate_sample(ContainerType::TS);
+        let frames = read_frames(&data, 100, ContainerSniffCodec::new(Detector::default())).await;
+        assert_eq!(frames.len(), 2);
+        match &frames[0] {
+            SniffFrame::Detection(x) => {
+                assert_eq!(x.result.as_ref().ok(), Some(&ContainerType::TS));
+                assert_eq!(x.probed, data.len());
+            }
+            x => panic!("expected detection, got {:?}", x),
+        }
+
+        let frames = read_frames(&[], 100, ContainerSniffCodec::new(Detector::default())).await;
+        assert_eq!(frames.len(), 1);
+    }
+}
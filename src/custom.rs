@@ -0,0 +1,183 @@
+use crate::bytes::slice_at;
+use crate::ContainerType;
+use std::fmt;
+
+/// A format unknown to the crate, described by fixed bytes.
+/// Register it with [`DetectorBuilder::custom_format`](crate::DetectorBuilder::custom_format)
+/// or [`Detector::load_definitions`](crate::Detector::load_definitions), then detect
+/// with [`Detector::detect_named`](crate::Detector::detect_named).
+///
+/// # Example
+/// ```rust
+/// use media_infer::{CustomFormat, Detected, Detector};
+///
+/// let format = CustomFormat::new("acme-raw").rule(0, b"ACME").masked_rule(6, &[0x10], &[0xF0]);
+/// let detector = Detector::builder().custom_format(format).build();
+///
+/// let buf = *b"ACME\x00\x00\x1Frest";
+/// assert_eq!(detector.detect_named(&buf).unwrap().name(), "acme-raw");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomFormat {
+    name: String,
+    rules: Vec<Rule>,
+    min_len: usize,
+    payload_offset: Option<usize>,
+}
+
+/// Bytes at an offset, compared after masking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// Offset from the start of the buffer.
+    pub offset: usize,
+    /// Expected bytes, already masked.
+    pub pattern: Vec<u8>,
+    /// Bits of each byte that are compared, same length as `pattern`.
+    pub mask: Vec<u8>,
+}
+
+impl Rule {
+    fn matches(&self, buffer: &[u8]) -> bool {
+        match slice_at(buffer, self.offset, self.pattern.len()) {
+            Some(bytes) => bytes
+                .iter()
+                .zip(self.mask.iter().zip(&self.pattern))
+                .all(|(b, (m, p))| b & m == *p),
+            None => false,
+        }
+    }
+
+    /// Offset of the byte after the rule, saturating.
+    fn end(&self) -> usize {
+        self.offset.saturating_add(self.pattern.len())
+    }
+}
+
+impl CustomFormat {
+    /// Format called `name` without rules, which matches any buffer.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            rules: Vec::new(),
+            min_len: 0,
+            payload_offset: None,
+        }
+    }
+
+    /// Also require `pattern` at `offset`.
+    pub fn rule(self, offset: usize, pattern: &[u8]) -> Self {
+        self.masked_rule(offset, pattern, &vec![0xFF; pattern.len()])
+    }
+
+    /// Also require the bytes at `offset`, ANDed with `mask`, to be `pattern` ANDed
+    /// with `mask`.
+    ///
+    /// # Panics
+    /// If `pattern` and `mask` have different lengths.
+    pub fn masked_rule(mut self, offset: usize, pattern: &[u8], mask: &[u8]) -> Self {
+        assert_eq!(pattern.len(), mask.len(), "pattern and mask lengths differ");
+        let pattern = pattern.iter().zip(mask).map(|(p, m)| p & m).collect();
+        self.rules.push(Rule {
+            offset,
+            pattern,
+            mask: mask.to_vec(),
+        });
+        self
+    }
+
+    /// Buffers shorter than `min_len` never match, even if the rules end earlier.
+    /// Default is where the last rule ends.
+    pub fn min_len(mut self, min_len: usize) -> Self {
+        self.min_len = min_len;
+        self
+    }
+
+    /// Where the wrapped stream starts, for formats that are a header in front of
+    /// another container (eg. a DVR header before plain TS). Used by
+    /// `Detector::detect_nested`.
+    /// Default is `None`, the payload is not looked at.
+    pub fn payload_offset(mut self, offset: usize) -> Self {
+        self.payload_offset = Some(offset);
+        self
+    }
+
+    /// Name the format was registered with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Offset of the wrapped stream, see `payload_offset`.
+    pub fn payload_start(&self) -> Option<usize> {
+        self.payload_offset
+    }
+
+    /// Rules, all of which have to match.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Shortest buffer that can match.
+    pub fn needed(&self) -> usize {
+        self.rules
+            .iter()
+            .map(Rule::end)
+            .fold(self.min_len, usize::max)
+    }
+
+    /// Whether `buffer` matches every rule.
+    pub fn matches(&self, buffer: &[u8]) -> bool {
+        buffer.len() >= self.needed() && self.rules.iter().all(|x| x.matches(buffer))
+    }
+}
+
+/// Result of [`Detector::detect_named`](crate::Detector::detect_named).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detected<'a> {
+    /// One of the built in containers.
+    Container(ContainerType),
+    /// A registered custom format.
+    Custom(&'a CustomFormat),
+}
+
+impl Detected<'_> {
+    /// `ContainerType::short_name` for built in containers, the registered name for
+    /// custom formats.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Container(kind) => kind.short_name(),
+            Self::Custom(format) => format.name(),
+        }
+    }
+}
+
+impl fmt::Display for Detected<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Container(kind) => kind.fmt(f),
+            Self::Custom(format) => f.write_str(format.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masked() {
+        let format = CustomFormat::new("x").masked_rule(2, &[0xAB, 0x01], &[0xF0, 0xFF]);
+        assert_eq!(format.rules()[0].pattern, [0xA0, 0x01]);
+        assert_eq!(format.needed(), 4);
+        assert!(format.matches(&[0, 0, 0xA7, 0x01]));
+        assert!(!format.matches(&[0, 0, 0xB0, 0x01]));
+        assert!(!format.matches(&[0, 0, 0xA0]));
+        assert!(!format.clone().min_len(8).matches(&[0, 0, 0xA7, 0x01]));
+    }
+
+    #[test]
+    fn huge_offset() {
+        let format = CustomFormat::new("x").rule(usize::MAX, &[1]);
+        assert_eq!(format.needed(), usize::MAX);
+        assert!(!format.matches(&[1; 16]));
+    }
+}
@@ -0,0 +1,210 @@
+//! Custom format definitions files, see `Detector::load_definitions`.
+
+use crate::{CustomFormat, Error};
+use serde::Deserialize;
+use std::ops::Range;
+use toml::Spanned;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Definitions {
+    #[serde(default)]
+    format: Vec<Spanned<FormatDef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FormatDef {
+    name: Spanned<String>,
+    min_len: Option<usize>,
+    payload_offset: Option<usize>,
+    #[serde(default)]
+    rule: Vec<Spanned<RuleDef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDef {
+    offset: usize,
+    pattern: Spanned<String>,
+    mask: Option<Spanned<String>>,
+}
+
+/// Parses the formats of a definitions file.
+pub(crate) fn parse(text: &str) -> Result<Vec<CustomFormat>, Error> {
+    let definitions: Definitions = toml::from_str(text).map_err(|e| Error::InvalidDefinitions {
+        line: e.span().map_or(1, |x| line(text, x)),
+        field: None,
+        message: e.message().to_owned(),
+    })?;
+
+    let mut formats: Vec<CustomFormat> = Vec::new();
+    for (i, def) in definitions.format.iter().enumerate() {
+        let invalid =
+            |span: Range<usize>, field: String, message: String| Error::InvalidDefinitions {
+                line: line(text, span),
+                field: Some(field),
+                message,
+            };
+        let def_span = def.span();
+        let def = def.get_ref();
+        let name = def.name.get_ref();
+        if name.is_empty() {
+            let field = format!("format[{}].name", i);
+            return Err(invalid(def.name.span(), field, "is empty".to_owned()));
+        }
+        if formats.iter().any(|x| x.name() == name) {
+            let field = format!("format[{}].name", i);
+            let message = format!("`{}` is defined twice", name);
+            return Err(invalid(def.name.span(), field, message));
+        }
+        if def.rule.is_empty() {
+            let field = format!("format[{}].rule", i);
+            return Err(invalid(
+                def_span,
+                field,
+                "needs at least one rule".to_owned(),
+            ));
+        }
+
+        let mut format = CustomFormat::new(name);
+        for (j, rule) in def.rule.iter().enumerate() {
+            let rule = rule.get_ref();
+            let field = |name: &str| format!("format[{}].rule[{}].{}", i, j, name);
+            let pattern = hex(rule.pattern.get_ref())
+                .map_err(|x| invalid(rule.pattern.span(), field("pattern"), x))?;
+            if pattern.is_empty() {
+                let message = "is empty".to_owned();
+                return Err(invalid(rule.pattern.span(), field("pattern"), message));
+            }
+            let mask = match &rule.mask {
+                Some(mask) => {
+                    let bytes =
+                        hex(mask.get_ref()).map_err(|x| invalid(mask.span(), field("mask"), x))?;
+                    if bytes.len() != pattern.len() {
+                        let message = format!(
+                            "has {} bytes, the pattern has {}",
+                            bytes.len(),
+                            pattern.len()
+                        );
+                        return Err(invalid(mask.span(), field("mask"), message));
+                    }
+                    bytes
+                }
+                None => vec![0xFF; pattern.len()],
+            };
+            format = format.masked_rule(rule.offset, &pattern, &mask);
+        }
+        if let Some(min_len) = def.min_len {
+            format = format.min_len(min_len);
+        }
+        if let Some(offset) = def.payload_offset {
+            format = format.payload_offset(offset);
+        }
+        formats.push(format);
+    }
+    Ok(formats)
+}
+
+/// 1 based line of the start of `span`.
+fn line(text: &str, span: Range<usize>) -> usize {
+    let start = span.start.min(text.len());
+    text.as_bytes()[..start]
+        .iter()
+        .filter(|x| **x == b'\n')
+        .count()
+        + 1
+}
+
+/// Bytes of hex pairs, optionally separated by whitespace, eg. "41 43 4d45".
+fn hex(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for word in text.split_whitespace() {
+        if word.len() % 2 != 0 {
+            return Err(format!("`{}` is not a whole number of hex bytes", word));
+        }
+        for pair in word.as_bytes().chunks(2) {
+            let pair = std::str::from_utf8(pair).unwrap_or("");
+            let byte = u8::from_str_radix(pair, 16)
+                .map_err(|_| format!("`{}` is not a hex byte", pair))?;
+            bytes.push(byte);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> (usize, Option<String>, String) {
+        match parse(text) {
+            Err(Error::InvalidDefinitions {
+                line,
+                field,
+                message,
+            }) => (line, field, message),
+            x => panic!("expected InvalidDefinitions, got {:?}", x.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn hex_bytes() {
+        assert_eq!(hex("41 43 4d45").unwrap(), [0x41, 0x43, 0x4d, 0x45]);
+        assert!(hex("4").is_err());
+        assert!(hex("4G").is_err());
+        assert!(hex("é1").is_err());
+    }
+
+    #[test]
+    fn mask_length() {
+        let text = "[[format]]\nname = \"x\"\n\n[[format.rule]]\noffset = 0\npattern = \"41 42\"\nmask = \"FF\"\n";
+        let (line, field, message) = error(text);
+        assert_eq!(line, 7);
+        assert_eq!(field.as_deref(), Some("format[0].rule[0].mask"));
+        assert_eq!(message, "has 1 bytes, the pattern has 2");
+    }
+
+    #[test]
+    fn bad_pattern() {
+        let text = "[[format]]\nname = \"x\"\n[[format.rule]]\noffset = 0\npattern = \"4\"\n[[format]]\nname = \"y\"\n";
+        let (line, field, _) = error(text);
+        assert_eq!(line, 5);
+        assert_eq!(field.as_deref(), Some("format[0].rule[0].pattern"));
+    }
+
+    #[test]
+    fn missing_field() {
+        let text = "[[format]]\nname = \"x\"\n\n[[format.rule]]\npattern = \"41\"\n";
+        let (line, field, message) = error(text);
+        assert_eq!(line, 4);
+        assert_eq!(field, None);
+        assert!(message.contains("offset"), "{}", message);
+    }
+
+    #[test]
+    fn unknown_field() {
+        let (line, _, message) = error("[[format]]\nname = \"x\"\nmagic = 1\n");
+        assert_eq!(line, 3);
+        assert!(message.contains("magic"), "{}", message);
+    }
+
+    #[test]
+    fn duplicate_name() {
+        let rule = "[[format.rule]]\noffset = 0\npattern = \"41\"\n";
+        let text = format!(
+            "[[format]]\nname = \"x\"\n{0}[[format]]\nname = \"x\"\n{0}",
+            rule
+        );
+        let (line, field, _) = error(&text);
+        assert_eq!(line, 7);
+        assert_eq!(field.as_deref(), Some("format[1].name"));
+    }
+
+    #[test]
+    fn no_rules() {
+        let (line, field, _) = error("\n[[format]]\nname = \"x\"\n");
+        assert_eq!(line, 2);
+        assert_eq!(field.as_deref(), Some("format[0].rule"));
+    }
+}
@@ -0,0 +1,1291 @@
+use crate::diagnose::{self, DiagnosisReport, NearMiss};
+use crate::{heuristic, nested};
+use crate::{
+    ContainerType, CustomFormat, Detected, Error, DEFAULT_PS_WINDOW, DEFAULT_TS_SYNC_THRESHOLD,
+    TS_PACKETS_TO_PROBE, TS_PACKET_LEN,
+};
+use std::convert::TryFrom;
+
+/// Configurable container detection.
+/// `ContainerType::from_bytes` and friends use the default configuration.
+///
+/// Checks run in [`DEFAULT_PRIORITY`] order unless overridden with
+/// [`DetectorBuilder::priority`]. The first match wins.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{ContainerType, Detector};
+///
+/// // Matroska Segment followed by a SeekHead, without the EBML header.
+/// let buf = [0x18, 0x53, 0x80, 0x67, 0x01, 0, 0, 0, 0, 0, 0, 0x10, 0x11, 0x4d, 0x9b, 0x74];
+/// assert!(Detector::default().detect(&buf).is_err());
+///
+/// let detector = Detector::builder().lenient(true).build();
+/// assert_eq!(detector.detect(&buf).ok(), Some(ContainerType::MKV));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Detector {
+    options: Options,
+    checks: Vec<&'static Check>,
+    custom: Vec<CustomFormat>,
+    /// Elementary streams the heuristics may report, empty when they are off.
+    heuristics: Vec<ContainerType>,
+}
+
+/// How sure a detection is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    /// Guessed by the ES heuristics, see [`DetectorBuilder::es_heuristics`].
+    Weak,
+    /// Found by a check.
+    Strong,
+}
+
+/// Knobs read by the checks.
+#[derive(Debug, Clone)]
+pub(crate) struct Options {
+    /// Accept weaker evidence, like a Matroska Segment without the EBML header.
+    pub(crate) lenient: bool,
+    /// Return `Error::Ambiguous` instead of the highest priority match.
+    pub(crate) strict_ambiguity: bool,
+    /// Number of probed TS packets that need a valid header.
+    pub(crate) ts_sync_threshold: usize,
+    /// Number of bytes searched for a PS pack header.
+    pub(crate) ps_window: usize,
+    /// Look for well known non media magics before running the checks.
+    pub(crate) non_media_check: bool,
+    /// Score headerless elementary streams when no check matches, with `lenient`.
+    pub(crate) es_heuristics: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            lenient: false,
+            strict_ambiguity: false,
+            ts_sync_threshold: DEFAULT_TS_SYNC_THRESHOLD,
+            ps_window: DEFAULT_PS_WINDOW,
+            non_media_check: true,
+            es_heuristics: false,
+        }
+    }
+}
+
+/// Order in which the checks run by default, which is also the priority when
+/// several containers match.
+///
+/// Cheapest first: the ones comparing bytes at fixed offsets, then the ones walking
+/// a few packets, then the ones scanning through the buffer.
+pub const DEFAULT_PRIORITY: &[ContainerType] = &[
+    ContainerType::ASF,
+    ContainerType::GXF,
+    ContainerType::WTV,
+    ContainerType::RCWT,
+    ContainerType::AVI,
+    ContainerType::WAV,
+    ContainerType::CDXA,
+    ContainerType::RF64,
+    ContainerType::W64,
+    ContainerType::AIFF,
+    ContainerType::AU,
+    ContainerType::AMRWB,
+    ContainerType::AMR,
+    ContainerType::CAF,
+    ContainerType::WavPack,
+    ContainerType::APE,
+    ContainerType::Musepack,
+    ContainerType::TTA,
+    ContainerType::Shorten,
+    ContainerType::MIDI,
+    ContainerType::DFF,
+    ContainerType::FLV,
+    ContainerType::OGG,
+    ContainerType::RealMedia,
+    ContainerType::IVF,
+    ContainerType::FLAC,
+    ContainerType::WebVTT,
+    ContainerType::EbuStl,
+    ContainerType::CheetahCAP,
+    ContainerType::TivoPS,
+    ContainerType::ES,
+    ContainerType::DSF,
+    ContainerType::SCC,
+    ContainerType::MCC,
+    ContainerType::TTML,
+    ContainerType::SAMI,
+    ContainerType::MicroDVD,
+    ContainerType::SubViewer,
+    ContainerType::LRC,
+    ContainerType::PGS,
+    ContainerType::VobSubIdx,
+    ContainerType::VobSubPS,
+    ContainerType::VOB,
+    ContainerType::SpruceSTL,
+    ContainerType::Y4M,
+    ContainerType::WEBM,
+    ContainerType::MKA,
+    ContainerType::MKV,
+    ContainerType::MOV,
+    ContainerType::HEIF,
+    ContainerType::AVIF,
+    ContainerType::AVIFSequence,
+    ContainerType::ThreeGP,
+    ContainerType::ThreeG2,
+    ContainerType::M4A,
+    ContainerType::MP4,
+    ContainerType::FragmentedMP4,
+    ContainerType::TS,
+    ContainerType::M2TS,
+    ContainerType::H264ES,
+    ContainerType::H265ES,
+    ContainerType::MPEG4ES,
+    ContainerType::AV1OBU,
+    ContainerType::McPoodlesRaw,
+    ContainerType::AACADTS,
+    ContainerType::AACLATM,
+    ContainerType::MP3,
+    ContainerType::TrueHD,
+    ContainerType::AC3,
+    ContainerType::EAC3,
+    ContainerType::DTSHD,
+    ContainerType::DTS,
+    ContainerType::TrackerModule,
+    ContainerType::PAC,
+    ContainerType::MXF,
+    ContainerType::PS,
+];
+
+/// Elementary streams the ES heuristics can report.
+const HEURISTIC_KINDS: [ContainerType; 3] = [
+    ContainerType::ES,
+    ContainerType::H264ES,
+    ContainerType::H265ES,
+];
+
+/// Magics of common files that are not media, checked before the real checks.
+const NON_MEDIA: [(&[u8], &str); 8] = [
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"%PDF", "PDF document"),
+    (b"\x89PNG", "PNG image"),
+    (b"\xFF\xD8\xFF", "JPEG image"),
+    (b"\x7FELF", "ELF binary"),
+    (b"\x1F\x8B", "gzip archive"),
+    (b"\x28\xB5\x2F\xFD", "zstd archive"),
+    (b"\xFD7zXZ\x00", "xz archive"),
+];
+
+/// Most bytes an `Anchored` check (or the non media check) looks at.
+const ANCHORED_PROBE_LEN: usize = 16;
+
+/// How much work a check does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Cost {
+    /// Compares bytes at fixed offsets.
+    Anchored,
+    /// Walks a bounded number of packets or header elements.
+    Bounded,
+    /// Searches through the buffer.
+    Scan,
+}
+
+/// Entry of the dispatch table.
+struct Check {
+    kind: ContainerType,
+    cost: Cost,
+    /// Buffers shorter than this never match.
+    min_len: usize,
+    /// Leading bytes of every match, empty if they are not fixed.
+    /// Can be only the start of the full magic.
+    magic: &'static [u8],
+    matches: fn(&[u8], &Options) -> bool,
+    /// Explains why a buffer that did not match came close, see `diagnose`.
+    near_miss: Option<NearMissFn>,
+}
+
+/// Explanation of a near miss, `None` if the buffer was not close.
+type NearMissFn = fn(&[u8], &Options) -> Option<String>;
+
+impl Check {
+    /// Length below which this check cannot match, even if the leading bytes are right.
+    fn needed(&self, options: &Options) -> usize {
+        match self.kind {
+            // Enough packets for the sync threshold.
+            ContainerType::TS => TS_PACKET_LEN * (options.ts_sync_threshold - 1) + 4,
+            _ => self.min_len,
+        }
+    }
+}
+
+impl std::fmt::Debug for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Check")
+            .field("kind", &self.kind)
+            .field("cost", &self.cost)
+            .field("min_len", &self.min_len)
+            .field("magic", &self.magic)
+            .finish()
+    }
+}
+
+/// All checks, in `DEFAULT_PRIORITY` order.
+const CHECKS: [Check; 76] = [
+    Check {
+        kind: ContainerType::ASF,
+        cost: Cost::Anchored,
+        min_len: 16,
+        magic: &[0x30, 0x26, 0xb2, 0x75],
+        matches: |b, _| ContainerType::check_asf(b),
+        near_miss: Some(diagnose::asf),
+    },
+    Check {
+        kind: ContainerType::GXF,
+        cost: Cost::Anchored,
+        min_len: 6,
+        magic: &[0, 0, 0, 0, 1, 0xbc],
+        matches: |b, _| ContainerType::check_gxf(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::WTV,
+        cost: Cost::Anchored,
+        min_len: 16,
+        magic: &[0xb7, 0xd8, 0x00, 0x20],
+        matches: |b, _| ContainerType::check_wtv(b),
+        near_miss: Some(diagnose::wtv),
+    },
+    Check {
+        kind: ContainerType::RCWT,
+        cost: Cost::Anchored,
+        min_len: 11,
+        magic: &[0xCC, 0xCC, 0xED],
+        matches: |b, _| ContainerType::check_rcwt(b),
+        near_miss: Some(diagnose::rcwt),
+    },
+    Check {
+        kind: ContainerType::AVI,
+        cost: Cost::Anchored,
+        min_len: 12,
+        magic: b"RIFF",
+        matches: |b, _| ContainerType::check_avi(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::WAV,
+        cost: Cost::Anchored,
+        min_len: 12,
+        magic: b"RIFF",
+        matches: |b, _| ContainerType::check_wav(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::CDXA,
+        cost: Cost::Anchored,
+        min_len: 12,
+        magic: b"RIFF",
+        matches: |b, _| ContainerType::check_cdxa(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::RF64,
+        cost: Cost::Anchored,
+        min_len: 16,
+        magic: b"RF64",
+        matches: |b, _| ContainerType::check_rf64(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::W64,
+        cost: Cost::Anchored,
+        min_len: 16,
+        magic: b"riff",
+        matches: |b, _| ContainerType::check_w64(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::AIFF,
+        cost: Cost::Anchored,
+        min_len: 12,
+        magic: b"FORM",
+        matches: |b, _| ContainerType::check_aiff(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::AU,
+        cost: Cost::Anchored,
+        min_len: 16,
+        magic: b".snd",
+        matches: |b, _| ContainerType::check_au(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::AMRWB,
+        cost: Cost::Anchored,
+        min_len: 9,
+        magic: b"#!AMR-WB",
+        matches: |b, _| ContainerType::check_amr_wb(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::AMR,
+        cost: Cost::Anchored,
+        min_len: 6,
+        magic: b"#!AMR",
+        matches: |b, _| ContainerType::check_amr(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::CAF,
+        cost: Cost::Anchored,
+        min_len: 12,
+        magic: b"caff",
+        matches: |b, _| ContainerType::check_caf(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::WavPack,
+        cost: Cost::Anchored,
+        min_len: 10,
+        magic: b"wvpk",
+        matches: |b, _| ContainerType::check_wavpack(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::APE,
+        cost: Cost::Anchored,
+        min_len: 6,
+        magic: b"MAC ",
+        matches: |b, _| ContainerType::check_ape(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::Musepack,
+        cost: Cost::Anchored,
+        min_len: 4,
+        magic: b"MP",
+        matches: |b, _| ContainerType::check_musepack(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::TTA,
+        cost: Cost::Anchored,
+        min_len: 6,
+        magic: b"TTA1",
+        matches: |b, _| ContainerType::check_tta(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::Shorten,
+        cost: Cost::Anchored,
+        min_len: 5,
+        magic: b"ajkg",
+        matches: |b, _| ContainerType::check_shorten(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::MIDI,
+        cost: Cost::Anchored,
+        min_len: 10,
+        magic: b"MThd",
+        matches: |b, _| ContainerType::check_midi(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::DFF,
+        cost: Cost::Anchored,
+        min_len: 16,
+        magic: b"FRM8",
+        matches: |b, _| ContainerType::check_dff(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::FLV,
+        cost: Cost::Anchored,
+        min_len: 9,
+        magic: b"FLV\x01",
+        matches: |b, _| ContainerType::check_flv(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::OGG,
+        cost: Cost::Anchored,
+        min_len: 6,
+        magic: b"OggS\x00",
+        matches: |b, o| ContainerType::check_ogg(b, o.lenient),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::RealMedia,
+        cost: Cost::Anchored,
+        min_len: 10,
+        magic: b".RMF",
+        matches: |b, _| ContainerType::check_rm(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::IVF,
+        cost: Cost::Anchored,
+        min_len: 12,
+        magic: b"DKIF",
+        matches: |b, _| ContainerType::check_ivf(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::FLAC,
+        cost: Cost::Anchored,
+        min_len: 8,
+        magic: b"fLaC",
+        matches: |b, _| ContainerType::check_flac(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::WebVTT,
+        cost: Cost::Anchored,
+        min_len: 6,
+        magic: &[],
+        matches: |b, _| ContainerType::check_webvtt(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::EbuStl,
+        cost: Cost::Anchored,
+        min_len: 12,
+        magic: &[],
+        matches: |b, _| ContainerType::check_ebu_stl(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::CheetahCAP,
+        cost: Cost::Anchored,
+        min_len: 4,
+        magic: &[0xEA, 0x22],
+        matches: |b, _| ContainerType::check_cheetah_cap(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::TivoPS,
+        cost: Cost::Anchored,
+        min_len: 4,
+        magic: b"TiVo",
+        matches: |b, _| ContainerType::check_tivo_ps(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::ES,
+        cost: Cost::Anchored,
+        min_len: 4,
+        magic: &[0, 0, 1, 0xB3],
+        matches: |b, _| ContainerType::check_es(b),
+        near_miss: None,
+    },
+    // The parameters are checked up to the end of the header line.
+    Check {
+        kind: ContainerType::DSF,
+        cost: Cost::Bounded,
+        min_len: 32,
+        magic: b"DSD ",
+        matches: |b, _| ContainerType::check_dsf(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::SCC,
+        cost: Cost::Bounded,
+        min_len: 18,
+        magic: &[],
+        matches: |b, _| ContainerType::check_scc(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::MCC,
+        cost: Cost::Bounded,
+        min_len: 31,
+        magic: &[],
+        matches: |b, _| ContainerType::check_mcc(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::TTML,
+        cost: Cost::Bounded,
+        min_len: 37,
+        magic: &[],
+        matches: |b, _| ContainerType::check_ttml(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::SAMI,
+        cost: Cost::Bounded,
+        min_len: 6,
+        magic: &[],
+        matches: |b, _| ContainerType::check_sami(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::MicroDVD,
+        cost: Cost::Bounded,
+        min_len: 13,
+        magic: &[],
+        matches: |b, _| ContainerType::check_microdvd(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::SubViewer,
+        cost: Cost::Bounded,
+        min_len: 13,
+        magic: &[],
+        matches: |b, _| ContainerType::check_subviewer(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::LRC,
+        cost: Cost::Bounded,
+        min_len: 9,
+        magic: &[],
+        matches: |b, _| ContainerType::check_lrc(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::PGS,
+        cost: Cost::Bounded,
+        min_len: 15,
+        magic: b"PG",
+        matches: |b, _| ContainerType::check_pgs(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::VobSubIdx,
+        cost: Cost::Bounded,
+        min_len: 23,
+        magic: b"# VobSub index file, v",
+        matches: |b, _| ContainerType::check_vobsub_idx(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::VobSubPS,
+        cost: Cost::Bounded,
+        min_len: 24,
+        magic: &[0x00, 0x00, 0x01, 0xBA],
+        matches: |b, _| ContainerType::check_vobsub_ps(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::VOB,
+        cost: Cost::Bounded,
+        min_len: 33,
+        magic: &[0x00, 0x00, 0x01, 0xBA],
+        matches: |b, _| ContainerType::check_vob(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::SpruceSTL,
+        cost: Cost::Bounded,
+        min_len: 5,
+        magic: &[],
+        matches: |b, _| ContainerType::check_spruce_stl(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::Y4M,
+        cost: Cost::Bounded,
+        min_len: 10,
+        magic: b"YUV4MPEG2 ",
+        matches: |b, _| ContainerType::check_y4m(b),
+        near_miss: None,
+    },
+    // Bounded rather than anchored: the DocType and tracks telling them apart are not
+    // at fixed offsets.
+    Check {
+        kind: ContainerType::WEBM,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[0x1a, 0x45, 0xdf, 0xa3],
+        matches: |b, _| ContainerType::check_webm(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::MKA,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[0x1a, 0x45, 0xdf, 0xa3],
+        matches: |b, _| ContainerType::check_mka(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::MKV,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[0x1a, 0x45, 0xdf, 0xa3],
+        matches: |b, o| {
+            (ContainerType::check_mkv(b)
+                && !ContainerType::check_webm(b)
+                && !ContainerType::check_mka(b))
+                || (o.lenient && ContainerType::check_mkv_segment(b))
+        },
+        near_miss: Some(diagnose::mkv),
+    },
+    // The atom after the first one is looked at when it is in the buffer.
+    Check {
+        kind: ContainerType::MOV,
+        cost: Cost::Bounded,
+        min_len: 8,
+        magic: &[],
+        matches: |b, _| ContainerType::check_mov(b),
+        near_miss: None,
+    },
+    // Compatible brands can be anywhere in the ftyp box.
+    Check {
+        kind: ContainerType::HEIF,
+        cost: Cost::Bounded,
+        min_len: 16,
+        magic: &[],
+        matches: |b, _| ContainerType::check_heif(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::AVIF,
+        cost: Cost::Bounded,
+        min_len: 16,
+        magic: &[],
+        matches: |b, _| ContainerType::check_avif(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::AVIFSequence,
+        cost: Cost::Bounded,
+        min_len: 16,
+        magic: &[],
+        matches: |b, _| ContainerType::check_avis(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::ThreeGP,
+        cost: Cost::Bounded,
+        min_len: 16,
+        magic: &[],
+        matches: |b, _| ContainerType::check_3gp(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::ThreeG2,
+        cost: Cost::Bounded,
+        min_len: 16,
+        magic: &[],
+        matches: |b, _| ContainerType::check_3g2(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::M4A,
+        cost: Cost::Bounded,
+        min_len: 16,
+        magic: &[],
+        matches: |b, _| ContainerType::check_m4a(b),
+        near_miss: None,
+    },
+    // Bounded too, an audio compatible brand makes it M4A.
+    Check {
+        kind: ContainerType::MP4,
+        cost: Cost::Bounded,
+        min_len: 12,
+        magic: &[],
+        matches: |b, _| ContainerType::check_mp4(b) && !ContainerType::check_m4a(b),
+        near_miss: Some(diagnose::mp4),
+    },
+    Check {
+        kind: ContainerType::FragmentedMP4,
+        cost: Cost::Bounded,
+        min_len: 8,
+        magic: &[],
+        matches: |b, _| ContainerType::check_fmp4(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::TS,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[0x47],
+        matches: |b, o| {
+            ContainerType::check_ts_family(b, o.ts_sync_threshold) == Some(ContainerType::TS)
+        },
+        near_miss: Some(diagnose::ts),
+    },
+    Check {
+        kind: ContainerType::M2TS,
+        cost: Cost::Bounded,
+        min_len: 8,
+        magic: &[],
+        matches: |b, o| {
+            ContainerType::check_ts_family(b, o.ts_sync_threshold) == Some(ContainerType::M2TS)
+        },
+        near_miss: Some(diagnose::m2ts),
+    },
+    // Leading zero padding moves the start code.
+    Check {
+        kind: ContainerType::H264ES,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[],
+        matches: |b, _| ContainerType::check_h264_es(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::H265ES,
+        cost: Cost::Bounded,
+        min_len: 5,
+        magic: &[],
+        matches: |b, _| ContainerType::check_h265_es(b),
+        near_miss: None,
+    },
+    // After ES, whose sequence header start code is a group of VOP one in MPEG-4.
+    Check {
+        kind: ContainerType::MPEG4ES,
+        cost: Cost::Bounded,
+        min_len: 5,
+        magic: &[],
+        matches: |b, _| ContainerType::check_mpeg4_es(b),
+        near_miss: None,
+    },
+    // OBUs before the sequence header are walked.
+    Check {
+        kind: ContainerType::AV1OBU,
+        cost: Cost::Bounded,
+        min_len: 3,
+        magic: &[0x12, 0x00],
+        matches: |b, _| ContainerType::check_av1_obu(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::McPoodlesRaw,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[],
+        matches: |b, _| ContainerType::check_mcpoodles_raw(b),
+        near_miss: None,
+    },
+    // The second frame header follows the first, after an ID3v2 tag of any length.
+    Check {
+        kind: ContainerType::AACADTS,
+        cost: Cost::Bounded,
+        min_len: 14,
+        magic: &[],
+        matches: |b, _| ContainerType::check_aac_adts(b),
+        near_miss: None,
+    },
+    // The sync word is 11 bits, and the second one sits one frame length in.
+    Check {
+        kind: ContainerType::AACLATM,
+        cost: Cost::Bounded,
+        min_len: 7,
+        magic: &[],
+        matches: |b, _| ContainerType::check_aac_latm(b),
+        near_miss: None,
+    },
+    // After AAC ADTS, which can be behind an ID3v2 tag too.
+    Check {
+        kind: ContainerType::MP3,
+        cost: Cost::Bounded,
+        min_len: 10,
+        magic: &[],
+        matches: |b, _| ContainerType::check_mp3(b),
+        near_miss: None,
+    },
+    // Before AC-3, whose frames Blu-ray TrueHD streams carry too.
+    Check {
+        kind: ContainerType::TrueHD,
+        cost: Cost::Bounded,
+        min_len: 10,
+        magic: &[],
+        matches: |b, _| ContainerType::check_truehd(b),
+        near_miss: None,
+    },
+    // The second sync frame is one frame length in, 128 to 3840 bytes.
+    Check {
+        kind: ContainerType::AC3,
+        cost: Cost::Bounded,
+        min_len: 134,
+        magic: &[0x0B, 0x77],
+        matches: |b, _| ContainerType::check_ac3(b),
+        near_miss: None,
+    },
+    // The second sync frame is one frame length in, up to 4096 bytes.
+    Check {
+        kind: ContainerType::EAC3,
+        cost: Cost::Bounded,
+        min_len: 12,
+        magic: &[0x0B, 0x77],
+        matches: |b, _| ContainerType::check_eac3(b),
+        near_miss: None,
+    },
+    // The extension substream may follow a core frame.
+    Check {
+        kind: ContainerType::DTSHD,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[],
+        matches: |b, _| ContainerType::check_dtshd(b),
+        near_miss: None,
+    },
+    // The sync word may follow some zero bytes.
+    Check {
+        kind: ContainerType::DTS,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[],
+        matches: |b, _| ContainerType::check_dts(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::TrackerModule,
+        cost: Cost::Bounded,
+        min_len: 4,
+        magic: &[],
+        matches: |b, _| ContainerType::check_tracker_module(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::PAC,
+        cost: Cost::Bounded,
+        min_len: 39,
+        magic: &[0x01, 0x00, 0x00, 0x00],
+        matches: |b, _| ContainerType::check_pac(b),
+        near_miss: None,
+    },
+    Check {
+        kind: ContainerType::MXF,
+        cost: Cost::Scan,
+        min_len: 14,
+        magic: &[],
+        matches: |b, _| ContainerType::check_mxf(b),
+        near_miss: Some(diagnose::mxf),
+    },
+    Check {
+        kind: ContainerType::PS,
+        cost: Cost::Scan,
+        min_len: 4,
+        magic: &[],
+        matches: |b, o| ContainerType::check_ps(b, o.ps_window),
+        near_miss: Some(diagnose::ps),
+    },
+];
+
+impl Default for Detector {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Detector {
+    /// Start building a Detector with non default options.
+    pub fn builder() -> DetectorBuilder {
+        DetectorBuilder::default()
+    }
+
+    /// Infer Container from a slice of bytes.
+    /// Throws `Error::Unidentified` if identification fails.
+    /// Throws `Error::Ambiguous` if more than one container matches in strict ambiguity mode.
+    /// Throws `Error::NotMedia` if the buffer starts with the magic of a well known non
+    /// media file, unless that check is turned off.
+    /// Returns a guessed elementary stream if the ES heuristics are on and none of the
+    /// checks match, see `detect_with_confidence` to tell them apart.
+    pub fn detect(&self, buffer: &[u8]) -> Result<ContainerType, Error> {
+        self.detect_with_confidence(buffer).map(|x| x.0)
+    }
+
+    /// `detect`, also telling how sure the result is.
+    /// Only ES heuristics results are `Confidence::Weak`.
+    pub fn detect_with_confidence(
+        &self,
+        buffer: &[u8],
+    ) -> Result<(ContainerType, Confidence), Error> {
+        match self.detect_checks(buffer) {
+            Ok(kind) => Ok((kind, Confidence::Strong)),
+            Err(Error::Unidentified) => heuristic::detect(buffer, &self.heuristics)
+                .map(|x| (x, Confidence::Weak))
+                .ok_or(Error::Unidentified),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn detect_checks(&self, buffer: &[u8]) -> Result<ContainerType, Error> {
+        if self.options.non_media_check {
+            if let Some((_, name)) = NON_MEDIA.iter().find(|x| buffer.starts_with(x.0)) {
+                return Err(Error::NotMedia(name));
+            }
+        }
+
+        if self.options.strict_ambiguity {
+            let mut kinds = self.detect_all(buffer);
+            // A refinement always matches the checks of its ancestors too, they do not
+            // make it ambiguous.
+            let refined = kinds.iter().copied().find(|x| {
+                let ancestors: Vec<_> = std::iter::successors(x.parent(), |y| y.parent()).collect();
+                kinds.iter().all(|y| y == x || ancestors.contains(y))
+            });
+            if let Some(x) = refined {
+                kinds = vec![x];
+            }
+            return match kinds.len() {
+                0 => Err(Error::Unidentified),
+                1 => Ok(kinds.remove(0)),
+                _ => Err(Error::Ambiguous(kinds)),
+            };
+        }
+
+        self.matching(buffer).next().ok_or(Error::Unidentified)
+    }
+
+    /// Like `detect`, but also reports the registered custom formats by name.
+    /// Custom formats are tried first, in the order they were registered, and even
+    /// before the non media check.
+    pub fn detect_named(&self, buffer: &[u8]) -> Result<Detected<'_>, Error> {
+        if let Some(format) = self.custom.iter().find(|x| x.matches(buffer)) {
+            return Ok(Detected::Custom(format));
+        }
+        self.detect(buffer).map(Detected::Container)
+    }
+
+    /// Layers of built in containers in `buffer`, outermost first, see
+    /// [`detect_nested`](crate::detect_nested). Custom layers are stripped but not
+    /// reported, `detect_nested_named` has them.
+    pub fn detect_nested(&self, buffer: &[u8]) -> Vec<ContainerType> {
+        self.detect_nested_named(buffer)
+            .into_iter()
+            .filter_map(|x| match x {
+                Detected::Container(kind) => Some(kind),
+                Detected::Custom(_) => None,
+            })
+            .collect()
+    }
+
+    /// `detect_nested` with custom formats reported by name.
+    pub fn detect_nested_named(&self, buffer: &[u8]) -> Vec<Detected<'_>> {
+        let mut layers = Vec::new();
+        let mut payload = std::borrow::Cow::Borrowed(buffer);
+        while layers.len() < nested::MAX_DEPTH {
+            let layer = match self.detect_named(&payload) {
+                Ok(x) => x,
+                Err(_) => break,
+            };
+            layers.push(layer);
+            payload = match nested::payload(&layer, &payload, self.options.ts_sync_threshold) {
+                Some(x) => std::borrow::Cow::Owned(x.into_owned()),
+                None => break,
+            };
+        }
+        layers
+    }
+
+    /// Registered custom formats, in the order they are tried.
+    pub fn custom_formats(&self) -> &[CustomFormat] {
+        &self.custom
+    }
+
+    /// Parse custom formats from a TOML definitions file and register them after the
+    /// ones already there. Nothing is registered if any of them is invalid.
+    ///
+    /// ```toml
+    /// [[format]]
+    /// name = "acme-raw"
+    /// min_len = 32          # optional
+    /// payload_offset = 512  # optional, see `CustomFormat::payload_offset`
+    ///
+    /// [[format.rule]]
+    /// offset = 0
+    /// pattern = "41 43 4D 45"
+    /// mask = "FF FF F0 FF"  # optional, all bits by default
+    /// ```
+    ///
+    /// Throws `Error::Read` if `reader` fails or is not UTF-8.
+    /// Throws `Error::InvalidDefinitions` with the line and field of the first problem.
+    #[cfg(feature = "definitions")]
+    pub fn load_definitions<R: std::io::Read>(&mut self, mut reader: R) -> Result<(), Error> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text).map_err(Error::Read)?;
+        self.custom.extend(crate::definitions::parse(&text)?);
+        Ok(())
+    }
+
+    /// `detect`, with an explanation of the checks that came close when it fails.
+    pub fn diagnose(&self, buffer: &[u8]) -> DiagnosisReport {
+        let result = self.detect(buffer);
+        let mut near_misses = Vec::new();
+        if result.is_err() {
+            for check in &self.checks {
+                let explanation = check.near_miss.and_then(|x| x(buffer, &self.options));
+                if let Some(explanation) = explanation {
+                    near_misses.push(NearMiss {
+                        kind: check.kind,
+                        explanation,
+                    });
+                }
+            }
+        }
+        DiagnosisReport::new(buffer, result, near_misses)
+    }
+
+    /// Number of starting bytes worth passing to `detect`.
+    /// Covers the PS search window, the MXF run-in and the TS/M2TS packets that are
+    /// probed. Longer buffers are fine but will not change the result for most files.
+    pub fn recommended_probe_len(&self) -> usize {
+        // SMPTE 377 allows up to 64 KiB of run-in before the MXF header partition pack.
+        // Kept in u64 so it saturates instead of overflowing a 16 bit usize.
+        const MXF_PROBE_LEN: u64 = 65536 + 16;
+        const TS_PROBE_LEN: usize = 192 * (TS_PACKETS_TO_PROBE + 1) + 4;
+
+        let mxf = usize::try_from(MXF_PROBE_LEN).unwrap_or(usize::MAX);
+        self.options.ps_window.max(mxf).max(TS_PROBE_LEN)
+    }
+
+    /// `detect` on the first `cap` bytes of a stream of which `buffer` has arrived so far,
+    /// `None` if more bytes could still change the result.
+    /// Known early when an anchored check matches and only anchored checks run before
+    /// it, otherwise once `cap` bytes or the end of the stream (`end`) are there.
+    pub(crate) fn detect_prefix(
+        &self,
+        buffer: &[u8],
+        cap: usize,
+        end: bool,
+    ) -> Option<Result<ContainerType, Error>> {
+        if end || buffer.len() >= cap {
+            return Some(self.detect(&buffer[..buffer.len().min(cap)]));
+        }
+        if self.options.strict_ambiguity || buffer.len() < ANCHORED_PROBE_LEN {
+            return None;
+        }
+        if self.options.non_media_check && NON_MEDIA.iter().any(|x| buffer.starts_with(x.0)) {
+            return Some(self.detect(buffer));
+        }
+        for check in self.checks.iter().take_while(|x| x.cost == Cost::Anchored) {
+            if (check.matches)(buffer, &self.options) {
+                return Some(Ok(check.kind));
+            }
+        }
+        None
+    }
+
+    /// Infer all matching Containers from a slice of bytes, in priority order.
+    /// Unlike `detect` this keeps going after the first match.
+    pub fn detect_all(&self, buffer: &[u8]) -> Vec<ContainerType> {
+        self.matching(buffer).collect()
+    }
+
+    /// Smallest length needed by a check whose leading bytes agree with `buffer`, when
+    /// `buffer` is shorter than that.
+    /// A buffer agrees with a check if it starts with the check's magic, or is shorter
+    /// than the magic and is a prefix of it, eg. `[0x1a, 0x45]` agrees with MKV.
+    /// Checks without fixed leading bytes (M2TS, MP4, MXF, PS) are never considered.
+    pub(crate) fn truncated_len(&self, buffer: &[u8]) -> Option<usize> {
+        self.checks
+            .iter()
+            .filter(|x| !x.magic.is_empty())
+            .filter(|x| {
+                let len = buffer.len().min(x.magic.len());
+                buffer[..len] == x.magic[..len]
+            })
+            .map(|x| x.needed(&self.options))
+            .filter(|x| *x > buffer.len())
+            .min()
+    }
+
+    fn matching<'a>(&'a self, buffer: &'a [u8]) -> impl Iterator<Item = ContainerType> + 'a {
+        self.checks
+            .iter()
+            .filter(move |x| buffer.len() >= x.min_len && (x.matches)(buffer, &self.options))
+            .map(|x| x.kind)
+    }
+}
+
+/// Builder for [`Detector`].
+#[derive(Debug, Clone, Default)]
+pub struct DetectorBuilder {
+    options: Options,
+    formats: Option<Vec<ContainerType>>,
+    priority: Vec<ContainerType>,
+    custom: Vec<CustomFormat>,
+}
+
+impl DetectorBuilder {
+    /// Accept weaker evidence for some containers.
+    /// Currently this lets a Matroska Segment without the EBML header (a mid file
+    /// fragment) be reported as MKV, and an Ogg page without the BOS flag (a capture
+    /// started mid stream) as OGG.
+    /// Default is `false`.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.options.lenient = lenient;
+        self
+    }
+
+    /// Return `Error::Ambiguous` when more than one container matches, instead of
+    /// the one with the highest priority. A refinement of PS such as VOB also matches
+    /// PS, that is not ambiguous.
+    /// Default is `false`.
+    pub fn strict_ambiguity(mut self, strict: bool) -> Self {
+        self.options.strict_ambiguity = strict;
+        self
+    }
+
+    /// Number of the 10 probed TS/M2TS packets that need a sync byte and a valid header.
+    /// Lower values tolerate more corrupted or dropped packets near the start.
+    /// Clamped to `1..=10`, default is 7.
+    pub fn ts_sync_threshold(mut self, threshold: usize) -> Self {
+        self.options.ts_sync_threshold = threshold.clamp(1, TS_PACKETS_TO_PROBE);
+        self
+    }
+
+    /// Number of starting bytes searched for an MPEG PS pack header.
+    /// Raise it for files with long leading padding, lower it to do less work.
+    /// Default is 50 000.
+    pub fn ps_window(mut self, window: usize) -> Self {
+        self.options.ps_window = window;
+        self
+    }
+
+    /// Look for the magics of common non media files (ZIP, PDF, PNG, JPEG, ELF, gzip,
+    /// zstd, xz) first and return `Error::NotMedia` without running the other checks.
+    /// Turn it off if such files may wrap media.
+    /// Default is `true`.
+    pub fn non_media_check(mut self, check: bool) -> Self {
+        self.options.non_media_check = check;
+        self
+    }
+
+    /// When no check matches, score the buffer as a headerless MPEG-2 video, H.264 or
+    /// H.265 elementary stream (eg. a capture that started mid GOP) by the density,
+    /// order and validity of its start codes. A result that clears the threshold is
+    /// reported with `Confidence::Weak`.
+    /// Only takes effect in lenient mode. The thresholds are documented in
+    /// `src/heuristic.rs`, they favour missing a stream over a false positive.
+    /// Default is `false`.
+    pub fn es_heuristics(mut self, heuristics: bool) -> Self {
+        self.options.es_heuristics = heuristics;
+        self
+    }
+
+    /// Only run the checks for these containers.
+    /// Default is to run all of them.
+    pub fn formats(mut self, formats: &[ContainerType]) -> Self {
+        self.formats = Some(formats.to_vec());
+        self
+    }
+
+    /// Run the checks for these containers first, in the given order.
+    /// Containers not listed run afterwards in `DEFAULT_PRIORITY` order.
+    /// Throws `Error::DuplicatePriority` if a container is listed twice.
+    pub fn priority(mut self, priority: &[ContainerType]) -> Result<Self, Error> {
+        for (i, kind) in priority.iter().enumerate() {
+            if priority[..i].contains(kind) {
+                return Err(Error::DuplicatePriority(*kind));
+            }
+        }
+        self.priority = priority.to_vec();
+        Ok(self)
+    }
+
+    /// Register a custom format, reported by `Detector::detect_named`.
+    /// Custom formats are tried in the order they are registered.
+    pub fn custom_format(mut self, format: CustomFormat) -> Self {
+        self.custom.push(format);
+        self
+    }
+
+    /// Finish building.
+    pub fn build(self) -> Detector {
+        let formats = self.formats;
+        let priority = self.priority;
+        let order = priority
+            .iter()
+            .chain(DEFAULT_PRIORITY.iter().filter(|x| !priority.contains(x)));
+        let checks = order
+            .filter_map(|kind| CHECKS.iter().find(|x| x.kind == *kind))
+            .filter(|x| match &formats {
+                Some(f) => f.contains(&x.kind),
+                None => true,
+            })
+            .collect();
+        let heuristics = if self.options.lenient && self.options.es_heuristics {
+            HEURISTIC_KINDS
+                .iter()
+                .filter(|x| match &formats {
+                    Some(f) => f.contains(x),
+                    None => true,
+                })
+                .copied()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Detector {
+            options: self.options,
+            checks,
+            custom: self.custom,
+            heuristics,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cheapest_first() {
+        assert!(CHECKS.windows(2).all(|x| x[0].cost <= x[1].cost));
+    }
+
+    #[test]
+    fn anchored_probe_len() {
+        let mut anchored = CHECKS.iter().filter(|x| x.cost == Cost::Anchored);
+        assert!(anchored.all(|x| x.min_len <= ANCHORED_PROBE_LEN));
+        assert!(NON_MEDIA.iter().all(|x| x.0.len() <= ANCHORED_PROBE_LEN));
+    }
+
+    #[test]
+    fn default_priority() {
+        let kinds: Vec<_> = CHECKS.iter().map(|x| x.kind).collect();
+        assert_eq!(kinds, DEFAULT_PRIORITY);
+    }
+
+    #[test]
+    fn recommended_probe_len() {
+        assert_eq!(Detector::default().recommended_probe_len(), 65536 + 16);
+        let detector = Detector::builder().ps_window(1024 * 1024).build();
+        assert_eq!(detector.recommended_probe_len(), 1024 * 1024);
+    }
+
+    #[test]
+    fn priority() {
+        let detector = Detector::builder()
+            .priority(&[ContainerType::PS, ContainerType::ASF])
+            .unwrap()
+            .build();
+        let kinds: Vec<_> = detector.checks.iter().map(|x| x.kind).collect();
+        assert_eq!(
+            kinds[..3],
+            [ContainerType::PS, ContainerType::ASF, ContainerType::GXF]
+        );
+        assert_eq!(kinds.len(), DEFAULT_PRIORITY.len());
+
+        let t = Detector::builder().priority(&[ContainerType::TS, ContainerType::TS]);
+        assert!(matches!(
+            t,
+            Err(Error::DuplicatePriority(ContainerType::TS))
+        ));
+    }
+
+    #[test]
+    fn formats() {
+        let mut buffer = [0; 64];
+        buffer[0..4].copy_from_slice(b"TiVo");
+        buffer[32..36].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+
+        let t = Detector::default().detect(&buffer);
+        assert_eq!(t.ok(), Some(ContainerType::TivoPS));
+        let t = Detector::builder()
+            .formats(&[ContainerType::PS])
+            .build()
+            .detect(&buffer);
+        assert_eq!(t.ok(), Some(ContainerType::PS));
+        let t = Detector::builder()
+            .formats(&[ContainerType::MKV])
+            .build()
+            .detect(&buffer);
+        assert!(t.is_err());
+    }
+}
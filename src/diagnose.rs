@@ -0,0 +1,366 @@
+//! Explanations for buffers that fail to match, see [`diagnose`].
+
+use crate::bytes::{slice_at, stride_pos};
+use crate::detector::Options;
+use crate::{scan, ContainerType, Detector, Error, SIGNATURES, TS_PACKETS_TO_PROBE};
+use std::fmt;
+
+/// Number of starting bytes in the hexdump of a report.
+const HEAD_LEN: usize = 64;
+
+/// Why a buffer was (or was not) identified, see [`diagnose`].
+/// The `Display` output is meant to be pasted into bug reports.
+#[derive(Debug)]
+pub struct DiagnosisReport {
+    /// Length of the diagnosed buffer.
+    pub len: usize,
+    /// What `Detector::detect` returned.
+    pub result: Result<ContainerType, Error>,
+    /// Checks that came close, in the order they ran. Only filled when detection failed.
+    pub near_misses: Vec<NearMiss>,
+    /// Up to the first 64 bytes of the buffer.
+    pub head: Vec<u8>,
+}
+
+/// A check that did not match, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearMiss {
+    /// Container of the check.
+    pub kind: ContainerType,
+    /// What was found and where it went wrong.
+    pub explanation: String,
+}
+
+/// Explains how the default detector treats `buffer`.
+///
+/// # Example
+/// ```rust
+/// let mut buf = [0; 16];
+/// buf[4..12].copy_from_slice(b"ftypM4V ");
+/// let report = media_infer::diagnose(&buf);
+/// assert!(report.result.is_err());
+/// println!("{}", report);
+/// ```
+pub fn diagnose(buffer: &[u8]) -> DiagnosisReport {
+    Detector::default().diagnose(buffer)
+}
+
+impl DiagnosisReport {
+    pub(crate) fn new(
+        buffer: &[u8],
+        result: Result<ContainerType, Error>,
+        near_misses: Vec<NearMiss>,
+    ) -> Self {
+        Self {
+            len: buffer.len(),
+            result,
+            near_misses,
+            head: buffer[..buffer.len().min(HEAD_LEN)].to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for DiagnosisReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "media_infer {}, {} bytes: ",
+            env!("CARGO_PKG_VERSION"),
+            self.len
+        )?;
+        match &self.result {
+            Ok(kind) => writeln!(f, "{}", kind)?,
+            Err(e) => writeln!(f, "{}", e)?,
+        }
+        for miss in &self.near_misses {
+            writeln!(f, "{:?}: {}", miss.kind, miss.explanation)?;
+        }
+        writeln!(f, "First {} bytes:", self.head.len())?;
+        for (i, line) in self.head.chunks(16).enumerate() {
+            write!(f, "{:08x} ", i * 16)?;
+            for j in 0..16 {
+                let sep = if j == 8 { "  " } else { " " };
+                match line.get(j) {
+                    Some(x) => write!(f, "{}{:02x}", sep, x)?,
+                    None => write!(f, "{}  ", sep)?,
+                }
+            }
+            writeln!(f, "  |{}|", printable(line))?;
+        }
+        Ok(())
+    }
+}
+
+/// ASCII of `bytes`, with '.' for anything not printable.
+fn printable(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|x| {
+            if x.is_ascii_graphic() || *x == b' ' {
+                char::from(*x)
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+/// "0", "0 and 188", "0, 188 and 376".
+fn list(items: &[usize]) -> String {
+    match items.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => {
+            let rest: Vec<_> = rest.iter().map(|x| x.to_string()).collect();
+            format!("{} and {}", rest.join(", "), last)
+        }
+        None => String::new(),
+    }
+}
+
+/// Start of a 16 byte GUID without the rest of it.
+fn guid(buffer: &[u8], kind: ContainerType) -> Option<String> {
+    let magic = SIGNATURES.iter().find(|x| x.kind == kind)?.rules[0].1;
+    let head = slice_at(buffer, 0, magic.len())?;
+    if head[..4] != magic[..4] || head == magic {
+        return None;
+    }
+    Some(format!(
+        "first 4 bytes of the {} header GUID found, the other {} differ",
+        kind,
+        magic.len() - 4
+    ))
+}
+
+pub(crate) fn asf(buffer: &[u8], _: &Options) -> Option<String> {
+    guid(buffer, ContainerType::ASF)
+}
+
+pub(crate) fn wtv(buffer: &[u8], _: &Options) -> Option<String> {
+    guid(buffer, ContainerType::WTV)
+}
+
+pub(crate) fn mkv(buffer: &[u8], options: &Options) -> Option<String> {
+    if options.lenient || !ContainerType::check_mkv_segment(buffer) {
+        return None;
+    }
+    Some("Segment without the EBML header, only accepted in lenient mode".to_owned())
+}
+
+pub(crate) fn rcwt(buffer: &[u8], _: &Options) -> Option<String> {
+    let header = slice_at(buffer, 0, 11)?;
+    if header[..3] != [0xCC, 0xCC, 0xED] {
+        return None;
+    }
+    let why = if header[3] != 0xCC {
+        format!("creating program 0x{:02x} is not CCExtractor", header[3])
+    } else if header[4..6] == [0, 0] {
+        "program version is 0".to_owned()
+    } else if header[6..8] != [0, 1] {
+        let version = u16::from_be_bytes([header[6], header[7]]);
+        format!("file format version {} is not 1", version)
+    } else {
+        "reserved bytes 8-10 are not zero".to_owned()
+    };
+    Some(format!("magic found but {}", why))
+}
+
+pub(crate) fn mp4(buffer: &[u8], _: &Options) -> Option<String> {
+    let brand = slice_at(buffer, 4, 8).filter(|x| x.starts_with(b"ftyp"))?;
+    // Brands shorter than 4 bytes are prefixes, eg. `3gp` for `3gp4` and `3gp5`.
+    let brands: Vec<_> = SIGNATURES
+        .iter()
+        .filter_map(|x| match x.rules.first() {
+            Some((4, bytes)) => bytes.strip_prefix(b"ftyp"),
+            _ => None,
+        })
+        .map(|x| {
+            let star = if x.len() < 4 { "*" } else { "" };
+            format!("{}{}", printable(x), star)
+        })
+        .collect();
+    Some(format!(
+        "ftyp found at offset 4 but brand '{}' not in accepted list ({})",
+        printable(&brand[4..]),
+        brands.join(", ")
+    ))
+}
+
+pub(crate) fn ts(buffer: &[u8], options: &Options) -> Option<String> {
+    ts_run(buffer, 0, 188, options)
+}
+
+pub(crate) fn m2ts(buffer: &[u8], options: &Options) -> Option<String> {
+    ts_run(buffer, 4, 192, options)
+}
+
+/// Longest run of valid packets from the start of the probe, if at least 2 long.
+fn ts_run(buffer: &[u8], prefix: usize, stride: usize, options: &Options) -> Option<String> {
+    let valid = |start: usize, y: usize| {
+        stride_pos(start, y, stride).and_then(|x| ContainerType::ts_packet_pid(buffer, x))
+    };
+    let run = |start: usize| {
+        (0..TS_PACKETS_TO_PROBE)
+            .take_while(|y| valid(start, *y).is_some())
+            .count()
+    };
+    // Reversed, so the first of the longest runs wins.
+    let (start, run) = (prefix..prefix + stride)
+        .rev()
+        .map(|x| (x, run(x)))
+        .max_by_key(|x| x.1)?;
+    if run < 2 {
+        return None;
+    }
+
+    let pids: Vec<_> = (0..TS_PACKETS_TO_PROBE)
+        .filter_map(|y| valid(start, y))
+        .collect();
+    if pids.len() >= options.ts_sync_threshold {
+        return Some(format!(
+            "{} valid packets from offset {} but all have the null PID",
+            pids.len(),
+            start
+        ));
+    }
+    let offsets: Vec<_> = (0..run).map(|y| start + y * stride).collect();
+    let broke = start + run * stride;
+    let end = if broke >= buffer.len() {
+        ", past the end of the buffer"
+    } else {
+        ""
+    };
+    Some(format!(
+        "0x47 sync found at offsets {} but run broke at packet {} (offset {}{}), {} of {} probed packets valid, needs {}",
+        list(&offsets),
+        run + 1,
+        broke,
+        end,
+        pids.len(),
+        TS_PACKETS_TO_PROBE,
+        options.ts_sync_threshold
+    ))
+}
+
+pub(crate) fn mxf(buffer: &[u8], _: &Options) -> Option<String> {
+    const PARTITION_KEY: [u8; 13] = [
+        0x06, 0x0e, 0x2b, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01,
+    ];
+
+    let pos = scan::find(buffer, &PARTITION_KEY, 0)?;
+    let partition = match buffer.get(pos + PARTITION_KEY.len()) {
+        Some(0x03) => "body".to_owned(),
+        Some(0x04) => "footer".to_owned(),
+        Some(x) => format!("0x{:02x}", x),
+        None => return None,
+    };
+    Some(format!(
+        "{} partition pack at offset {}, not the header partition",
+        partition, pos
+    ))
+}
+
+pub(crate) fn ps(buffer: &[u8], options: &Options) -> Option<String> {
+    let window = options.ps_window;
+    let pos = scan::find(buffer.get(window.saturating_sub(3)..)?, &[0, 0, 1, 0xBA], 3)?;
+    Some(format!(
+        "pack header found at offset {}, past the {} byte search window",
+        pos + window.saturating_sub(3),
+        window
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explanations(buffer: &[u8]) -> Vec<String> {
+        let report = diagnose(buffer);
+        assert!(report.result.is_err());
+        report
+            .near_misses
+            .into_iter()
+            .map(|x| format!("{:?}: {}", x.kind, x.explanation))
+            .collect()
+    }
+
+    #[test]
+    fn ftyp_unknown_brand() {
+        let mut buffer = [0; 32];
+        buffer[0..12].copy_from_slice(b"\x00\x00\x00\x20ftypM4V ");
+        assert_eq!(
+            explanations(&buffer),
+            ["MP4: ftyp found at offset 4 but brand 'M4V ' not in accepted list (isom, MSNV, qt  , hei*, hev*, avif, avis, 3gp*, 3g2*, M4A , M4B , M4P )"]
+        );
+    }
+
+    #[test]
+    fn broken_ts_run() {
+        let mut buffer = vec![0; 188 * 10];
+        for pos in [0, 188] {
+            buffer[pos..pos + 4].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+        }
+        assert_eq!(
+            explanations(&buffer),
+            ["TS: 0x47 sync found at offsets 0 and 188 but run broke at packet 3 (offset 376), 2 of 10 probed packets valid, needs 7"]
+        );
+
+        buffer.truncate(188 * 3);
+        buffer[376..380].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+        let t = explanations(&buffer);
+        assert!(t[0].ends_with("run broke at packet 4 (offset 564, past the end of the buffer), 3 of 10 probed packets valid, needs 7"), "{:?}", t);
+    }
+
+    #[test]
+    fn null_ts_packets() {
+        let mut buffer = vec![0; 188 * 10];
+        for pos in (0..10).map(|x| x * 188) {
+            buffer[pos..pos + 4].copy_from_slice(&[0x47, 0x1F, 0xFF, 0x10]);
+        }
+        assert_eq!(
+            explanations(&buffer),
+            ["TS: 10 valid packets from offset 0 but all have the null PID"]
+        );
+    }
+
+    #[test]
+    fn others() {
+        let t = explanations(&[0xCC, 0xCC, 0xED, 0xCC, 0, 1, 0, 2, 0, 0, 0]);
+        assert_eq!(t, ["RCWT: magic found but file format version 2 is not 1"]);
+
+        let mut buffer = vec![0xFF; 64];
+        buffer[8..24].copy_from_slice(&[
+            0x06, 0x0e, 0x2b, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01, 0x04,
+            0x04, 0x00,
+        ]);
+        let t = explanations(&buffer);
+        assert_eq!(
+            t,
+            ["MXF: footer partition pack at offset 8, not the header partition"]
+        );
+
+        let mut buffer = vec![0xFF; 60000];
+        buffer[55000..55004].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+        let t = explanations(&buffer);
+        assert_eq!(
+            t,
+            ["PS: pack header found at offset 55000, past the 50000 byte search window"]
+        );
+    }
+
+    #[test]
+    fn display() {
+        let mut buffer = [0; 20];
+        buffer[0..12].copy_from_slice(b"\x00\x00\x00\x20ftypM4V ");
+        let t = diagnose(&buffer).to_string();
+        let expected = format!(
+            "media_infer {}, 20 bytes: Could Not Identify
+MP4: ftyp found at offset 4 but brand 'M4V ' not in accepted list (isom, MSNV, qt  , hei*, hev*, avif, avis, 3gp*, 3g2*, M4A , M4B , M4P )
+First 20 bytes:
+00000000  00 00 00 20 66 74 79 70  4d 34 56 20 00 00 00 00  |... ftypM4V ....|
+00000010  00 00 00 00                                       |....|
+",
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(t, expected);
+    }
+}
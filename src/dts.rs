@@ -0,0 +1,107 @@
+//! Sync word form of a DTS stream, see [`dts_sync`], and DTS-HD extension substreams.
+
+use crate::scan;
+
+/// How the sync word of a DTS stream is packed, which tells how to read the rest of
+/// it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DtsSync {
+    /// 16 bit words, big endian, `7F FE 80 01`
+    Be16,
+    /// 16 bit words, little endian (byte swapped), `FE 7F 01 80`
+    Le16,
+    /// 14 bits in each 16 bit word, big endian, `1F FF E8 00`
+    Be14,
+    /// 14 bits in each 16 bit word, little endian, `FF 1F 00 E8`
+    Le14,
+}
+
+/// Most zero bytes skipped before the sync word.
+pub(crate) const MAX_LEADING_ZEROS: usize = 32;
+
+/// Sync word of a DTS-HD extension substream.
+const EXTENSION_SYNC: [u8; 4] = [0x64, 0x58, 0x20, 0x25];
+
+/// Extension substreams following a core frame further into the stream than this are
+/// not looked for. Core frames are at most 16 KiB.
+const EXTENSION_SEARCH_LEN: usize = 16 * 1024 + 4;
+
+const SYNC_WORDS: [([u8; 4], DtsSync); 4] = [
+    ([0x7F, 0xFE, 0x80, 0x01], DtsSync::Be16),
+    ([0xFE, 0x7F, 0x01, 0x80], DtsSync::Le16),
+    ([0x1F, 0xFF, 0xE8, 0x00], DtsSync::Be14),
+    ([0xFF, 0x1F, 0x00, 0xE8], DtsSync::Le14),
+];
+
+/// Sync word form of the DTS stream `buffer` starts with, after at most
+/// [`MAX_LEADING_ZEROS`] zero bytes. `None` if there is no DTS sync word there.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{dts_sync, DtsSync};
+///
+/// assert_eq!(dts_sync(b"\xFE\x7F\x01\x80\xFC\x3C"), Some(DtsSync::Le16));
+/// ```
+pub fn dts_sync(buffer: &[u8]) -> Option<DtsSync> {
+    let word = first_word(buffer)?;
+    SYNC_WORDS
+        .iter()
+        .find(|(x, _)| x == word)
+        .map(|(_, sync)| *sync)
+}
+
+/// First four bytes of `buffer` after at most [`MAX_LEADING_ZEROS`] zero bytes.
+fn first_word(buffer: &[u8]) -> Option<&[u8]> {
+    let start = buffer
+        .iter()
+        .take(MAX_LEADING_ZEROS + 1)
+        .position(|x| *x != 0)?;
+    buffer.get(start..start + 4)
+}
+
+/// Whether the DTS stream `buffer` starts with carries DTS-HD extension substreams:
+/// it starts with one, like a stream without a core, or one follows the first core
+/// frame. Only 16 bit big endian cores are looked behind, DTS-HD uses no other packing.
+pub(crate) fn has_extension(buffer: &[u8]) -> bool {
+    if first_word(buffer) == Some(&EXTENSION_SYNC[..]) {
+        return true;
+    }
+    let end = buffer.len().min(EXTENSION_SEARCH_LEN);
+    dts_sync(buffer) == Some(DtsSync::Be16) && scan::contains(&buffer[..end], &EXTENSION_SYNC, 3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leading_zeros() {
+        let mut buffer = vec![0; MAX_LEADING_ZEROS];
+        buffer.extend_from_slice(&[0x1F, 0xFF, 0xE8, 0x00, 0x07, 0xF0]);
+        assert_eq!(dts_sync(&buffer), Some(DtsSync::Be14));
+        buffer.insert(0, 0);
+        assert_eq!(dts_sync(&buffer), None);
+        assert_eq!(dts_sync(&[0; 8]), None);
+    }
+
+    #[test]
+    fn extension() {
+        let core = [0x7F, 0xFE, 0x80, 0x01, 0xFC, 0x3C, 0x3F, 0xF2, 0x75, 0xE0];
+        assert!(!has_extension(&core));
+        let buffer = [&core[..], &[0; 1014], &EXTENSION_SYNC].concat();
+        assert!(has_extension(&buffer));
+        assert!(has_extension(&EXTENSION_SYNC));
+        // Byte swapped cores are not looked behind.
+        let swapped = [&[0xFE, 0x7F, 0x01, 0x80][..], &EXTENSION_SYNC].concat();
+        assert!(!has_extension(&swapped));
+        // Past the first core frame.
+        let buffer = [&core[..], &[0; EXTENSION_SEARCH_LEN], &EXTENSION_SYNC].concat();
+        assert!(!has_extension(&buffer));
+    }
+
+    #[test]
+    fn cut_off() {
+        assert_eq!(dts_sync(&[0x7F, 0xFE, 0x80]), None);
+        assert_eq!(dts_sync(&[0xFF, 0x1F, 0x00, 0xE8]), Some(DtsSync::Le14));
+    }
+}
@@ -0,0 +1,271 @@
+//! Minimal EBML reader, enough to find the DocType of a Matroska or WebM header and
+//! the track types of a Matroska Segment.
+
+use std::convert::TryFrom;
+
+/// ID of the EBML header element.
+const EBML_ID: u32 = 0x1a45_dfa3;
+/// ID of the DocType element, a child of the EBML header.
+const DOC_TYPE_ID: u32 = 0x4282;
+/// ID of the Segment, the top level element following the EBML header.
+const SEGMENT_ID: u32 = 0x1853_8067;
+/// ID of the Tracks element, a child of the Segment.
+const TRACKS_ID: u32 = 0x1654_ae6b;
+/// ID of a TrackEntry, a child of Tracks.
+const TRACK_ENTRY_ID: u32 = 0xae;
+/// ID of the TrackType of a TrackEntry.
+const TRACK_TYPE_ID: u32 = 0x83;
+
+/// TrackType of a video track.
+const TRACK_TYPE_VIDEO: u64 = 1;
+/// TrackType of an audio track.
+const TRACK_TYPE_AUDIO: u64 = 2;
+
+/// Tracks starting further into the file than this are not looked at.
+const TRACKS_SEARCH_LEN: usize = 1024 * 1024;
+
+/// Header of an element.
+#[derive(Debug, Clone, Copy)]
+struct Element {
+    id: u32,
+    /// Offset of the data, right after the header.
+    data: usize,
+    /// Data size, `None` if unknown.
+    size: Option<usize>,
+}
+
+impl Element {
+    /// Offset right after the data, `None` if the size is unknown.
+    fn end(&self) -> Option<usize> {
+        self.data.checked_add(self.size?)
+    }
+}
+
+/// Length of the vint starting with `first`, `None` past 8 bytes.
+fn vint_len(first: u8) -> Option<usize> {
+    let len = first.leading_zeros() as usize + 1;
+    if len > 8 {
+        return None;
+    }
+    Some(len)
+}
+
+/// Element ID at `pos` (marker bits kept, as IDs are written) and its length.
+/// IDs are at most 4 bytes.
+fn id_at(buffer: &[u8], pos: usize) -> Option<(u32, usize)> {
+    let len = vint_len(*buffer.get(pos)?)?;
+    if len > 4 {
+        return None;
+    }
+    let bytes = buffer.get(pos..pos.checked_add(len)?)?;
+    let id = bytes.iter().fold(0, |acc, x| acc << 8 | u32::from(*x));
+    Some((id, len))
+}
+
+/// Data size at `pos` (`None` for the reserved "unknown" size) and its length.
+fn size_at(buffer: &[u8], pos: usize) -> Option<(Option<u64>, usize)> {
+    let len = vint_len(*buffer.get(pos)?)?;
+    let bytes = buffer.get(pos..pos.checked_add(len)?)?;
+    let first = u64::from(bytes[0]) & (0xff >> len);
+    let size = bytes[1..]
+        .iter()
+        .fold(first, |acc, x| acc << 8 | u64::from(*x));
+    // All value bits set means unknown.
+    if size == (1 << (7 * len)) - 1 {
+        return Some((None, len));
+    }
+    Some((Some(size), len))
+}
+
+/// Header of the element at `pos`.
+fn element_at(buffer: &[u8], pos: usize) -> Option<Element> {
+    let (id, id_len) = id_at(buffer, pos)?;
+    let (size, size_len) = size_at(buffer, pos.checked_add(id_len)?)?;
+    let size = match size {
+        Some(x) => Some(usize::try_from(x).ok()?),
+        None => None,
+    };
+    Some(Element {
+        id,
+        data: pos + id_len + size_len,
+        size,
+    })
+}
+
+/// Big endian unsigned integer value of `element`, `None` past 8 bytes.
+fn uint(buffer: &[u8], element: &Element) -> Option<u64> {
+    let value = buffer.get(element.data..element.end()?)?;
+    if value.len() > 8 {
+        return None;
+    }
+    Some(value.iter().fold(0, |acc, x| acc << 8 | u64::from(*x)))
+}
+
+/// Children of an element, up to the end of the buffer if its size is unknown.
+/// Ends at the first child that cannot be read or overruns its parent, and after a
+/// child of unknown size, as there is no telling where it ends.
+struct Children<'a> {
+    buffer: &'a [u8],
+    pos: Option<usize>,
+    end: usize,
+}
+
+fn children<'a>(buffer: &'a [u8], parent: &Element) -> Children<'a> {
+    Children {
+        buffer,
+        pos: Some(parent.data),
+        end: parent.end().unwrap_or(usize::MAX),
+    }
+}
+
+impl Iterator for Children<'_> {
+    type Item = Element;
+
+    fn next(&mut self) -> Option<Element> {
+        let pos = self.pos.take().filter(|x| *x < self.end)?;
+        let element = element_at(self.buffer, pos)?;
+        match element.end() {
+            Some(end) if end > self.end => return None,
+            end => self.pos = end,
+        }
+        Some(element)
+    }
+}
+
+/// EBML header at the start of `buffer`, it needs a known size.
+fn header(buffer: &[u8]) -> Option<Element> {
+    let header = element_at(buffer, 0)?;
+    if header.id != EBML_ID || header.size.is_none() {
+        return None;
+    }
+    Some(header)
+}
+
+/// DocType of the EBML header at the start of `buffer`, without trailing NULs.
+/// `None` if the buffer does not start with an EBML header, or the DocType is not
+/// in it or not in the buffer.
+pub(crate) fn doc_type(buffer: &[u8]) -> Option<&[u8]> {
+    let header = header(buffer)?;
+    let doc_type = children(buffer, &header).find(|x| x.id == DOC_TYPE_ID)?;
+    let value = buffer.get(doc_type.data..doc_type.end()?)?;
+    let len = value.iter().rposition(|x| *x != 0).map_or(0, |x| x + 1);
+    Some(&value[..len])
+}
+
+/// Whether the Segment following the EBML header has an audio track and no video
+/// track.
+/// `false` unless its Tracks element ends within the buffer and the first
+/// `TRACKS_SEARCH_LEN` bytes, Tracks cut off could still list a video track.
+pub(crate) fn is_audio_only(buffer: &[u8]) -> bool {
+    let buffer = &buffer[..buffer.len().min(TRACKS_SEARCH_LEN)];
+    let segment = match header(buffer)
+        .and_then(|x| x.end())
+        .and_then(|x| element_at(buffer, x))
+    {
+        Some(x) if x.id == SEGMENT_ID => x,
+        _ => return false,
+    };
+    let tracks = match children(buffer, &segment).find(|x| x.id == TRACKS_ID) {
+        Some(x) if matches!(x.end(), Some(end) if end <= buffer.len()) => x,
+        _ => return false,
+    };
+
+    let mut audio = false;
+    for entry in children(buffer, &tracks).filter(|x| x.id == TRACK_ENTRY_ID) {
+        let track_type = children(buffer, &entry)
+            .find(|x| x.id == TRACK_TYPE_ID)
+            .and_then(|x| uint(buffer, &x));
+        match track_type {
+            Some(TRACK_TYPE_VIDEO) => return false,
+            Some(TRACK_TYPE_AUDIO) => audio = true,
+            _ => {}
+        }
+    }
+    audio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vint_sizes() {
+        assert_eq!(size_at(&[0x84], 0), Some((Some(4), 1)));
+        assert_eq!(size_at(&[0x40, 0x04], 0), Some((Some(4), 2)));
+        let t = size_at(&[0x01, 0, 0, 0, 0, 0, 0, 0x04], 0);
+        assert_eq!(t, Some((Some(4), 8)));
+        assert_eq!(size_at(&[0xff], 0), Some((None, 1)));
+        let t = size_at(&[0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], 0);
+        assert_eq!(t, Some((None, 8)));
+        // Longer than 8 bytes, and cut off.
+        assert_eq!(size_at(&[0x00, 0x84], 0), None);
+        assert_eq!(size_at(&[0x40], 0), None);
+    }
+
+    #[test]
+    fn doc_type_bounds() {
+        let mut buffer = vec![0x1a, 0x45, 0xdf, 0xa3, 0x87, 0x42, 0x82, 0x84];
+        buffer.extend_from_slice(b"webm");
+        assert_eq!(doc_type(&buffer), Some(&b"webm"[..]));
+        assert_eq!(doc_type(&buffer[..10]), None);
+
+        // DocType past the end of the header.
+        buffer[4] = 0x83;
+        assert_eq!(doc_type(&buffer), None);
+        // Header of unknown size.
+        buffer[4] = 0xff;
+        assert_eq!(doc_type(&buffer), None);
+
+        // Huge sizes do not overflow.
+        let buffer = [0x1a, 0x45, 0xdf, 0xa3, 0x84, 0x42, 0x86, 0x01, 0xff, 0xff];
+        assert_eq!(doc_type(&buffer), None);
+    }
+
+    /// EBML header and a Segment of unknown size holding Tracks with `track_types`.
+    fn with_tracks(track_types: &[u8]) -> Vec<u8> {
+        let mut tracks = Vec::new();
+        for (i, track_type) in track_types.iter().enumerate() {
+            // TrackNumber and TrackType.
+            tracks.extend_from_slice(&[0xae, 0x86, 0xd7, 0x81, i as u8 + 1]);
+            tracks.extend_from_slice(&[0x83, 0x81, *track_type]);
+        }
+        let mut out = vec![0x1a, 0x45, 0xdf, 0xa3, 0x84, 0x42, 0x82, 0x81, b'm'];
+        out.extend_from_slice(&[0x18, 0x53, 0x80, 0x67, 0xff]);
+        // Void before Tracks.
+        out.extend_from_slice(&[0xec, 0x82, 0, 0]);
+        out.extend_from_slice(&[0x16, 0x54, 0xae, 0x6b, 0x80 | tracks.len() as u8]);
+        out.extend(tracks);
+        out
+    }
+
+    #[test]
+    fn audio_only() {
+        assert!(is_audio_only(&with_tracks(&[2])));
+        assert!(is_audio_only(&with_tracks(&[2, 0x11])));
+        assert!(!is_audio_only(&with_tracks(&[2, 1])));
+        assert!(!is_audio_only(&with_tracks(&[1])));
+        assert!(!is_audio_only(&with_tracks(&[])));
+        // Tracks cut off after the audio entry, or at their very end.
+        let buffer = with_tracks(&[2, 1]);
+        assert!(!is_audio_only(&buffer[..buffer.len() - 8]));
+        let buffer = with_tracks(&[2]);
+        assert!(!is_audio_only(&buffer[..buffer.len() - 1]));
+    }
+
+    #[test]
+    fn tracks_search_len() {
+        let mut buffer = with_tracks(&[2]);
+        // Void pushing Tracks past the first MiB.
+        let size = TRACKS_SEARCH_LEN - 4;
+        let mut void = vec![
+            0xec,
+            0x20 | (size >> 16) as u8,
+            (size >> 8) as u8,
+            size as u8,
+        ];
+        void.resize(TRACKS_SEARCH_LEN, 0);
+        let pos = buffer.len() - 13;
+        buffer.splice(pos..pos, void);
+        assert!(!is_audio_only(&buffer));
+    }
+}
@@ -0,0 +1,108 @@
+use crate::ContainerType;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors returned by the detection APIs.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The file could not be opened.
+    Open {
+        /// Path that was passed in.
+        path: PathBuf,
+        /// Underlying IO error.
+        source: io::Error,
+    },
+    /// The file could not be read.
+    Read(io::Error),
+    /// The path points to a directory.
+    IsADirectory {
+        /// Path that was passed in, `None` when only the opened file was.
+        path: Option<PathBuf>,
+    },
+    /// The file (or buffer) has zero length.
+    EmptyFile,
+    /// The file (or buffer) is shorter than the smallest buffer any check can match,
+    /// or too short for a check whose magic it starts with.
+    FileTooSmall {
+        /// Length in bytes.
+        len: u64,
+        /// Length the check needs.
+        min_needed: u64,
+    },
+    /// None of the checks matched.
+    Unidentified,
+    /// More than one check matched, only returned in strict ambiguity mode.
+    /// Contains the matches in priority order.
+    Ambiguous(Vec<ContainerType>),
+    /// The buffer starts with the magic of a well known non media file.
+    /// Contains what was found, eg. "ZIP archive".
+    NotMedia(&'static str),
+    /// A container was listed more than once in `DetectorBuilder::priority`.
+    DuplicatePriority(ContainerType),
+    /// A custom format definitions file is malformed.
+    InvalidDefinitions {
+        /// 1 based line of the problem.
+        line: usize,
+        /// Path of the offending field, eg. `format[1].rule[0].mask`, if known.
+        field: Option<String>,
+        /// What is wrong.
+        message: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Open { path, source } => {
+                write!(f, "failed to open {}: {}", path.display(), source)
+            }
+            Self::Read(source) => write!(f, "failed to read file: {}", source),
+            Self::IsADirectory { path: Some(path) } => {
+                write!(f, "{} is a directory", path.display())
+            }
+            Self::IsADirectory { path: None } => write!(f, "Path is a directory"),
+            Self::EmptyFile => write!(f, "File is empty"),
+            Self::FileTooSmall { len, min_needed } => write!(
+                f,
+                "File is too small ({} bytes, needs at least {})",
+                len, min_needed
+            ),
+            Self::Unidentified => write!(f, "Could Not Identify"),
+            Self::Ambiguous(kinds) => {
+                write!(f, "Ambiguous, matches")?;
+                for (i, kind) in kinds.iter().enumerate() {
+                    let sep = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", sep, kind)?;
+                }
+                Ok(())
+            }
+            Self::NotMedia(name) => write!(f, "Not a media file ({})", name),
+            Self::DuplicatePriority(kind) => write!(f, "{} is listed twice in priority", kind),
+            Self::InvalidDefinitions {
+                line,
+                field: Some(field),
+                message,
+            } => write!(
+                f,
+                "Invalid definitions, line {}, {}: {}",
+                line, field, message
+            ),
+            Self::InvalidDefinitions {
+                line,
+                field: None,
+                message,
+            } => write!(f, "Invalid definitions, line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Open { source, .. } | Self::Read(source) => Some(source),
+            _ => None,
+        }
+    }
+}
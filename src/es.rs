@@ -0,0 +1,83 @@
+//! MPEG video generation of an elementary stream, see [`es_version`].
+
+use crate::{scan, ContainerType};
+
+/// Generation of an MPEG video elementary stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EsVersion {
+    /// MPEG-1 video, ISO/IEC 11172-2
+    Mpeg1,
+    /// MPEG-2 video, ISO/IEC 13818-2
+    Mpeg2,
+}
+
+/// Start code of an extension.
+const EXTENSION_START_CODE: u8 = 0xB5;
+/// Extension ID of the sequence extension, in the high nibble of the byte after its
+/// start code.
+const SEQUENCE_EXTENSION_ID: u8 = 0x1;
+
+/// Generation of the MPEG video elementary stream `buffer` starts with, from the start
+/// code after its sequence header: MPEG-2 puts a sequence extension right after it,
+/// MPEG-1 has none. `None` if `buffer` does not start with a sequence header or ends
+/// before the next start code.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{es_version, EsVersion};
+///
+/// let mut buffer = b"\x00\x00\x01\xB3\x2D\x02\x40\x33\x17\xED\x23\x80".to_vec();
+/// buffer.extend_from_slice(b"\x00\x00\x01\xB5\x14\x8A\x00\x01\x00\x00");
+/// assert_eq!(es_version(&buffer), Some(EsVersion::Mpeg2));
+/// ```
+pub fn es_version(buffer: &[u8]) -> Option<EsVersion> {
+    if !ContainerType::check_es(buffer) {
+        return None;
+    }
+    let next = scan::start_codes(buffer).nth(1)?;
+    match (*buffer.get(next)?, buffer.get(next + 1)) {
+        (EXTENSION_START_CODE, Some(x)) if x >> 4 == SEQUENCE_EXTENSION_ID => {
+            Some(EsVersion::Mpeg2)
+        }
+        // Extension ID cut off.
+        (EXTENSION_START_CODE, None) => None,
+        _ => Some(EsVersion::Mpeg1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sequence header of a 352x240 VCD stream, 29.97 fps at 1150 kbit/s.
+    const MPEG1_SEQUENCE: [u8; 12] = [
+        0x00, 0x00, 0x01, 0xB3, 0x16, 0x00, 0xF0, 0xC4, 0x02, 0xCE, 0xE0, 0xA4,
+    ];
+
+    #[test]
+    fn mpeg1() {
+        let mut buffer = MPEG1_SEQUENCE.to_vec();
+        // GOP header.
+        buffer.extend_from_slice(&[0x00, 0x00, 0x01, 0xB8, 0x00, 0x08, 0x00, 0x00]);
+        assert_eq!(es_version(&buffer), Some(EsVersion::Mpeg1));
+    }
+
+    #[test]
+    fn extension_id() {
+        let mut buffer = MPEG1_SEQUENCE.to_vec();
+        // Sequence display extension, only found after a sequence extension.
+        buffer.extend_from_slice(&[0x00, 0x00, 0x01, 0xB5, 0x23, 0x05, 0x05, 0x05]);
+        assert_eq!(es_version(&buffer), Some(EsVersion::Mpeg1));
+        buffer[16] = 0x14;
+        assert_eq!(es_version(&buffer), Some(EsVersion::Mpeg2));
+    }
+
+    #[test]
+    fn undecided() {
+        assert_eq!(es_version(&MPEG1_SEQUENCE), None);
+        let mut buffer = MPEG1_SEQUENCE.to_vec();
+        buffer.extend_from_slice(&[0x00, 0x00, 0x01, 0xB5]);
+        assert_eq!(es_version(&buffer), None);
+        assert_eq!(es_version(&[0x00, 0x00, 0x01, 0xBA, 0x44]), None);
+    }
+}
@@ -0,0 +1,285 @@
+//! Bindings for Kotlin and Swift, generated with uniffi.
+
+use crate::{ContainerType, Error};
+use std::fmt;
+use std::path::Path;
+
+/// `ContainerType` with names that stay the same in the bindings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum ContainerKind {
+    Mkv,
+    Asf,
+    Gxf,
+    Wtv,
+    Rcwt,
+    Mp4,
+    Ts,
+    Ps,
+    Mxf,
+    M2ts,
+    TivoPs,
+    McPoodlesRaw,
+    Es,
+    H264Es,
+    H265Es,
+    Avi,
+    Wav,
+    Flv,
+    Ogg,
+    Webm,
+    Mka,
+    Mov,
+    ThreeGp,
+    ThreeG2,
+    M4a,
+    FragmentedMp4,
+    Heif,
+    Avif,
+    AvifSequence,
+    RealMedia,
+    Ivf,
+    Y4m,
+    Mpeg4Es,
+    Av1Obu,
+    AacAdts,
+    AacLatm,
+    Mp3,
+    Flac,
+    Ac3,
+    Eac3,
+    Dts,
+    TrueHd,
+    DtsHd,
+    Aiff,
+    Au,
+    Amr,
+    AmrWb,
+    Caf,
+    WavPack,
+    Ape,
+    Musepack,
+    Tta,
+    Shorten,
+    Midi,
+    Dsf,
+    Dff,
+    Rf64,
+    W64,
+    TrackerModule,
+    WebVtt,
+    Scc,
+    Mcc,
+    Ttml,
+    EbuStl,
+    Sami,
+    MicroDvd,
+    SubViewer,
+    Lrc,
+    Pgs,
+    VobSubIdx,
+    VobSubPs,
+    CheetahCap,
+    Pac,
+    SpruceStl,
+    Cdxa,
+    Vob,
+}
+
+impl From<ContainerType> for ContainerKind {
+    fn from(kind: ContainerType) -> Self {
+        match kind {
+            ContainerType::MKV => Self::Mkv,
+            ContainerType::ASF => Self::Asf,
+            ContainerType::GXF => Self::Gxf,
+            ContainerType::WTV => Self::Wtv,
+            ContainerType::RCWT => Self::Rcwt,
+            ContainerType::MP4 => Self::Mp4,
+            ContainerType::TS => Self::Ts,
+            ContainerType::PS => Self::Ps,
+            ContainerType::MXF => Self::Mxf,
+            ContainerType::M2TS => Self::M2ts,
+            ContainerType::TivoPS => Self::TivoPs,
+            ContainerType::McPoodlesRaw => Self::McPoodlesRaw,
+            ContainerType::ES => Self::Es,
+            ContainerType::H264ES => Self::H264Es,
+            ContainerType::H265ES => Self::H265Es,
+            ContainerType::AVI => Self::Avi,
+            ContainerType::WAV => Self::Wav,
+            ContainerType::FLV => Self::Flv,
+            ContainerType::OGG => Self::Ogg,
+            ContainerType::WEBM => Self::Webm,
+            ContainerType::MKA => Self::Mka,
+            ContainerType::MOV => Self::Mov,
+            ContainerType::ThreeGP => Self::ThreeGp,
+            ContainerType::ThreeG2 => Self::ThreeG2,
+            ContainerType::M4A => Self::M4a,
+            ContainerType::FragmentedMP4 => Self::FragmentedMp4,
+            ContainerType::HEIF => Self::Heif,
+            ContainerType::AVIF => Self::Avif,
+            ContainerType::AVIFSequence => Self::AvifSequence,
+            ContainerType::RealMedia => Self::RealMedia,
+            ContainerType::IVF => Self::Ivf,
+            ContainerType::Y4M => Self::Y4m,
+            ContainerType::MPEG4ES => Self::Mpeg4Es,
+            ContainerType::AV1OBU => Self::Av1Obu,
+            ContainerType::AACADTS => Self::AacAdts,
+            ContainerType::AACLATM => Self::AacLatm,
+            ContainerType::MP3 => Self::Mp3,
+            ContainerType::FLAC => Self::Flac,
+            ContainerType::AC3 => Self::Ac3,
+            ContainerType::EAC3 => Self::Eac3,
+            ContainerType::DTS => Self::Dts,
+            ContainerType::TrueHD => Self::TrueHd,
+            ContainerType::DTSHD => Self::DtsHd,
+            ContainerType::AIFF => Self::Aiff,
+            ContainerType::AU => Self::Au,
+            ContainerType::AMR => Self::Amr,
+            ContainerType::AMRWB => Self::AmrWb,
+            ContainerType::CAF => Self::Caf,
+            ContainerType::WavPack => Self::WavPack,
+            ContainerType::APE => Self::Ape,
+            ContainerType::Musepack => Self::Musepack,
+            ContainerType::TTA => Self::Tta,
+            ContainerType::Shorten => Self::Shorten,
+            ContainerType::MIDI => Self::Midi,
+            ContainerType::DSF => Self::Dsf,
+            ContainerType::DFF => Self::Dff,
+            ContainerType::RF64 => Self::Rf64,
+            ContainerType::W64 => Self::W64,
+            ContainerType::TrackerModule => Self::TrackerModule,
+            ContainerType::WebVTT => Self::WebVtt,
+            ContainerType::SCC => Self::Scc,
+            ContainerType::MCC => Self::Mcc,
+            ContainerType::TTML => Self::Ttml,
+            ContainerType::EbuStl => Self::EbuStl,
+            ContainerType::SAMI => Self::Sami,
+            ContainerType::MicroDVD => Self::MicroDvd,
+            ContainerType::SubViewer => Self::SubViewer,
+            ContainerType::LRC => Self::Lrc,
+            ContainerType::PGS => Self::Pgs,
+            ContainerType::VobSubIdx => Self::VobSubIdx,
+            ContainerType::VobSubPS => Self::VobSubPs,
+            ContainerType::CheetahCAP => Self::CheetahCap,
+            ContainerType::PAC => Self::Pac,
+            ContainerType::SpruceSTL => Self::SpruceStl,
+            ContainerType::CDXA => Self::Cdxa,
+            ContainerType::VOB => Self::Vob,
+        }
+    }
+}
+
+/// `Error` flattened to what the bindings can carry.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Error)]
+pub enum DetectError {
+    /// Opening or reading the file failed.
+    Io {
+        message: String,
+    },
+    IsADirectory,
+    EmptyFile,
+    FileTooSmall {
+        len: u64,
+        min_needed: u64,
+    },
+    Unidentified,
+    NotMedia {
+        name: String,
+    },
+}
+
+impl From<Error> for DetectError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Open { .. } | Error::Read(_) => Self::Io {
+                message: e.to_string(),
+            },
+            Error::IsADirectory { .. } => Self::IsADirectory,
+            Error::EmptyFile => Self::EmptyFile,
+            Error::FileTooSmall { len, min_needed } => Self::FileTooSmall { len, min_needed },
+            Error::NotMedia(name) => Self::NotMedia {
+                name: name.to_string(),
+            },
+            // Only come from non default detector options.
+            Error::Unidentified
+            | Error::Ambiguous(_)
+            | Error::DuplicatePriority(_)
+            | Error::InvalidDefinitions { .. } => Self::Unidentified,
+        }
+    }
+}
+
+impl fmt::Display for DetectError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io { message } => write!(f, "{}", message),
+            Self::IsADirectory => write!(f, "{}", Error::IsADirectory { path: None }),
+            Self::EmptyFile => write!(f, "{}", Error::EmptyFile),
+            Self::FileTooSmall { len, min_needed } => write!(
+                f,
+                "{}",
+                Error::FileTooSmall {
+                    len: *len,
+                    min_needed: *min_needed
+                }
+            ),
+            Self::Unidentified => write!(f, "{}", Error::Unidentified),
+            Self::NotMedia { name } => write!(f, "Not a media file ({})", name),
+        }
+    }
+}
+
+impl std::error::Error for DetectError {}
+
+/// `ContainerType::from_bytes` for the bindings.
+#[uniffi::export]
+pub fn detect_bytes(bytes: Vec<u8>) -> Result<ContainerKind, DetectError> {
+    crate::Detector::default()
+        .detect(&bytes)
+        .map(ContainerKind::from)
+        .map_err(DetectError::from)
+}
+
+/// `ContainerType::from_file_path` for the bindings.
+#[uniffi::export]
+pub fn detect_path(path: String) -> Result<ContainerKind, DetectError> {
+    ContainerType::from_file_path(Path::new(&path))
+        .map(ContainerKind::from)
+        .map_err(DetectError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_PRIORITY;
+
+    #[test]
+    fn detect_bytes() {
+        let t = super::detect_bytes(vec![0x1a, 0x45, 0xdf, 0xa3, 0, 1]);
+        assert_eq!(t, Ok(ContainerKind::Mkv));
+        let t = super::detect_bytes(vec![0x42; 64]);
+        assert_eq!(t, Err(DetectError::Unidentified));
+        let t = super::detect_bytes(b"%PDF-1.7".to_vec());
+        assert_eq!(
+            t,
+            Err(DetectError::NotMedia {
+                name: "PDF document".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn detect_path() {
+        let t = super::detect_path("/nonexistent/x.ts".to_string());
+        assert!(matches!(t, Err(DetectError::Io { .. })));
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mkv.mkv");
+        assert_eq!(super::detect_path(path.to_string()), Ok(ContainerKind::Mkv));
+    }
+
+    #[test]
+    fn kinds_are_distinct() {
+        let kinds: Vec<ContainerKind> = DEFAULT_PRIORITY.iter().map(|x| (*x).into()).collect();
+        for (i, kind) in kinds.iter().enumerate() {
+            assert!(!kinds[..i].contains(kind), "{:?}", kind);
+        }
+    }
+}
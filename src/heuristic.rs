@@ -0,0 +1,227 @@
+//! Scoring of elementary streams without a recognizable start, eg. a capture that
+//! began mid GOP. See `DetectorBuilder::es_heuristics`.
+//!
+//! Every `00 00 01` start code is judged as MPEG-2 video, H.264 and H.265: is the
+//! code (or NAL unit header) valid for the codec, and in a plausible order. A codec
+//! matches when:
+//! - There are at least [`MIN_START_CODES`] start codes.
+//! - No stretch without a start code, including the ones at both ends, is longer
+//!   than half the buffer.
+//! - At least [`SCORE_THRESHOLD`] of the start codes are valid and plausible for it,
+//!   and it scores higher than the other two.
+
+use crate::{scan, ContainerType};
+
+/// Fewest start codes a buffer needs to be scored at all.
+pub(crate) const MIN_START_CODES: usize = 8;
+
+/// Fraction of start codes that have to be valid and plausible for a codec.
+pub(crate) const SCORE_THRESHOLD: f64 = 0.9;
+
+/// Most start codes looked at, the rest of the buffer is ignored.
+const MAX_START_CODES: usize = 4096;
+
+/// Elementary stream `buffer` most likely is, among `kinds`.
+pub(crate) fn detect(buffer: &[u8], kinds: &[ContainerType]) -> Option<ContainerType> {
+    let codes: Vec<usize> = scan::start_codes(buffer)
+        .take(MAX_START_CODES)
+        .filter(|x| *x + 1 < buffer.len())
+        .collect();
+    if codes.len() < MIN_START_CODES || !spread(&codes, buffer.len()) {
+        return None;
+    }
+
+    let mut scores: Vec<(ContainerType, f64)> = kinds
+        .iter()
+        .filter_map(|kind| match kind {
+            ContainerType::ES => Some((*kind, mpeg2_score(buffer, &codes))),
+            ContainerType::H264ES => Some((*kind, h264_score(buffer, &codes))),
+            ContainerType::H265ES => Some((*kind, h265_score(buffer, &codes))),
+            _ => None,
+        })
+        .collect();
+    scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+    match scores.as_slice() {
+        [(kind, best), rest @ ..]
+            if *best >= SCORE_THRESHOLD && rest.iter().all(|x| x.1 < *best) =>
+        {
+            Some(*kind)
+        }
+        _ => None,
+    }
+}
+
+/// Whether no gap between start codes (or the buffer ends) is over half the buffer.
+fn spread(codes: &[usize], len: usize) -> bool {
+    let max_gap = len / 2;
+    let first = codes.first().map_or(len, |x| *x);
+    let last = codes.last().map_or(0, |x| *x);
+    first <= max_gap && len - last <= max_gap && codes.windows(2).all(|x| x[1] - x[0] <= max_gap)
+}
+
+fn ratio(good: usize, total: usize) -> f64 {
+    good as f64 / total as f64
+}
+
+/// MPEG-2 video start codes: pictures (0x00) each followed by their slices, which start
+/// at row 1 and go down one row at a time (0x01-0xAF), plus the sequence level codes.
+/// System start codes (0xB9-0xFF) never belong.
+fn mpeg2_score(buffer: &[u8], codes: &[usize]) -> f64 {
+    let mut good = 0;
+    let mut pictures = 0;
+    // A picture whose first slice has not come yet.
+    let mut picture_open = false;
+    let mut last_slice: Option<u8> = None;
+    for code in codes.iter().map(|x| buffer[*x]) {
+        let plausible = match code {
+            0x00 => {
+                let ok = !picture_open;
+                pictures += 1;
+                picture_open = true;
+                ok
+            }
+            0x01..=0xAF => {
+                let ok = match (picture_open, last_slice) {
+                    (true, _) => code == 0x01,
+                    (false, Some(prev)) => code == prev || code == prev + 1,
+                    // Capture started in the middle of a picture.
+                    (false, None) => true,
+                };
+                picture_open = false;
+                last_slice = Some(code);
+                ok
+            }
+            // Extension and user data follow headers, not slices.
+            0xB2 | 0xB5 => last_slice.is_none(),
+            // Sequence header, sequence end and GOP.
+            0xB3 | 0xB7 | 0xB8 => !picture_open,
+            _ => false,
+        };
+        if !(0x01..=0xAF).contains(&code) {
+            last_slice = None;
+        }
+        good += usize::from(plausible);
+    }
+    if pictures == 0 {
+        return 0.0;
+    }
+    ratio(good, codes.len())
+}
+
+/// Whether `header` is a valid H.264 NAL unit header: forbidden bit clear, a known
+/// type, and a nal_ref_idc that agrees with it (set for IDR slices and parameter sets,
+/// clear for SEI and delimiters).
+pub(crate) fn h264_header_valid(header: u8) -> bool {
+    let referenced = header & 0x60 != 0;
+    header & 0x80 == 0
+        && match header & 0x1F {
+            1..=4 | 13..=15 | 19 | 20 => true,
+            5 | 7 | 8 => referenced,
+            6 | 9..=12 => !referenced,
+            _ => false,
+        }
+}
+
+/// H.264 NAL unit headers, see `h264_header_valid`.
+fn h264_score(buffer: &[u8], codes: &[usize]) -> f64 {
+    let mut good = 0;
+    let mut slices = 0;
+    for header in codes.iter().map(|x| buffer[*x]) {
+        let valid = h264_header_valid(header);
+        if valid && matches!(header & 0x1F, 1..=5 | 20) {
+            slices += 1;
+        }
+        good += usize::from(valid);
+    }
+    if slices == 0 {
+        return 0.0;
+    }
+    ratio(good, codes.len())
+}
+
+/// Whether `b0` and `b1` are a valid H.265 NAL unit header: forbidden bit clear, a
+/// known type, layer 0 and a non zero temporal id plus 1.
+pub(crate) fn h265_header_valid(b0: u8, b1: u8) -> bool {
+    b0 & 0x81 == 0 && b1 >> 3 == 0 && b1 & 0x07 != 0 && matches!(b0 >> 1, 0..=9 | 16..=21 | 32..=40)
+}
+
+/// H.265 NAL unit headers, see `h265_header_valid`.
+fn h265_score(buffer: &[u8], codes: &[usize]) -> f64 {
+    let mut good = 0;
+    let mut slices = 0;
+    for pos in codes {
+        let (b0, b1) = (buffer[*pos], buffer[pos + 1]);
+        let kind = b0 >> 1;
+        let valid = h265_header_valid(b0, b1);
+        if valid && matches!(kind, 0..=9 | 16..=21) {
+            slices += 1;
+        }
+        good += usize::from(valid);
+    }
+    if slices == 0 {
+        return 0.0;
+    }
+    ratio(good, codes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [ContainerType; 3] = [
+        ContainerType::ES,
+        ContainerType::H264ES,
+        ContainerType::H265ES,
+    ];
+
+    /// `units` joined with 3 byte start codes, each followed by `filler` bytes.
+    fn stream(units: &[&[u8]], filler: usize) -> Vec<u8> {
+        let mut buffer = vec![0x55; filler];
+        for unit in units {
+            buffer.extend_from_slice(&[0, 0, 1]);
+            buffer.extend_from_slice(unit);
+            buffer.extend((0..filler).map(|x| (x % 200 + 20) as u8));
+        }
+        buffer
+    }
+
+    #[test]
+    fn h264() {
+        // AUD, then IDR, then non IDR slices.
+        let mut units: Vec<&[u8]> = vec![&[0x09, 0xF0], &[0x65, 0x88]];
+        units.extend([&[0x41u8, 0x9A][..]; 10]);
+        let buffer = stream(&units, 300);
+        assert_eq!(detect(&buffer, &KINDS), Some(ContainerType::H264ES));
+        assert_eq!(detect(&buffer, &[ContainerType::ES]), None);
+    }
+
+    #[test]
+    fn h265() {
+        // AUD, then TRAIL_R slices, layer 0, temporal id 0.
+        let mut units: Vec<&[u8]> = vec![&[0x46, 0x01, 0x50]];
+        units.extend([&[0x02u8, 0x01, 0xD0][..]; 10]);
+        let buffer = stream(&units, 300);
+        assert_eq!(detect(&buffer, &KINDS), Some(ContainerType::H265ES));
+    }
+
+    #[test]
+    fn too_few() {
+        let units: Vec<&[u8]> = vec![&[0x41, 0x9A]; MIN_START_CODES - 1];
+        assert_eq!(detect(&stream(&units, 300), &KINDS), None);
+    }
+
+    #[test]
+    fn clustered() {
+        let units: Vec<&[u8]> = vec![&[0x41, 0x9A]; 20];
+        let mut buffer = stream(&units, 10);
+        buffer.extend(vec![0x55; buffer.len() * 2]);
+        assert_eq!(detect(&buffer, &KINDS), None);
+    }
+
+    #[test]
+    fn picture_without_slices() {
+        // Big endian 1s, eg. a table of integers, look like back to back pictures.
+        let buffer: Vec<u8> = (0..64).flat_map(|_| [0, 0, 0, 1]).collect();
+        assert_eq!(detect(&buffer, &KINDS), None);
+    }
+}
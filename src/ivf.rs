@@ -0,0 +1,72 @@
+//! Codec of an IVF file, see [`ivf_codec`].
+
+use crate::bytes::slice_at;
+use crate::ContainerType;
+
+/// Codec of an IVF file, from the fourcc in its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IvfCodec {
+    /// VP8 video, `VP80`
+    Vp8,
+    /// VP9 video, `VP90`
+    Vp9,
+    /// AV1 video, `AV01`
+    Av1,
+    /// Any other fourcc, as written in the header
+    Other([u8; 4]),
+}
+
+/// Offset of the fourcc in the IVF header.
+const FOURCC_OFFSET: usize = 8;
+
+/// Codec of the IVF file `buffer` starts with. `None` if `buffer` does not start
+/// with an IVF header.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{ivf_codec, IvfCodec};
+///
+/// let header = b"DKIF\x00\x00\x20\x00AV01";
+/// assert_eq!(ivf_codec(header), Some(IvfCodec::Av1));
+/// ```
+pub fn ivf_codec(buffer: &[u8]) -> Option<IvfCodec> {
+    if !ContainerType::check_ivf(buffer) {
+        return None;
+    }
+    let codec = match slice_at(buffer, FOURCC_OFFSET, 4)? {
+        b"VP80" => IvfCodec::Vp8,
+        b"VP90" => IvfCodec::Vp9,
+        b"AV01" => IvfCodec::Av1,
+        x => IvfCodec::Other([x[0], x[1], x[2], x[3]]),
+    };
+    Some(codec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codecs() {
+        let mut header = *b"DKIF\x00\x00\x20\x00VP80";
+        assert_eq!(ivf_codec(&header), Some(IvfCodec::Vp8));
+        header[8..].copy_from_slice(b"VP90");
+        assert_eq!(ivf_codec(&header), Some(IvfCodec::Vp9));
+        header[8..].copy_from_slice(b"AV01");
+        assert_eq!(ivf_codec(&header), Some(IvfCodec::Av1));
+        header[8..].copy_from_slice(b"H264");
+        assert_eq!(ivf_codec(&header), Some(IvfCodec::Other(*b"H264")));
+    }
+
+    #[test]
+    fn malformed() {
+        let good = *b"DKIF\x00\x00\x20\x00VP90";
+        for len in 0..good.len() {
+            assert_eq!(ivf_codec(&good[..len]), None, "{}", len);
+        }
+        // Version 1.
+        let mut t = good;
+        t[4] = 1;
+        assert_eq!(ivf_codec(&t), None);
+    }
+}
@@ -29,15 +29,91 @@ let mut file = File::open("some.abc").unwrap();
 let kind = media_infer::ContainerType::from_file(&mut file);
 ```
  */
+mod bytes;
+#[cfg(feature = "ccextractor-abi")]
+pub mod ccx;
+#[cfg(feature = "tokio-util")]
+mod codec;
+mod custom;
+#[cfg(feature = "definitions")]
+mod definitions;
+mod detector;
+mod diagnose;
+mod dts;
+mod ebml;
+mod error;
+mod es;
+#[cfg(feature = "uniffi")]
+pub mod ffi;
+mod heuristic;
+mod ivf;
+mod m2ts;
+mod mcc;
+mod mpeg_audio;
+mod nested;
+#[cfg(feature = "napi")]
+pub mod node;
+mod ogg;
+#[cfg(any(test, feature = "samples"))]
+pub mod samples;
+mod scan;
+mod signature;
+mod stream;
+mod text;
+mod tracker;
+#[cfg(feature = "watch")]
+mod watch;
+mod xml;
+
+#[cfg(feature = "tokio-util")]
+pub use codec::{ContainerSniffCodec, SniffFrame};
+pub use custom::{CustomFormat, Detected, Rule};
+pub use detector::{Confidence, Detector, DetectorBuilder, DEFAULT_PRIORITY};
+pub use diagnose::{diagnose, DiagnosisReport, NearMiss};
+pub use dts::{dts_sync, DtsSync};
+pub use error::Error;
+pub use es::{es_version, EsVersion};
+pub use ivf::{ivf_codec, IvfCodec};
+pub use m2ts::{m2ts_profile, M2tsProfile};
+pub use mcc::{mcc_version, MccVersion};
+pub use nested::detect_nested;
+pub use ogg::{ogg_codec, OggCodec};
+pub use signature::{magic_definitions, Signature, SIGNATURES};
+pub use stream::{Detection, StreamDetector};
+pub use tracker::{tracker_format, TrackerFormat};
+#[cfg(feature = "watch")]
+pub use watch::{watch_dir, WatchHandle, WatchOptions};
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+use std::convert::TryFrom;
 use std::fmt;
 use std::fs::File;
-use std::io::prelude::*;
+use std::io;
 use std::path::Path;
 use std::str::FromStr;
 
+/// Smallest buffer any of the checks can match.
+const MIN_BUFFER_LEN: usize = 4;
+
+/// Size of a TS packet.
+const TS_PACKET_LEN: usize = 188;
+
+/// Number of packets the TS and M2TS checks look at.
+const TS_PACKETS_TO_PROBE: usize = 10;
+
+/// Default number of probed TS packets that need to be valid.
+const DEFAULT_TS_SYNC_THRESHOLD: usize = 7;
+
+/// Default number of bytes searched for a PS pack header.
+/// Same window CCExtractor uses in its stream detection.
+const DEFAULT_PS_WINDOW: usize = 50000;
+
 /// Enum of the vairous Container Types.
 /// Does not contain Unknown. Methods throw error if container cannot be identified.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum ContainerType {
     /// Matroska stream file
     MKV,
@@ -61,220 +137,2189 @@ pub enum ContainerType {
     M2TS,
     /// TIVO Program Stream
     TivoPS,
+    /// McPoodle's raw caption dump, broadcast or DVD flavour
     McPoodlesRaw,
-    /// Elementary Stream
+    /// MPEG-1 or MPEG-2 video Elementary Stream, see `es_version` to tell them apart
     ES,
+    /// H.264 Annex B Elementary Stream
+    H264ES,
+    /// H.265 Annex B Elementary Stream
+    H265ES,
+    /// Audio Video Interleave
+    AVI,
+    /// Waveform Audio
+    WAV,
+    /// Flash Video
+    FLV,
+    /// Ogg, including OGV and OGA
+    OGG,
+    /// WebM, the Matroska subset with the `webm` DocType
+    WEBM,
+    /// Matroska audio, a Matroska file without video tracks
+    MKA,
+    /// QuickTime File Format
+    MOV,
+    /// 3GPP, ISO base media files from mobile phones
+    ThreeGP,
+    /// 3GPP2, ISO base media files from CDMA phones
+    ThreeG2,
+    /// MPEG-4 audio, including M4B audiobooks and M4P protected audio
+    M4A,
+    /// Fragmented MP4 media segment, as used by DASH, HLS and CMAF
+    FragmentedMP4,
+    /// HEIF still image or image sequence, including HEIC
+    HEIF,
+    /// AVIF still image
+    AVIF,
+    /// AVIF image sequence
+    AVIFSequence,
+    /// RealMedia, including RMVB
+    RealMedia,
+    /// IVF, the raw VP8, VP9 and AV1 bitstream container of codec test vectors
+    IVF,
+    /// YUV4MPEG2, raw video with a one line text header
+    Y4M,
+    /// MPEG-4 Part 2 (DivX, Xvid) video Elementary Stream
+    MPEG4ES,
+    /// AV1 low overhead bitstream, a sequence of OBUs
+    AV1OBU,
+    /// AAC audio in ADTS frames
+    AACADTS,
+    /// AAC audio in LATM, in LOAS (`AudioSyncStream`) framing
+    AACLATM,
+    /// MPEG audio, MP3 or the MP1 and MP2 sharing its framing, with or without an ID3v2 tag
+    MP3,
+    /// Native FLAC
+    FLAC,
+    /// Dolby Digital (AC-3) Elementary Stream
+    AC3,
+    /// Dolby Digital Plus (E-AC-3) Elementary Stream
+    EAC3,
+    /// DTS Elementary Stream, see `dts_sync` for how its words are packed
+    DTS,
+    /// Dolby TrueHD or MLP Elementary Stream
+    TrueHD,
+    /// DTS-HD (High Resolution or Master Audio) Elementary Stream, a DTS core with
+    /// extension substreams or the extension substreams alone
+    DTSHD,
+    /// Audio Interchange File Format, plain (AIFF) or compressed (AIFF-C)
+    AIFF,
+    /// Sun/NeXT audio
+    AU,
+    /// Adaptive Multi-Rate narrowband speech, AMR storage format
+    AMR,
+    /// Adaptive Multi-Rate wideband speech, AMR storage format
+    AMRWB,
+    /// Apple Core Audio Format
+    CAF,
+    /// WavPack
+    WavPack,
+    /// Monkey's Audio
+    APE,
+    /// Musepack, stream version 7 or 8
+    Musepack,
+    /// True Audio
+    TTA,
+    /// Shorten
+    Shorten,
+    /// Standard MIDI File
+    MIDI,
+    /// DSD Stream File
+    DSF,
+    /// DSD Interchange File Format (DSDIFF)
+    DFF,
+    /// RF64, WAV with 64 bit sizes
+    RF64,
+    /// Sony Wave64
+    W64,
+    /// Tracker module (MOD, XM, S3M or IT), format through `tracker_format`
+    TrackerModule,
+    /// WebVTT subtitles
+    WebVTT,
+    /// Scenarist Closed Captions
+    SCC,
+    /// MacCaption closed captions, version through `mcc_version`
+    MCC,
+    /// Timed Text Markup Language, DFXP included
+    TTML,
+    /// EBU Tech 3264 subtitles (EBU STL)
+    EbuStl,
+    /// Synchronized Accessible Media Interchange captions
+    SAMI,
+    /// MicroDVD subtitles
+    MicroDVD,
+    /// SubViewer subtitles, Google's `.sbv` included
+    SubViewer,
+    /// LRC synced lyrics
+    LRC,
+    /// Blu-ray Presentation Graphic Stream subtitles (SUP)
+    PGS,
+    /// VobSub index, the text half of a DVD subtitle rip (IDX)
+    VobSubIdx,
+    /// VobSub subpictures, the MPEG-PS half of a DVD subtitle rip (SUB)
+    VobSubPS,
+    /// Cheetah binary captions (CAP)
+    CheetahCAP,
+    /// Screen Electronics subtitles (PAC)
+    PAC,
+    /// Spruce subtitle script, also called STL
+    SpruceSTL,
+    /// Video CD `.DAT` file, MPEG-1 PS sectors in a RIFF CDXA file
+    CDXA,
+    /// DVD-Video Object, an MPEG-2 PS starting with a navigation pack
+    VOB,
 }
 
-impl ContainerType {
-    /// Function to infer Container from a slice of bytes.
-    /// Throws Error if identification fails.
-    pub fn from_bytes(buffer: &[u8]) -> Result<Self, String> {
-        if Self::check_asf(buffer) {
-            return Ok(ContainerType::ASF);
-        } else if Self::check_mkv(buffer) {
-            return Ok(ContainerType::MKV);
-        } else if Self::check_gxf(buffer) {
-            return Ok(ContainerType::GXF);
-        } else if Self::check_wtv(buffer) {
-            return Ok(ContainerType::WTV);
-        } else if Self::check_rcwt(buffer) {
-            return Ok(ContainerType::RCWT);
-        } else if Self::check_mp4(&buffer) {
-            return Ok(ContainerType::MP4);
-        } else if Self::check_mxf(&buffer) {
-            return Ok(ContainerType::MXF);
-        } else if Self::check_ts(&buffer) {
-            return Ok(ContainerType::TS);
-        } else if Self::check_m2ts(&buffer) {
-            return Ok(ContainerType::M2TS);
-        } else if Self::check_ps(&buffer) {
-            return Ok(ContainerType::PS);
-        } else if Self::check_tivo_ps(&buffer) {
-            return Ok(ContainerType::TivoPS);
-        } else if Self::check_es(&buffer) {
-            return Ok(ContainerType::ES);
-        }
-
-        Err("Could Not Identify".to_string())
+impl ContainerType {
+    /// Function to infer Container from a slice of bytes.
+    /// Throws Error if identification fails.
+    pub fn from_bytes(buffer: &[u8]) -> Result<Self, String> {
+        Detector::default()
+            .detect(buffer)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Function to infer all matching Containers from a slice of bytes.
+    /// Ordered by priority, the first one is what `from_bytes` returns.
+    pub fn from_bytes_all(buffer: &[u8]) -> Vec<Self> {
+        Detector::default().detect_all(buffer)
+    }
+
+    /// Whether the check for this container alone matches `buffer`, with the default
+    /// options. Unlike `from_bytes` the other checks and the priority play no part.
+    pub fn matches(&self, buffer: &[u8]) -> bool {
+        Detector::builder()
+            .formats(&[*self])
+            .non_media_check(false)
+            .build()
+            .detect(buffer)
+            .is_ok()
+    }
+
+    /// Short name of this container, the one `from_str` accepts.
+    pub fn short_name(&self) -> &'static str {
+        match self {
+            Self::MKV => "mkv",
+            Self::ASF => "asf",
+            Self::GXF => "gxf",
+            Self::WTV => "wtv",
+            Self::RCWT => "rcwt",
+            Self::MP4 => "mp4",
+            Self::TS => "ts",
+            Self::M2TS => "m2ts",
+            Self::PS => "ps",
+            Self::TivoPS => "tivops",
+            Self::MXF => "mxf",
+            Self::McPoodlesRaw => "raw",
+            Self::ES => "es",
+            Self::H264ES => "h264",
+            Self::H265ES => "h265",
+            Self::AVI => "avi",
+            Self::WAV => "wav",
+            Self::FLV => "flv",
+            Self::OGG => "ogg",
+            Self::WEBM => "webm",
+            Self::MKA => "mka",
+            Self::MOV => "mov",
+            Self::ThreeGP => "3gp",
+            Self::ThreeG2 => "3g2",
+            Self::M4A => "m4a",
+            Self::FragmentedMP4 => "fmp4",
+            Self::HEIF => "heif",
+            Self::AVIF => "avif",
+            Self::AVIFSequence => "avis",
+            Self::RealMedia => "rm",
+            Self::IVF => "ivf",
+            Self::Y4M => "y4m",
+            Self::MPEG4ES => "mpeg4es",
+            Self::AV1OBU => "obu",
+            Self::AACADTS => "aac",
+            Self::AACLATM => "latm",
+            Self::MP3 => "mp3",
+            Self::FLAC => "flac",
+            Self::AC3 => "ac3",
+            Self::EAC3 => "eac3",
+            Self::DTS => "dts",
+            Self::TrueHD => "truehd",
+            Self::DTSHD => "dtshd",
+            Self::AIFF => "aiff",
+            Self::AU => "au",
+            Self::AMR => "amr",
+            Self::AMRWB => "awb",
+            Self::CAF => "caf",
+            Self::WavPack => "wv",
+            Self::APE => "ape",
+            Self::Musepack => "mpc",
+            Self::TTA => "tta",
+            Self::Shorten => "shn",
+            Self::MIDI => "mid",
+            Self::DSF => "dsf",
+            Self::DFF => "dff",
+            Self::RF64 => "rf64",
+            Self::W64 => "w64",
+            Self::TrackerModule => "mod",
+            Self::WebVTT => "vtt",
+            Self::SCC => "scc",
+            Self::MCC => "mcc",
+            Self::TTML => "ttml",
+            Self::EbuStl => "ebustl",
+            Self::SAMI => "smi",
+            Self::MicroDVD => "microdvd",
+            Self::SubViewer => "sbv",
+            Self::LRC => "lrc",
+            Self::PGS => "sup",
+            Self::VobSubIdx => "idx",
+            Self::VobSubPS => "vobsub",
+            Self::CheetahCAP => "cap",
+            Self::PAC => "pac",
+            Self::SpruceSTL => "spruce-stl",
+            Self::CDXA => "dat",
+            Self::VOB => "vob",
+        }
+    }
+
+    /// GStreamer caps for this container, as its typefind reports them.
+    /// TS and M2TS carry their packet size. DVD VOBs, VobSub `.sub` files and Video CD
+    /// `.DAT` files are found as MPEG PS, MicroDVD and SubViewer both as
+    /// `application/x-subtitle`, so those caps are shared.
+    /// Typefind has nothing for TrueHD, PGS, AVIF and AV1 OBU streams, they get the caps
+    /// of the GStreamer elements reading them.
+    /// GStreamer knows none of these made up media types: `video/x-ms-wtv`,
+    /// `application/x-rcwt`, `video/x-tivo`, `application/x-mcpoodles-raw`,
+    /// `image/heif`, `audio/x-dff`, `audio/x-w64`, `application/x-ebu-stl`,
+    /// `application/x-vobsub-idx`, `application/x-cheetah-cap`, `application/x-pac` and
+    /// `application/x-spruce-stl`. Neither the `hd` field of DTS-HD nor the `sequence`
+    /// field of AVIF sequences.
+    pub fn gst_caps(&self) -> &'static str {
+        match self {
+            Self::MKV => "video/x-matroska",
+            Self::ASF => "video/x-ms-asf",
+            Self::GXF => "application/gxf",
+            Self::WTV => "video/x-ms-wtv",
+            Self::RCWT => "application/x-rcwt",
+            Self::MP4 => "video/quicktime, variant=(string)iso",
+            Self::TS => "video/mpegts, systemstream=(boolean)true, packetsize=(int)188",
+            Self::M2TS => "video/mpegts, systemstream=(boolean)true, packetsize=(int)192",
+            Self::PS => "video/mpeg, systemstream=(boolean)true, mpegversion=(int)2",
+            Self::MXF => "application/mxf",
+            Self::TivoPS => "video/x-tivo",
+            Self::McPoodlesRaw => "application/x-mcpoodles-raw",
+            Self::ES => "video/mpeg, systemstream=(boolean)false, mpegversion=(int)2",
+            Self::H264ES => "video/x-h264, stream-format=(string)byte-stream",
+            Self::H265ES => "video/x-h265, stream-format=(string)byte-stream",
+            Self::AVI => "video/x-msvideo",
+            Self::WAV => "audio/x-wav",
+            Self::FLV => "video/x-flv",
+            Self::OGG => "application/ogg",
+            Self::WEBM => "video/webm",
+            Self::MKA => "audio/x-matroska",
+            Self::MOV => "video/quicktime, variant=(string)apple",
+            Self::ThreeGP => "application/x-3gp",
+            Self::ThreeG2 => "video/quicktime, variant=(string)3g2",
+            Self::M4A => "audio/x-m4a",
+            Self::FragmentedMP4 => "video/quicktime, variant=(string)iso-fragmented",
+            Self::HEIF => "image/heif",
+            Self::AVIF => "image/avif",
+            Self::AVIFSequence => "image/avif, sequence=(boolean)true",
+            Self::RealMedia => "application/vnd.rn-realmedia",
+            Self::IVF => "video/x-ivf",
+            Self::Y4M => "application/x-yuv4mpeg, y4mversion=(int)2",
+            Self::MPEG4ES => "video/mpeg, systemstream=(boolean)false, mpegversion=(int)4",
+            Self::AV1OBU => "video/x-av1, stream-format=(string)obu-stream",
+            Self::AACADTS => "audio/mpeg, mpegversion=(int)4, stream-format=(string)adts",
+            Self::AACLATM => "audio/mpeg, mpegversion=(int)4, stream-format=(string)loas",
+            Self::MP3 => "audio/mpeg, mpegversion=(int)1",
+            Self::FLAC => "audio/x-flac",
+            Self::AC3 => "audio/x-ac3",
+            Self::EAC3 => "audio/x-eac3",
+            Self::DTS => "audio/x-dts",
+            Self::TrueHD => "audio/x-true-hd",
+            Self::DTSHD => "audio/x-dts, hd=(boolean)true",
+            Self::AIFF => "audio/x-aiff",
+            Self::AU => "audio/x-au",
+            Self::AMR => "audio/x-amr-nb-sh",
+            Self::AMRWB => "audio/x-amr-wb-sh",
+            Self::CAF => "audio/x-caf",
+            Self::WavPack => "audio/x-wavpack",
+            Self::APE => "application/x-ape",
+            Self::Musepack => "audio/x-musepack",
+            Self::TTA => "audio/x-ttafile",
+            Self::Shorten => "audio/x-shorten",
+            Self::MIDI => "audio/midi",
+            Self::DSF => "audio/x-dsf",
+            Self::DFF => "audio/x-dff",
+            Self::RF64 => "audio/x-rf64",
+            Self::W64 => "audio/x-w64",
+            Self::TrackerModule => "audio/x-mod",
+            Self::WebVTT => "application/x-subtitle-vtt",
+            Self::SCC => "application/x-scc",
+            Self::MCC => "application/x-mcc",
+            Self::TTML => "application/ttml+xml",
+            Self::EbuStl => "application/x-ebu-stl",
+            Self::SAMI => "application/x-subtitle-sami",
+            Self::MicroDVD => "application/x-subtitle",
+            Self::SubViewer => "application/x-subtitle",
+            Self::LRC => "application/x-subtitle-lrc",
+            Self::PGS => "subpicture/x-pgs",
+            Self::VobSubIdx => "application/x-vobsub-idx",
+            Self::VobSubPS => "video/mpeg, systemstream=(boolean)true, mpegversion=(int)2",
+            Self::CheetahCAP => "application/x-cheetah-cap",
+            Self::PAC => "application/x-pac",
+            Self::SpruceSTL => "application/x-spruce-stl",
+            Self::CDXA => "video/mpeg, systemstream=(boolean)true, mpegversion=(int)2",
+            Self::VOB => "video/mpeg, systemstream=(boolean)true, mpegversion=(int)2",
+        }
+    }
+
+    /// FFmpeg demuxer name for this container, what `ffmpeg -f` takes.
+    /// M2TS is read by the `mpegts` demuxer too, WebM and MKA by the Matroska one and
+    /// MOV, 3GP, 3G2, M4A, fragmented MP4, HEIF and AVIF by the MP4 one.
+    /// RF64 is read by the `wav` demuxer and DFF by the `iff` one, which also reads 8SVX
+    /// and other IFF forms. Tracker modules need FFmpeg built with libopenmpt.
+    /// VobSub `.sub` files are read by the `mpeg` demuxer, or through their index by
+    /// the `vobsub` one. Video CD `.DAT` files and DVD VOBs are read by the `mpeg`
+    /// demuxer too.
+    /// `None` for what FFmpeg cannot demux: RCWT, McPoodle's raw files, MIDI files, TTML,
+    /// EBU STL, Cheetah CAP and PAC.
+    pub fn ffmpeg_format(&self) -> Option<&'static str> {
+        match self {
+            Self::MKV => Some("matroska,webm"),
+            Self::ASF => Some("asf"),
+            Self::GXF => Some("gxf"),
+            Self::WTV => Some("wtv"),
+            Self::MP4 => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::TS => Some("mpegts"),
+            Self::M2TS => Some("mpegts"),
+            Self::PS => Some("mpeg"),
+            Self::MXF => Some("mxf"),
+            Self::TivoPS => Some("ty"),
+            Self::ES => Some("mpegvideo"),
+            Self::H264ES => Some("h264"),
+            Self::H265ES => Some("hevc"),
+            Self::AVI => Some("avi"),
+            Self::WAV => Some("wav"),
+            Self::FLV => Some("flv"),
+            Self::OGG => Some("ogg"),
+            Self::WEBM => Some("matroska,webm"),
+            Self::MKA => Some("matroska,webm"),
+            Self::MOV => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::ThreeGP => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::ThreeG2 => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::M4A => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::FragmentedMP4 => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::HEIF => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::AVIF => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::AVIFSequence => Some("mov,mp4,m4a,3gp,3g2,mj2"),
+            Self::RealMedia => Some("rm"),
+            Self::IVF => Some("ivf"),
+            Self::Y4M => Some("yuv4mpegpipe"),
+            Self::MPEG4ES => Some("m4v"),
+            Self::AV1OBU => Some("obu"),
+            Self::AACADTS => Some("aac"),
+            Self::AACLATM => Some("loas"),
+            Self::MP3 => Some("mp3"),
+            Self::FLAC => Some("flac"),
+            Self::AC3 => Some("ac3"),
+            Self::EAC3 => Some("eac3"),
+            Self::DTS => Some("dts"),
+            Self::TrueHD => Some("truehd"),
+            Self::DTSHD => Some("dts"),
+            Self::AIFF => Some("aiff"),
+            Self::AU => Some("au"),
+            Self::AMR | Self::AMRWB => Some("amr"),
+            Self::CAF => Some("caf"),
+            Self::WavPack => Some("wv"),
+            Self::APE => Some("ape"),
+            Self::Musepack => Some("mpc"),
+            Self::TTA => Some("tta"),
+            Self::Shorten => Some("shn"),
+            Self::DSF => Some("dsf"),
+            Self::DFF => Some("iff"),
+            Self::RF64 => Some("wav"),
+            Self::W64 => Some("w64"),
+            Self::TrackerModule => Some("libopenmpt"),
+            Self::WebVTT => Some("webvtt"),
+            Self::SCC => Some("scc"),
+            Self::MCC => Some("mcc"),
+            Self::SAMI => Some("sami"),
+            Self::MicroDVD => Some("microdvd"),
+            Self::SubViewer => Some("subviewer"),
+            Self::LRC => Some("lrc"),
+            Self::PGS => Some("sup"),
+            Self::VobSubIdx => Some("vobsub"),
+            Self::VobSubPS => Some("mpeg"),
+            Self::SpruceSTL => Some("stl"),
+            Self::CDXA => Some("mpeg"),
+            Self::VOB => Some("mpeg"),
+            Self::RCWT
+            | Self::McPoodlesRaw
+            | Self::MIDI
+            | Self::TTML
+            | Self::EbuStl
+            | Self::CheetahCAP
+            | Self::PAC => None,
+        }
+    }
+
+    /// Container for an FFmpeg demuxer name, eg. the `format_name` of ffprobe.
+    /// Accepts comma separated alias lists ("matroska,webm") and single aliases
+    /// ("webm"), ignoring case. The first known alias wins.
+    /// `mpegts` is always TS and `webm` always MKV, FFmpeg does not tell M2TS or WebM
+    /// apart. `iff` is always DFF.
+    pub fn from_ffmpeg_format(name: &str) -> Option<Self> {
+        name.split(',')
+            .find_map(|x| match x.trim().to_ascii_lowercase().as_str() {
+                "matroska" | "webm" => Some(Self::MKV),
+                "asf" => Some(Self::ASF),
+                "gxf" => Some(Self::GXF),
+                "wtv" => Some(Self::WTV),
+                "mov" | "mp4" | "m4a" | "3gp" | "3g2" | "mj2" => Some(Self::MP4),
+                "mpegts" => Some(Self::TS),
+                "mpeg" => Some(Self::PS),
+                "mxf" => Some(Self::MXF),
+                "ty" => Some(Self::TivoPS),
+                "mpegvideo" => Some(Self::ES),
+                "h264" => Some(Self::H264ES),
+                "hevc" => Some(Self::H265ES),
+                "avi" => Some(Self::AVI),
+                "wav" => Some(Self::WAV),
+                "flv" => Some(Self::FLV),
+                "ogg" => Some(Self::OGG),
+                "rm" => Some(Self::RealMedia),
+                "ivf" => Some(Self::IVF),
+                "yuv4mpegpipe" => Some(Self::Y4M),
+                "m4v" => Some(Self::MPEG4ES),
+                "obu" => Some(Self::AV1OBU),
+                "aac" => Some(Self::AACADTS),
+                "loas" => Some(Self::AACLATM),
+                "mp3" => Some(Self::MP3),
+                "flac" => Some(Self::FLAC),
+                "ac3" => Some(Self::AC3),
+                "eac3" => Some(Self::EAC3),
+                "dts" => Some(Self::DTS),
+                "truehd" | "mlp" => Some(Self::TrueHD),
+                "aiff" => Some(Self::AIFF),
+                "au" => Some(Self::AU),
+                "amr" => Some(Self::AMR),
+                "caf" => Some(Self::CAF),
+                "wv" => Some(Self::WavPack),
+                "ape" => Some(Self::APE),
+                "mpc" | "mpc8" => Some(Self::Musepack),
+                "tta" => Some(Self::TTA),
+                "shn" => Some(Self::Shorten),
+                "dsf" => Some(Self::DSF),
+                "iff" => Some(Self::DFF),
+                "w64" => Some(Self::W64),
+                "libopenmpt" | "libmodplug" => Some(Self::TrackerModule),
+                "webvtt" => Some(Self::WebVTT),
+                "scc" => Some(Self::SCC),
+                "mcc" => Some(Self::MCC),
+                "sami" => Some(Self::SAMI),
+                "microdvd" => Some(Self::MicroDVD),
+                "subviewer" | "subviewer1" => Some(Self::SubViewer),
+                "lrc" => Some(Self::LRC),
+                "sup" => Some(Self::PGS),
+                "vobsub" => Some(Self::VobSubIdx),
+                "stl" => Some(Self::SpruceSTL),
+                _ => None,
+            })
+    }
+
+    /// Container whose check also matches every file of this one, if any. Only the PS
+    /// check looks at nothing but the pack header, so it is the only one refined.
+    pub(crate) fn parent(&self) -> Option<Self> {
+        match self {
+            Self::VobSubPS | Self::CDXA | Self::VOB | Self::TivoPS => Some(Self::PS),
+            _ => None,
+        }
+    }
+
+    /// Function to infer Container from file.
+    /// Reads the starting bytes from an open file.
+    /// Same as `from_file_ref`, the cursor is not used or moved on Unix.
+    /// Throws IO error + error in indentification failure
+    pub fn from_file(file: &mut File) -> Result<Self, Error> {
+        Self::from_file_ref(file)
+    }
+
+    /// Function to infer Container from a shared file.
+    /// Reads the starting bytes with positioned reads, so the file can be probed from
+    /// several threads and its cursor stays where it was on Unix. On Windows the
+    /// positioned reads move the cursor, and elsewhere (eg. WASI) they are a seek
+    /// followed by a read.
+    /// Directories, empty files and files too small for any check are reported
+    /// from the metadata before reading.
+    /// A whole file that matched nothing, but whose leading bytes agree with the magic
+    /// of a check it is too short for (eg. a 40 byte file starting with a TS sync byte),
+    /// is reported as `Error::FileTooSmall` instead of `Error::Unidentified`. A file
+    /// shorter than the magic agrees if it is a prefix of it.
+    ///
+    /// Positioned reads fail on files that cannot seek, like pipes, and some
+    /// special files (procfs) may return less than their contents.
+    /// Throws IO error + error in indentification failure
+    pub fn from_file_ref(file: &File) -> Result<Self, Error> {
+        const START_BYTES_LENGTH: usize = 1024 * 1024;
+
+        let metadata = file.metadata().map_err(Error::Read)?;
+        if metadata.is_dir() {
+            return Err(Error::IsADirectory { path: None });
+        }
+        // Special files (pipes, procfs) report a length of 0, so only trust it for regular files.
+        if metadata.is_file() {
+            let len = metadata.len();
+            if len == 0 {
+                return Err(Error::EmptyFile);
+            } else if len < MIN_BUFFER_LEN as u64 {
+                return Err(Error::FileTooSmall {
+                    len,
+                    min_needed: MIN_BUFFER_LEN as u64,
+                });
+            }
+        }
+
+        // On the heap, 1 MiB is the whole default stack on wasm32.
+        let mut buffer = vec![0; START_BYTES_LENGTH];
+        let mut read = 0;
+        while read < buffer.len() {
+            match read_at(file, &mut buffer[read..], read as u64) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(Error::Read(e)),
+            }
+        }
+
+        // Only the bytes actually read, the zero tail is not part of the file.
+        let detector = Detector::default();
+        match detector.detect(&buffer[..read]) {
+            Err(Error::Unidentified) if read < buffer.len() => {
+                match detector.truncated_len(&buffer[..read]) {
+                    Some(min_needed) => Err(Error::FileTooSmall {
+                        len: read as u64,
+                        min_needed: min_needed as u64,
+                    }),
+                    None => Err(Error::Unidentified),
+                }
+            }
+            result => result,
+        }
+    }
+
+    /// Function to infer Container from file.
+    /// Takes path of file and opens it itself.
+    /// Throws error in IO failure + identification failure.
+    pub fn from_file_path(path: &Path) -> Result<Self, Error> {
+        let mut file = match File::open(path) {
+            Ok(x) => x,
+            Err(source) => {
+                return Err(Error::Open {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        Self::from_file(&mut file).map_err(|e| match e {
+            Error::IsADirectory { path: None } => Error::IsADirectory {
+                path: Some(path.to_path_buf()),
+            },
+            e => e,
+        })
+    }
+
+    /// Function to infer Container from a slice of bytes, separating "unknown" from errors.
+    /// Returns `Ok(None)` when the checks ran and nothing matched (including well known
+    /// non media files).
+    /// Throws `Error::EmptyFile` or `Error::FileTooSmall` if the buffer is too short for
+    /// any check to run.
+    pub fn try_from_bytes(buffer: &[u8]) -> Result<Option<Self>, Error> {
+        if buffer.is_empty() {
+            return Err(Error::EmptyFile);
+        } else if buffer.len() < MIN_BUFFER_LEN {
+            return Err(Error::FileTooSmall {
+                len: buffer.len() as u64,
+                min_needed: MIN_BUFFER_LEN as u64,
+            });
+        }
+        Self::optional(Detector::default().detect(buffer))
+    }
+
+    /// Function to infer Container from a shared file, separating "unknown" from errors.
+    /// Same as `from_file_ref`, but returns `Ok(None)` when nothing matched.
+    pub fn try_from_file(file: &File) -> Result<Option<Self>, Error> {
+        Self::optional(Self::from_file_ref(file))
+    }
+
+    /// Function to infer Container from path to file, separating "unknown" from errors.
+    /// Same as `from_file_path`, but returns `Ok(None)` when nothing matched.
+    pub fn try_from_file_path(path: &Path) -> Result<Option<Self>, Error> {
+        Self::optional(Self::from_file_path(path))
+    }
+
+    /// Turns the "nothing matched" errors into `Ok(None)`.
+    fn optional(result: Result<Self, Error>) -> Result<Option<Self>, Error> {
+        match result {
+            Ok(x) => Ok(Some(x)),
+            Err(Error::Unidentified) | Err(Error::NotMedia(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Checks for ASF magic bytes
+    /// Compares the full 16 byte ASF_Header_Object GUID.
+    /// Min size of buffer is 16 bytes.
+    fn check_asf(buffer: &[u8]) -> bool {
+        const ASF_MAGIC_BYTES: [u8; 16] = [
+            0x30, 0x26, 0xb2, 0x75, 0x8e, 0x66, 0xcf, 0x11, 0xa6, 0xd9, 0x00, 0xaa, 0x00, 0x62,
+            0xce, 0x6c,
+        ];
+
+        if buffer.len() >= ASF_MAGIC_BYTES.len() {
+            return ASF_MAGIC_BYTES == buffer[0..ASF_MAGIC_BYTES.len()];
+        }
+        false
+    }
+
+    /// Checks for MKV Magic bytes.
+    /// Requires the EBML header. A buffer starting with the Segment is only a fragment,
+    /// see `check_mkv_segment`. WebM and MKA files pass too, see `check_webm` and
+    /// `check_mka`.
+    /// Min Size of buffer is 4 bytes.
+    fn check_mkv(buffer: &[u8]) -> bool {
+        const MKV_EMBL_MAGIC_BYTES: [u8; 4] = [0x1a, 0x45, 0xdf, 0xa3];
+
+        if buffer.len() >= MKV_EMBL_MAGIC_BYTES.len() {
+            return MKV_EMBL_MAGIC_BYTES == buffer[0..MKV_EMBL_MAGIC_BYTES.len()];
+        }
+        false
+    }
+
+    /// Checks for an EBML header whose DocType is `webm`.
+    /// The header has to be complete enough to reach the DocType, a header cut off
+    /// before it is only MKV.
+    fn check_webm(buffer: &[u8]) -> bool {
+        Self::check_mkv(buffer) && ebml::doc_type(buffer) == Some(b"webm")
+    }
+
+    /// Checks for a Matroska (not WebM) file whose Tracks list audio and no video.
+    /// Tracks have to end within the first MiB, files without them there are MKV.
+    fn check_mka(buffer: &[u8]) -> bool {
+        Self::check_mkv(buffer)
+            && ebml::doc_type(buffer) != Some(b"webm")
+            && ebml::is_audio_only(buffer)
+    }
+
+    /// Checks for a Matroska fragment starting with the Segment element.
+    /// The Segment size must be a valid EBML vint and be followed by the ID of an
+    /// element that lives directly inside a Segment.
+    /// Only used in lenient mode.
+    fn check_mkv_segment(buffer: &[u8]) -> bool {
+        const MKV_SEGMENT_MAGIC_BYTES: [u8; 4] = [0x18, 0x53, 0x80, 0x67];
+        const SEGMENT_CHILDREN: [&[u8]; 9] = [
+            &[0x11, 0x4d, 0x9b, 0x74], // SeekHead
+            &[0x15, 0x49, 0xa9, 0x66], // Info
+            &[0x16, 0x54, 0xae, 0x6b], // Tracks
+            &[0x1f, 0x43, 0xb6, 0x75], // Cluster
+            &[0x1c, 0x53, 0xbb, 0x6b], // Cues
+            &[0x12, 0x54, 0xc3, 0x67], // Tags
+            &[0x10, 0x43, 0xa7, 0x70], // Chapters
+            &[0x19, 0x41, 0xa4, 0x69], // Attachments
+            &[0xec],                   // Void
+        ];
+
+        let magic_len = MKV_SEGMENT_MAGIC_BYTES.len();
+        if buffer.len() <= magic_len || MKV_SEGMENT_MAGIC_BYTES != buffer[0..magic_len] {
+            return false;
+        }
+
+        // Length of an EBML vint is the number of leading zeros of the first byte + 1.
+        let size_len = buffer[magic_len].leading_zeros() as usize + 1;
+        if size_len > 8 {
+            return false;
+        }
+        match buffer.get(magic_len + size_len..) {
+            Some(child) => SEGMENT_CHILDREN.iter().any(|x| child.starts_with(x)),
+            None => false,
+        }
+    }
+
+    /// Checks for GXF Magic bytes.
+    /// Min Size of buffer is 6 bytes.
+    fn check_gxf(buffer: &[u8]) -> bool {
+        const GXF_MAGIC_BYTES: [u8; 6] = [0, 0, 0, 0, 1, 0xbc];
+
+        if buffer.len() >= GXF_MAGIC_BYTES.len() {
+            return GXF_MAGIC_BYTES == buffer[0..GXF_MAGIC_BYTES.len()];
+        }
+
+        false
+    }
+
+    /// Checks for WTV Magic Bytes.
+    /// Compares the full 16 byte header GUID.
+    /// Min Size of buffer is 16 bytes.
+    fn check_wtv(buffer: &[u8]) -> bool {
+        const WTV_MAGIC_BYTES: [u8; 16] = [
+            0xb7, 0xd8, 0x00, 0x20, 0x37, 0x49, 0xda, 0x11, 0xa6, 0x4e, 0x00, 0x07, 0xe9, 0x5e,
+            0xad, 0x8d,
+        ];
+
+        if buffer.len() >= WTV_MAGIC_BYTES.len() {
+            return WTV_MAGIC_BYTES == buffer[0..WTV_MAGIC_BYTES.len()];
+        }
+        false
+    }
+
+    /// Checks for CCExtractor Magic Bytes.
+    /// Besides the magic, validates the rest of the 11 byte header:
+    /// - Byte 3: Creating program, must be a known one (0xCC is CCExtractor).
+    /// - Bytes 4-5: Program version, must be non zero.
+    /// - Bytes 6-7: File format version, must be one CCExtractor has written (only 1 so far).
+    ///
+    /// Headers with a newer file format version are rejected, since the layout after the
+    /// header may have changed.
+    /// Min Size of buffer is 11 bytes.
+    fn check_rcwt(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 11;
+        const RCWT_MAGIC_BYTES: [(usize, u8); 6] =
+            [(0, 0xCC), (1, 0xCC), (2, 0xED), (8, 0), (9, 0), (10, 0)];
+        const KNOWN_PROGRAMS: [u8; 1] = [0xCC];
+        const MAX_FORMAT_VERSION: u16 = 1;
+
+        if buffer.len() >= MIN_LEN {
+            let program_version = u16::from_be_bytes([buffer[4], buffer[5]]);
+            let format_version = u16::from_be_bytes([buffer[6], buffer[7]]);
+            return RCWT_MAGIC_BYTES.iter().all(|x| buffer[x.0] == x.1)
+                && KNOWN_PROGRAMS.contains(&buffer[3])
+                && program_version != 0
+                && (1..=MAX_FORMAT_VERSION).contains(&format_version);
+        }
+        false
+    }
+
+    /// Checks for an AVI RIFF header.
+    /// Min Size of buffer is 12 bytes.
+    fn check_avi(buffer: &[u8]) -> bool {
+        Self::check_riff(buffer, b"AVI ")
+    }
+
+    /// Checks for a WAV RIFF header.
+    /// Min Size of buffer is 12 bytes.
+    fn check_wav(buffer: &[u8]) -> bool {
+        Self::check_riff(buffer, b"WAVE")
+    }
+
+    /// Checks for a RIFF CDXA header, a Video CD `.DAT` file.
+    /// Min Size of buffer is 12 bytes.
+    fn check_cdxa(buffer: &[u8]) -> bool {
+        Self::check_riff(buffer, b"CDXA")
+    }
+
+    /// Checks for an RF64 header: `RF64`, the chunk size (usually -1, the real one is in
+    /// `ds64`), `WAVE`, then the `ds64` chunk.
+    /// Min Size of buffer is 16 bytes.
+    fn check_rf64(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 16;
+
+        buffer.len() >= MIN_LEN
+            && &buffer[0..4] == b"RF64"
+            && &buffer[8..12] == b"WAVE"
+            && &buffer[12..16] == b"ds64"
+    }
+
+    /// Checks for the Wave64 `riff` chunk GUID. Wave64 chunks are identified by GUIDs
+    /// whose first four bytes are the old fourcc, so the whole GUID is compared.
+    /// Min Size of buffer is 16 bytes.
+    fn check_w64(buffer: &[u8]) -> bool {
+        const RIFF_GUID: [u8; 16] = [
+            0x72, 0x69, 0x66, 0x66, 0x2E, 0x91, 0xCF, 0x11, 0xA5, 0xD6, 0x28, 0xDB, 0x04, 0xC1,
+            0x00, 0x00,
+        ];
+
+        buffer.starts_with(&RIFF_GUID)
+    }
+
+    /// Checks for a RIFF header: `RIFF`, the chunk size, then the form type `form`.
+    /// RIFF forms (AVI, WAV, CDXA, ...) only differ by the form type, so they never
+    /// cross-match.
+    /// The chunk size covers the form type and has to leave room for at least one
+    /// chunk header, so a stray `RIFF....AVI ` with a tiny size is rejected.
+    /// Min Size of buffer is 12 bytes.
+    fn check_riff(buffer: &[u8], form: &[u8; 4]) -> bool {
+        const MIN_LEN: usize = 12;
+        const MIN_CHUNK_SIZE: u32 = 4 + 8;
+
+        if buffer.len() >= MIN_LEN {
+            let size = u32::from_le_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+            return &buffer[0..4] == b"RIFF" && &buffer[8..12] == form && size >= MIN_CHUNK_SIZE;
+        }
+        false
+    }
+
+    /// Checks for an AIFF or AIFF-C header: `FORM`, the chunk size, then the form type
+    /// `AIFF` or `AIFC`. Like RIFF, the big endian chunk size has to leave room for at
+    /// least one chunk header. Other IFF forms (`8SVX`, ...) and `FRM8` (DFF) are not
+    /// AIFF.
+    /// Min Size of buffer is 12 bytes.
+    fn check_aiff(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 12;
+        const MIN_CHUNK_SIZE: u32 = 4 + 8;
+
+        if buffer.len() >= MIN_LEN {
+            let size = u32::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+            return &buffer[0..4] == b"FORM"
+                && matches!(&buffer[8..12], b"AIFF" | b"AIFC")
+                && size >= MIN_CHUNK_SIZE;
+        }
+        false
+    }
+
+    /// Checks for a Sun/NeXT audio header: `.snd`, a big endian data offset past the 24
+    /// byte header, the data size, then one of the defined encodings (1 to 27).
+    /// Min Size of buffer is 16 bytes.
+    fn check_au(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 16;
+        const HEADER_LEN: u32 = 24;
+
+        if buffer.len() >= MIN_LEN {
+            let offset = u32::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+            let encoding = u32::from_be_bytes([buffer[12], buffer[13], buffer[14], buffer[15]]);
+            return &buffer[0..4] == b".snd"
+                && offset >= HEADER_LEN
+                && (1..=27).contains(&encoding);
+        }
+        false
+    }
+
+    /// Checks for a CAF file header: `caff`, file version 1, flags 0, then the `desc`
+    /// chunk, which always comes first.
+    /// Min Size of buffer is 12 bytes.
+    fn check_caf(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 12;
+
+        if buffer.len() >= MIN_LEN {
+            return &buffer[0..8] == b"caff\x00\x01\x00\x00" && &buffer[8..12] == b"desc";
+        }
+        false
+    }
+
+    /// Checks for a WavPack block header: `wvpk`, a little endian block size covering
+    /// at least the rest of the 32 byte header, then a version from 0x402 to 0x410.
+    /// Min Size of buffer is 10 bytes.
+    fn check_wavpack(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 10;
+        const MIN_BLOCK_SIZE: u32 = 32 - 8;
+
+        if buffer.len() >= MIN_LEN {
+            let size = u32::from_le_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+            let version = u16::from_le_bytes([buffer[8], buffer[9]]);
+            return &buffer[0..4] == b"wvpk"
+                && size >= MIN_BLOCK_SIZE
+                && (0x402..=0x410).contains(&version);
+        }
+        false
+    }
+
+    /// Checks for a Monkey's Audio header: `MAC ` and a little endian file version from
+    /// 3800 (3.80) to 4000. The magic is plain text, the version keeps "MAC address"
+    /// and the like from matching.
+    /// Min Size of buffer is 6 bytes.
+    fn check_ape(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 6;
+
+        if buffer.len() >= MIN_LEN {
+            let version = u16::from_le_bytes([buffer[4], buffer[5]]);
+            return &buffer[0..4] == b"MAC " && (3800..=4000).contains(&version);
+        }
+        false
+    }
+
+    /// Checks for a Musepack stream: `MPCK` and the two upper case letters of the first
+    /// packet key for SV8, or `MP+` and a byte with 7 in its low nibble for SV7. The
+    /// high nibble of that byte is the minor version.
+    /// Min Size of buffer is 4 bytes.
+    fn check_musepack(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 4;
+
+        if buffer.starts_with(b"MPCK") {
+            return buffer.len() >= 6 && buffer[4..6].iter().all(u8::is_ascii_uppercase);
+        }
+        buffer.len() >= MIN_LEN && &buffer[0..3] == b"MP+" && buffer[3] & 0x0F == 7
+    }
+
+    /// Checks for a True Audio header: `TTA1` and a little endian audio format of 1
+    /// (PCM) or 2 (encrypted).
+    /// Min Size of buffer is 6 bytes.
+    fn check_tta(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 6;
+
+        if buffer.len() >= MIN_LEN {
+            let format = u16::from_le_bytes([buffer[4], buffer[5]]);
+            return &buffer[0..4] == b"TTA1" && (1..=2).contains(&format);
+        }
+        false
+    }
+
+    /// Checks for a Shorten stream: `ajkg` and a format version of at most 3.
+    /// Min Size of buffer is 5 bytes.
+    fn check_shorten(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 5;
+        const MAX_VERSION: u8 = 3;
+
+        buffer.len() >= MIN_LEN && &buffer[0..4] == b"ajkg" && buffer[4] <= MAX_VERSION
+    }
+
+    /// Checks for a Standard MIDI File header chunk: `MThd`, a big endian length of 6
+    /// and a format of 0 (one track), 1 (simultaneous tracks) or 2 (independent tracks).
+    /// Min Size of buffer is 10 bytes.
+    fn check_midi(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 10;
+        const HEADER_LEN: u32 = 6;
+
+        if buffer.len() >= MIN_LEN {
+            let len = u32::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+            let format = u16::from_be_bytes([buffer[8], buffer[9]]);
+            return &buffer[0..4] == b"MThd" && len == HEADER_LEN && format <= 2;
+        }
+        false
+    }
+
+    /// Checks for a DSD Stream File: the `DSD ` chunk with its little endian size of 28,
+    /// followed by the `fmt ` chunk.
+    /// Min Size of buffer is 32 bytes.
+    fn check_dsf(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 32;
+        const DSD_CHUNK_LEN: u64 = 28;
+
+        if buffer.len() >= MIN_LEN {
+            let mut size = [0; 8];
+            size.copy_from_slice(&buffer[4..12]);
+            return &buffer[0..4] == b"DSD "
+                && u64::from_le_bytes(size) == DSD_CHUNK_LEN
+                && &buffer[28..32] == b"fmt ";
+        }
+        false
+    }
+
+    /// Checks for a DSDIFF header: `FRM8`, a big endian 64 bit size leaving room for at
+    /// least one chunk header, then the `DSD ` form type.
+    /// Min Size of buffer is 16 bytes.
+    fn check_dff(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 16;
+        const MIN_CHUNK_SIZE: u64 = 4 + 12;
+
+        if buffer.len() >= MIN_LEN {
+            let mut size = [0; 8];
+            size.copy_from_slice(&buffer[4..12]);
+            return &buffer[0..4] == b"FRM8"
+                && &buffer[12..16] == b"DSD "
+                && u64::from_be_bytes(size) >= MIN_CHUNK_SIZE;
+        }
+        false
+    }
+
+    /// Checks for a WebVTT file: an optional UTF-8 BOM, `WEBVTT`, then a space, tab, line
+    /// break or the end of the buffer. `WEBVTTX` and the like are not WebVTT.
+    /// Min Size of buffer is 6 bytes.
+    fn check_webvtt(buffer: &[u8]) -> bool {
+        let buffer = buffer.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(buffer);
+        match buffer.strip_prefix(b"WEBVTT") {
+            Some(rest) => matches!(rest.first(), None | Some(b' ' | b'\t' | b'\n' | b'\r')),
+            None => false,
+        }
+    }
+
+    /// Checks for the GSI block of an EBU STL file: a three digit code page number, the
+    /// disk format code `STL25.01` or `STL30.01`, then a display standard code of blank
+    /// (undefined), `0` (open subtitles), `1` or `2` (teletext).
+    /// Min Size of buffer is 12 bytes.
+    fn check_ebu_stl(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 12;
+
+        if buffer.len() >= MIN_LEN {
+            return buffer[0..3].iter().all(u8::is_ascii_digit)
+                && matches!(&buffer[3..11], b"STL25.01" | b"STL30.01")
+                && matches!(buffer[11], b' ' | b'0' | b'1' | b'2');
+        }
+        false
+    }
+
+    /// Checks for a Cheetah CAP caption file: `EA 22`, then a little endian format
+    /// version of 1 to 3.
+    /// Min Size of buffer is 4 bytes.
+    fn check_cheetah_cap(buffer: &[u8]) -> bool {
+        match buffer.get(..4) {
+            Some(&[0xEA, 0x22, version, 0x00]) => (1..=3).contains(&version),
+            _ => false,
+        }
+    }
+
+    /// AMR flavour of the AMR storage format header `buffer` starts with. `#!AMR` is a
+    /// prefix of both magics, so the wideband one is tried first.
+    fn amr_kind(buffer: &[u8]) -> Option<Self> {
+        if buffer.starts_with(b"#!AMR-WB\n") {
+            Some(Self::AMRWB)
+        } else if buffer.starts_with(b"#!AMR\n") {
+            Some(Self::AMR)
+        } else {
+            None
+        }
+    }
+
+    /// Checks for the narrowband AMR storage format magic `#!AMR\n`.
+    /// Min Size of buffer is 6 bytes.
+    fn check_amr(buffer: &[u8]) -> bool {
+        Self::amr_kind(buffer) == Some(Self::AMR)
+    }
+
+    /// Checks for the wideband AMR storage format magic `#!AMR-WB\n`.
+    /// Min Size of buffer is 9 bytes.
+    fn check_amr_wb(buffer: &[u8]) -> bool {
+        Self::amr_kind(buffer) == Some(Self::AMRWB)
+    }
+
+    /// Checks for an FLV header: `FLV`, version 1, the flags byte and the header size.
+    /// Only the audio (0x04) and video (0x01) flags may be set, and the header size
+    /// has to be at least the 9 bytes of the version 1 header, so text starting with
+    /// "FLV" does not match.
+    /// Min Size of buffer is 9 bytes.
+    fn check_flv(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 9;
+        const FLV_MAGIC_BYTES: [u8; 4] = [b'F', b'L', b'V', 0x01];
+        const FLAGS: u8 = 0x04 | 0x01;
+
+        if buffer.len() >= MIN_LEN {
+            let header_size = u32::from_be_bytes([buffer[5], buffer[6], buffer[7], buffer[8]]);
+            return FLV_MAGIC_BYTES == buffer[0..4]
+                && buffer[4] & !FLAGS == 0
+                && header_size >= MIN_LEN as u32;
+        }
+        false
+    }
+
+    /// Checks for a RealMedia file header: the `.RMF` chunk ID, a chunk size of at least
+    /// the 18 bytes of the header and object version 0 or 1.
+    /// Min Size of buffer is 10 bytes.
+    fn check_rm(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 10;
+        const RM_MAGIC_BYTES: [u8; 4] = [b'.', b'R', b'M', b'F'];
+        const HEADER_LEN: u32 = 18;
+
+        if buffer.len() >= MIN_LEN {
+            let size = u32::from_be_bytes([buffer[4], buffer[5], buffer[6], buffer[7]]);
+            let version = u16::from_be_bytes([buffer[8], buffer[9]]);
+            return RM_MAGIC_BYTES == buffer[0..4] && size >= HEADER_LEN && version <= 1;
+        }
+        false
+    }
+
+    /// Checks for an IVF file header: `DKIF`, version 0 and a printable codec fourcc.
+    /// The fourcc is not limited to the VP8, VP9 and AV1 ones, tools write IVF for
+    /// other codecs too, see `ivf_codec`.
+    /// Min Size of buffer is 12 bytes.
+    pub(crate) fn check_ivf(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 12;
+        const IVF_MAGIC_BYTES: [u8; 6] = [b'D', b'K', b'I', b'F', 0, 0];
+
+        if buffer.len() >= MIN_LEN {
+            return IVF_MAGIC_BYTES == buffer[0..6] && bytes::is_fourcc(&buffer[8..12]);
+        }
+        false
+    }
+
+    /// Checks for a native FLAC stream: `fLaC` and a first metadata block header of a
+    /// STREAMINFO block, type 0 and 34 bytes long. The last metadata block flag may be set.
+    /// Min Size of buffer is 8 bytes.
+    fn check_flac(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 8;
+        const STREAMINFO_LEN: [u8; 3] = [0, 0, 34];
+
+        if buffer.len() >= MIN_LEN {
+            return buffer.starts_with(b"fLaC")
+                && buffer[4] & 0x7F == 0
+                && buffer[5..8] == STREAMINFO_LEN;
+        }
+        false
+    }
+
+    /// Checks for an Ogg page header: the `OggS` capture pattern, stream structure
+    /// version 0 and a header type with only the continuation, BOS and EOS flags.
+    /// The first page of a file begins a stream, so the BOS flag is required unless
+    /// `lenient`, which accepts a capture that starts mid stream.
+    /// Min Size of buffer is 6 bytes.
+    fn check_ogg(buffer: &[u8], lenient: bool) -> bool {
+        const MIN_LEN: usize = 6;
+        const OGG_MAGIC_BYTES: [u8; 5] = [b'O', b'g', b'g', b'S', 0];
+        const FLAGS: u8 = 0x07;
+        const BOS: u8 = 0x02;
+
+        if buffer.len() >= MIN_LEN {
+            let flags = buffer[5];
+            return OGG_MAGIC_BYTES == buffer[0..5]
+                && flags & !FLAGS == 0
+                && (lenient || flags & BOS != 0);
+        }
+        false
+    }
+
+    /// Checks for MP4 magic bytes.
+    /// M4A files with the `isom` brand pass too, see `check_m4a`.
+    /// [Magic Bytes List](https://www.garykessler.net/library/file_sigs.html)
+    fn check_mp4(buffer: &[u8]) -> bool {
+        const MIN_LEN: usize = 12;
+        const MP4_MAGIC_BYTES_1: [u8; 8] = [0x66, 0x74, 0x79, 0x70, 0x4D, 0x53, 0x4E, 0x56];
+        const MP4_MAGIC_BYTES_2: [u8; 8] = [0x66, 0x74, 0x79, 0x70, 0x69, 0x73, 0x6F, 0x6D];
+
+        if buffer.len() >= MIN_LEN {
+            let buffer = &buffer[4..MIN_LEN];
+            return MP4_MAGIC_BYTES_1 == buffer || MP4_MAGIC_BYTES_2 == buffer;
+        }
+        false
+    }
+
+    /// Major brand and compatible brands of the `ftyp` box starting `buffer`.
+    /// The box may have a 64 bit size. Compatible brands are cut at the end of the
+    /// buffer.
+    fn ftyp_brands(buffer: &[u8]) -> Option<(&[u8], &[u8])> {
+        if bytes::slice_at(buffer, 4, 4)? != b"ftyp" {
+            return None;
+        }
+        let (size, header_len) = match bytes::be_u32_at(buffer, 0)? {
+            1 => (bytes::be_u64_at(buffer, 8)?, 16),
+            x => (u64::from(x), 8),
+        };
+        // Major brand and minor version.
+        let brands = header_len + 8;
+        if size < brands as u64 {
+            return None;
+        }
+        let major = bytes::slice_at(buffer, header_len, 4)?;
+        let end = usize::try_from(size).map_or(buffer.len(), |x| x.min(buffer.len()));
+        Some((major, buffer.get(brands..end).unwrap_or(&[])))
+    }
+
+    /// Checks for an `ftyp` box of a 3GPP family, the brands starting with one of
+    /// `prefixes`.
+    /// The major brand decides. A compatible brand counts when the major brand belongs
+    /// to no container with a check (eg. `mp42`), so MP4 (`isom`), QuickTime and other
+    /// 3GPP family files listing these brands stay what they are.
+    /// Min Size of buffer is 16 bytes.
+    fn check_3gpp(buffer: &[u8], prefixes: &[&[u8; 3]]) -> bool {
+        const OTHER_MAJOR_BRANDS: [&[u8; 4]; 6] =
+            [b"isom", b"MSNV", b"qt  ", b"M4A ", b"M4B ", b"M4P "];
+
+        if Self::ftyp_image(buffer).is_some() {
+            return false;
+        }
+
+        let family = |brand: &[u8]| prefixes.iter().any(|x| brand.starts_with(*x));
+        match Self::ftyp_brands(buffer) {
+            Some((major, _)) if family(major) => true,
+            // Another 3GPP family, or a container with a check.
+            Some((major, _))
+                if major.starts_with(b"3g") || OTHER_MAJOR_BRANDS.iter().any(|x| major == *x) =>
+            {
+                false
+            }
+            Some((_, compatible)) => compatible.chunks_exact(4).any(family),
+            None => false,
+        }
+    }
+
+    /// Checks for 3GPP, brands `3gp*` and the release 6+ `3ge*`, `3gg*`, `3gr*`, `3gs*`.
+    fn check_3gp(buffer: &[u8]) -> bool {
+        Self::check_3gpp(buffer, &[b"3gp", b"3ge", b"3gg", b"3gr", b"3gs"])
+    }
+
+    /// Checks for 3GPP2, brands `3g2*`.
+    fn check_3g2(buffer: &[u8]) -> bool {
+        Self::check_3gpp(buffer, &[b"3g2"])
+    }
+
+    /// Checks for an `ftyp` box of MPEG-4 audio, brands `M4A `, `M4B ` and `M4P `.
+    /// Generic major brands (`isom`, `mp42`, ...) count when an audio brand is among the
+    /// compatible ones, video major brands (QuickTime, 3GPP, `M4V `) never do.
+    /// Min Size of buffer is 16 bytes.
+    fn check_m4a(buffer: &[u8]) -> bool {
+        const AUDIO_BRANDS: [&[u8; 4]; 3] = [b"M4A ", b"M4B ", b"M4P "];
+
+        let audio = |brand: &[u8]| AUDIO_BRANDS.iter().any(|x| brand == *x);
+        match Self::ftyp_brands(buffer) {
+            Some((major, _)) if audio(major) => true,
+            Some((major, _))
+                if major == b"qt  " || major.starts_with(b"3g") || major.starts_with(b"M4V") =>
+            {
+                false
+            }
+            Some(_) if Self::ftyp_image(buffer).is_some() => false,
+            Some((_, compatible)) => compatible.chunks_exact(4).any(audio),
+            None => false,
+        }
+    }
+
+    /// Image container of an `ftyp` box with a HEIF or AVIF major brand.
+    /// The generic `mif1` (image) and `msf1` (sequence) brands are AVIF when `avif` or
+    /// `avis` is among the compatible brands and HEIF otherwise.
+    fn ftyp_image(buffer: &[u8]) -> Option<Self> {
+        const HEIF_BRANDS: [&[u8; 4]; 8] = [
+            b"heic", b"heix", b"heim", b"heis", b"hevc", b"hevx", b"hevm", b"hevs",
+        ];
+
+        let (major, compatible) = Self::ftyp_brands(buffer)?;
+        let has = |brand: &[u8; 4]| compatible.chunks_exact(4).any(|x| x == brand);
+        match major {
+            b"avif" => Some(Self::AVIF),
+            b"avis" => Some(Self::AVIFSequence),
+            b"mif1" | b"msf1" if has(b"avis") => Some(Self::AVIFSequence),
+            b"mif1" | b"msf1" if has(b"avif") => Some(Self::AVIF),
+            b"mif1" | b"msf1" => Some(Self::HEIF),
+            x if HEIF_BRANDS.iter().any(|y| x == *y) => Some(Self::HEIF),
+            _ => None,
+        }
+    }
+
+    /// Checks for an `ftyp` box of a HEIF image, see `ftyp_image`.
+    /// Min Size of buffer is 16 bytes.
+    fn check_heif(buffer: &[u8]) -> bool {
+        Self::ftyp_image(buffer) == Some(Self::HEIF)
+    }
+
+    /// Checks for an `ftyp` box of an AVIF image, see `ftyp_image`.
+    /// Min Size of buffer is 16 bytes.
+    fn check_avif(buffer: &[u8]) -> bool {
+        Self::ftyp_image(buffer) == Some(Self::AVIF)
+    }
+
+    /// Checks for an `ftyp` box of an AVIF image sequence, see `ftyp_image`.
+    /// Min Size of buffer is 16 bytes.
+    fn check_avis(buffer: &[u8]) -> bool {
+        Self::ftyp_image(buffer) == Some(Self::AVIFSequence)
+    }
+
+    /// Checks for a fragmented MP4 media segment: a first box only segments start with
+    /// (`styp`, `sidx`, `prft`, `emsg`, `moof`), then well formed boxes.
+    /// Up to 8 top level boxes are walked. The ones whose header is in the buffer need a
+    /// size covering the header and a printable type, and a `moof` has to open with
+    /// its `mfhd`.
+    /// Min Size of buffer is 8 bytes.
+    fn check_fmp4(buffer: &[u8]) -> bool {
+        const SEGMENT_BOXES: [&[u8; 4]; 5] = [b"styp", b"sidx", b"prft", b"emsg", b"moof"];
+        const MAX_BOXES: usize = 8;
+
+        match bytes::slice_at(buffer, 4, 4) {
+            Some(x) if SEGMENT_BOXES.iter().any(|y| x == *y) => {}
+            _ => return false,
+        }
+        let mut pos = 0;
+        for _ in 0..MAX_BOXES {
+            let (size, kind) = match (
+                bytes::be_u32_at(buffer, pos),
+                bytes::slice_at(buffer, pos + 4, 4),
+            ) {
+                (Some(size), Some(kind)) => (size, kind),
+                _ => break,
+            };
+            if !bytes::is_fourcc(kind) {
+                return false;
+            }
+            let (size, header_len) = match size {
+                // Extended size, after the type.
+                1 => match bytes::be_u64_at(buffer, pos + 8) {
+                    Some(x) => (x, 16),
+                    None => break,
+                },
+                x => (u64::from(x), 8),
+            };
+            if size < header_len {
+                return false;
+            }
+            let child = bytes::slice_at(buffer, pos + header_len as usize + 4, 4);
+            if kind == b"moof" && matches!(child, Some(x) if x != b"mfhd") {
+                return false;
+            }
+            pos = match usize::try_from(size).ok().and_then(|x| pos.checked_add(x)) {
+                Some(x) if x < buffer.len() => x,
+                _ => break,
+            };
+        }
+        true
+    }
+
+    /// Checks for QuickTime: an `ftyp` with the `qt  ` major brand, or, in files without
+    /// `ftyp`, a first atom of a type only found at the top level (`moov`, `mdat`,
+    /// `wide`, `free`, `skip`).
+    /// The first atom needs a plausible size, 32 bit or extended 64 bit, and if the
+    /// atom after it starts in the buffer its type has to be printable, so a stray
+    /// "mdat" in random data does not match.
+    /// Min Size of buffer is 8 bytes.
+    fn check_mov(buffer: &[u8]) -> bool {
+        const QT_FTYP: [u8; 8] = *b"ftypqt  ";
+        const TOP_LEVEL_ATOMS: [&[u8; 4]; 5] = [b"moov", b"mdat", b"wide", b"free", b"skip"];
+        const HEADER_LEN: u64 = 8;
+        const EXTENDED_HEADER_LEN: u64 = 16;
+
+        if bytes::slice_at(buffer, 4, 8) == Some(&QT_FTYP) {
+            return true;
+        }
+        match bytes::slice_at(buffer, 4, 4) {
+            Some(x) if TOP_LEVEL_ATOMS.iter().any(|y| x == *y) => {}
+            _ => return false,
+        }
+        let size = match bytes::be_u32_at(buffer, 0) {
+            // Extended size, after the type.
+            Some(1) => match bytes::be_u64_at(buffer, 8) {
+                Some(x) if x >= EXTENDED_HEADER_LEN => x,
+                _ => return false,
+            },
+            Some(x) if u64::from(x) >= HEADER_LEN => u64::from(x),
+            _ => return false,
+        };
+        match usize::try_from(size)
+            .ok()
+            .and_then(|x| x.checked_add(4))
+            .and_then(|x| bytes::slice_at(buffer, x, 4))
+        {
+            Some(next) => bytes::is_fourcc(next),
+            None => true,
+        }
+    }
+
+    /// Checks for Material Exchange Format
+    fn check_mxf(buffer: &[u8]) -> bool {
+        const BYTES_LEN: usize = 14;
+        const MXF_MAGIC_BYTES: [u8; BYTES_LEN] = [
+            0x06, 0x0e, 0x2b, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01, 0x02,
+        ];
+
+        // The last byte has never been part of the search.
+        let end = buffer.len().saturating_sub(1);
+        scan::contains(&buffer[..end], &MXF_MAGIC_BYTES, 0)
+    }
+
+    /// Checks for Transport stream.
+    /// Probes 10 packets, at least `threshold` of them need a sync byte followed by a
+    /// plausible packet header.
+    fn check_ts(buffer: &[u8], threshold: usize) -> bool {
+        const TS_MAGIC_POS: usize = 188;
+
+        (0..TS_MAGIC_POS).any(|x| Self::check_ts_packets(buffer, x, TS_MAGIC_POS, threshold))
+    }
+
+    /// Checks for M2TS
+    /// Seperating from TS seemed better.
+    fn check_m2ts(buffer: &[u8], threshold: usize) -> bool {
+        const M2TS_MAGIC_POS: usize = 192;
+
+        (0..M2TS_MAGIC_POS)
+            .any(|x| Self::check_ts_packets(buffer, x + 4, M2TS_MAGIC_POS, threshold))
+    }
+
+    /// Checks for TS and M2TS together.
+    /// If packets line up on both the 188 and 192 byte strides, the stride with the
+    /// longer run of valid packets wins. On a tie M2TS is only picked when the arrival
+    /// timestamps in its 4 byte prefixes are ascending.
+    fn check_ts_family(buffer: &[u8], threshold: usize) -> Option<Self> {
+        const M2TS_PACKET_LEN: usize = 192;
+
+        match (
+            Self::check_ts(buffer, threshold),
+            Self::check_m2ts(buffer, threshold),
+        ) {
+            (false, false) => None,
+            (true, false) => Some(ContainerType::TS),
+            (false, true) => Some(ContainerType::M2TS),
+            (true, true) => {
+                let (_, ts_run) = Self::ts_best_run(buffer, 0, TS_PACKET_LEN, threshold);
+                let (m2ts_start, m2ts_run) =
+                    Self::ts_best_run(buffer, 4, M2TS_PACKET_LEN, threshold);
+                if m2ts_run > ts_run
+                    || (m2ts_run == ts_run && Self::m2ts_ascending_ats(buffer, m2ts_start))
+                {
+                    Some(ContainerType::M2TS)
+                } else {
+                    Some(ContainerType::TS)
+                }
+            }
+        }
+    }
+
+    /// Finds the start offset (below `stride`) with the longest run of valid TS packets.
+    /// `prefix` is the number of bytes before the sync byte in each packet.
+    /// Returns the offset of the first packet and the length of the run.
+    fn ts_best_run(
+        buffer: &[u8],
+        prefix: usize,
+        stride: usize,
+        threshold: usize,
+    ) -> (usize, usize) {
+        (0..stride)
+            .filter(|x| Self::check_ts_packets(buffer, x + prefix, stride, threshold))
+            .map(|x| {
+                let run = (0..)
+                    .map_while(|y| bytes::stride_pos(x + prefix, y, stride))
+                    .take_while(|pos| Self::ts_packet_pid(buffer, *pos).is_some())
+                    .count();
+                (x, run)
+            })
+            .max_by_key(|x| x.1)
+            .unwrap_or((0, 0))
+    }
+
+    /// Checks that the 30 bit arrival timestamps of the first 8 M2TS packets starting at
+    /// `start` are strictly ascending.
+    fn m2ts_ascending_ats(buffer: &[u8], start: usize) -> bool {
+        const M2TS_PACKET_LEN: usize = 192;
+        const PACKETS_TO_CHECK: usize = 8;
+
+        let ats = |i: usize| {
+            let pos = bytes::stride_pos(start, i, M2TS_PACKET_LEN)?;
+            bytes::be_u32_at(buffer, pos).map(|x| x & 0x3FFF_FFFF)
+        };
+        (1..PACKETS_TO_CHECK).all(|i| match (ats(i - 1), ats(i)) {
+            (Some(prev), Some(cur)) => cur > prev,
+            _ => false,
+        })
+    }
+
+    /// Probes `TS_PACKETS_TO_PROBE` TS packet headers starting at `start`, `stride` bytes
+    /// apart, and checks that at least `threshold` of them are valid. This tolerates a few
+    /// corrupted or dropped packets while keeping the run on one stride.
+    /// At least one PID must not be the null PID, so runs of 0x47 bytes
+    /// (eg. text full of 'G') are rejected.
+    fn check_ts_packets(buffer: &[u8], start: usize, stride: usize, threshold: usize) -> bool {
+        const NULL_PID: u16 = 0x1FFF;
+
+        let mut valid = 0;
+        let mut only_null = true;
+        for y in 0..TS_PACKETS_TO_PROBE {
+            if valid + (TS_PACKETS_TO_PROBE - y) < threshold {
+                return false;
+            }
+            let pid =
+                bytes::stride_pos(start, y, stride).and_then(|x| Self::ts_packet_pid(buffer, x));
+            match pid {
+                Some(NULL_PID) => valid += 1,
+                Some(_) => {
+                    valid += 1;
+                    only_null = false;
+                }
+                None => {}
+            }
+        }
+
+        valid >= threshold && !only_null
+    }
+
+    /// Parses the 4 byte TS packet header at `pos` and returns its PID.
+    /// The header needs the sync byte, a clear transport_error_indicator and a
+    /// non reserved adaptation_field_control.
+    fn ts_packet_pid(buffer: &[u8], pos: usize) -> Option<u16> {
+        const MAGIC_BYTE: u8 = 0x47;
+
+        let header = bytes::slice_at(buffer, pos, 4)?;
+        if header[0] != MAGIC_BYTE || header[1] & 0x80 != 0 || header[3] & 0x30 == 0 {
+            return None;
+        }
+        Some((u16::from(header[1] & 0x1F) << 8) | u16::from(header[2]))
+    }
+
+    /// Checks for PS (Needs PACK header)
+    /// Only the first `window` bytes (or the whole buffer if shorter) are searched.
+    fn check_ps(buffer: &[u8], window: usize) -> bool {
+        const PS_MAGIC_BYTES: [u8; 4] = [0x00, 0x00, 0x01, 0xBA];
+
+        let len = buffer.len().min(window);
+        // 0xBA is the rarest byte of the pack start code.
+        scan::contains(&buffer[..len], &PS_MAGIC_BYTES, 3)
+    }
+
+    /// Checks for a YUV4MPEG2 stream header: `YUV4MPEG2 ` and space separated
+    /// parameters up to a newline. When the newline is in the buffer the line needs a
+    /// width (`W`) and a height (`H`) parameter, each a number.
+    /// Min Size of buffer is 10 bytes.
+    fn check_y4m(buffer: &[u8]) -> bool {
+        const Y4M_MAGIC_BYTES: &[u8; 10] = b"YUV4MPEG2 ";
+
+        if !buffer.starts_with(Y4M_MAGIC_BYTES) {
+            return false;
+        }
+        let params = &buffer[Y4M_MAGIC_BYTES.len()..];
+        let line = match params.iter().position(|x| *x == b'\n') {
+            Some(x) => &params[..x],
+            None => return true,
+        };
+        let has = |tag: u8| {
+            line.split(|x| *x == b' ')
+                .any(|x| x.len() > 1 && x[0] == tag && x[1..].iter().all(u8::is_ascii_digit))
+        };
+        has(b'W') && has(b'H')
+    }
+
+    /// Checks for a Scenarist SCC caption file: an optional UTF-8 BOM and the
+    /// `Scenarist_SCC V1.0` line, then after blank lines a caption line starting with a
+    /// `HH:MM:SS:FF` timecode, or `HH:MM:SS;FF` for drop frame. A buffer ending before
+    /// the timecode is complete is accepted.
+    /// Min Size of buffer is 18 bytes.
+    fn check_scc(buffer: &[u8]) -> bool {
+        const SCC_MAGIC_BYTES: &[u8; 18] = b"Scenarist_SCC V1.0";
+        const TIMECODE: &[u8; 11] = b"00:00:00;00";
+
+        let buffer = buffer.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(buffer);
+        let rest = match buffer.strip_prefix(SCC_MAGIC_BYTES) {
+            Some(x) => x,
+            None => return false,
+        };
+        if !matches!(rest.first(), None | Some(b'\r' | b'\n')) {
+            return false;
+        }
+        let line = match rest.iter().position(|x| !x.is_ascii_whitespace()) {
+            Some(x) => &rest[x..],
+            None => return true,
+        };
+        line.iter().zip(TIMECODE).all(|(x, pattern)| match pattern {
+            b'0' => x.is_ascii_digit(),
+            b';' => matches!(x, b';' | b':'),
+            _ => x == pattern,
+        })
+    }
+
+    /// Checks for a MacCaption file: the `File Format=MacCaption_MCC V1.0` or `V2.0`
+    /// line, see `mcc_version`.
+    /// Min Size of buffer is 31 bytes.
+    fn check_mcc(buffer: &[u8]) -> bool {
+        mcc::mcc_version(buffer).is_some()
+    }
+
+    /// Checks for a TTML document: a `tt` root element, prefixed or not, declaring the
+    /// TTML namespace or one of the older DFXP ones. SMIL, SVG and other XML have other
+    /// root elements.
+    /// Min Size of buffer is 37 bytes, `<tt xmlns="http://www.w3.org/ns/ttml"`.
+    fn check_ttml(buffer: &[u8]) -> bool {
+        const NAMESPACES: [&[u8]; 3] = [
+            b"http://www.w3.org/ns/ttml",
+            b"http://www.w3.org/2006/10/ttaf1",
+            b"http://www.w3.org/2006/04/ttaf1",
+        ];
+
+        let tag = match xml::root_tag(buffer) {
+            Some(x) => x,
+            None => return false,
+        };
+        let name = xml::name(tag);
+        (name == b"tt" || name.ends_with(b":tt"))
+            && xml::namespaces(tag).any(|x| NAMESPACES.contains(&x))
+    }
+
+    /// Checks for a SAMI document: a `SAMI` root element, in any case. SMIL files, also
+    /// `.smi`, have a `smil` root element.
+    /// Min Size of buffer is 6 bytes.
+    fn check_sami(buffer: &[u8]) -> bool {
+        match xml::root_tag(buffer) {
+            Some(x) => xml::name(x).eq_ignore_ascii_case(b"sami"),
+            None => false,
+        }
+    }
+
+    /// Checks for MicroDVD subtitles: the first two non-blank lines start with
+    /// `{start}{end}` frame numbers. Without a magic one cue is not enough, a buffer
+    /// ending in or right after the first line is not MicroDVD.
+    /// Min Size of buffer is 13 bytes.
+    fn check_microdvd(buffer: &[u8]) -> bool {
+        fn frames(line: &[u8]) -> Option<&[u8]> {
+            let rest = text::skip_digits(line.strip_prefix(b"{")?)?;
+            rest.strip_prefix(b"}")
+        }
+
+        let cues = text::lines(buffer)
+            .take(2)
+            .filter(|x| frames(x).and_then(frames).is_some());
+        cues.count() == 2
+    }
+
+    /// Checks for SubViewer subtitles: the first non-blank line is `[INFORMATION]`, the
+    /// start of the SubViewer 2 header, or a `H:MM:SS.mmm,H:MM:SS.mmm` timing line like
+    /// Google's `.sbv` files start with. SubViewer 1 has hundredths, `H:MM:SS.hh`.
+    /// Min Size of buffer is 13 bytes.
+    fn check_subviewer(buffer: &[u8]) -> bool {
+        fn timestamp(x: &[u8]) -> Option<&[u8]> {
+            let x = text::two_digits(text::skip_digits(x)?.strip_prefix(b":")?)?;
+            let x = text::two_digits(x.strip_prefix(b":")?)?;
+            let x = x.strip_prefix(b".")?;
+            let fraction = text::skip_digits(x)?;
+            matches!(x.len() - fraction.len(), 2 | 3).then(|| fraction)
+        }
+
+        let line = match text::lines(buffer).next() {
+            Some(x) => text::trim_end(x),
+            None => return false,
+        };
+        if line == b"[INFORMATION]" {
+            return true;
+        }
+        match timestamp(line).and_then(|x| timestamp(x.strip_prefix(b",")?)) {
+            Some(rest) => rest.is_empty(),
+            None => false,
+        }
+    }
+
+    /// Checks for LRC lyrics: the first two non-blank lines start with an ID tag like
+    /// `[ar:` or `[ti:`, or a `[mm:ss.xx]` timestamp. A single line could be any
+    /// bracketed text.
+    /// Min Size of buffer is 9 bytes.
+    fn check_lrc(buffer: &[u8]) -> bool {
+        /// ID tags, up to their `:`.
+        const TAGS: [&[u8]; 10] = [
+            b"ar", b"al", b"ti", b"au", b"by", b"re", b"ve", b"length", b"offset", b"#",
+        ];
+
+        fn tagged(line: &[u8]) -> bool {
+            let line = match line.strip_prefix(b"[") {
+                Some(x) => x,
+                None => return false,
+            };
+            TAGS.iter()
+                .any(|x| matches!(line.strip_prefix(*x), Some([b':', ..])))
+                || timestamp(line).is_some()
+        }
+        fn timestamp(x: &[u8]) -> Option<&[u8]> {
+            let x = text::two_digits(text::skip_digits(x)?.strip_prefix(b":")?)?;
+            let x = match x {
+                [b'.' | b':', rest @ ..] => text::skip_digits(rest)?,
+                _ => x,
+            };
+            x.strip_prefix(b"]")
+        }
+
+        text::lines(buffer).take(2).filter(|x| tagged(x)).count() == 2
+    }
+
+    /// Checks for a Spruce subtitle script: after `//` comment lines, a known `$`
+    /// directive like `$FontName = Arial` or `$TapeOffset = FALSE`. Unrelated to the
+    /// binary EBU STL.
+    /// Min Size of buffer is 5 bytes.
+    fn check_spruce_stl(buffer: &[u8]) -> bool {
+        /// Directives, without their `$`.
+        const DIRECTIVES: [&[u8]; 20] = [
+            b"FontName",
+            b"FontSize",
+            b"HorzAlign",
+            b"VertAlign",
+            b"XOffset",
+            b"YOffset",
+            b"TextContrast",
+            b"Outline1Contrast",
+            b"Outline2Contrast",
+            b"BackgroundContrast",
+            b"ForceDisplay",
+            b"FadeIn",
+            b"FadeOut",
+            b"TapeOffset",
+            b"Bold",
+            b"Italic",
+            b"Underlined",
+            b"ColorIndex1",
+            b"ColorIndex2",
+            b"SetFilePathToken",
+        ];
+
+        let line = match text::lines(buffer).find(|x| !x.starts_with(b"//")) {
+            Some(x) => x,
+            None => return false,
+        };
+        let name = match line.strip_prefix(b"$") {
+            Some(x) => x,
+            None => return false,
+        };
+        DIRECTIVES.iter().any(|x| match name.get(..x.len()) {
+            Some(y) if y.eq_ignore_ascii_case(x) => {
+                matches!(name.get(x.len()), None | Some(b' ' | b'\t' | b'='))
+            }
+            _ => false,
+        })
+    }
+
+    /// Checks for a Blu-ray PGS subtitle stream: a `PG` segment header with a known
+    /// segment type, and the `PG` of the next segment right after the segment.
+    /// Min Size of buffer is 15 bytes.
+    fn check_pgs(buffer: &[u8]) -> bool {
+        /// Palette, object, presentation composition, window definition and end.
+        const SEGMENT_TYPES: [u8; 5] = [0x14, 0x15, 0x16, 0x17, 0x80];
+
+        let header = match bytes::slice_at(buffer, 0, 13) {
+            Some(x) if x.starts_with(b"PG") => x,
+            _ => return false,
+        };
+        let len = usize::from(u16::from_be_bytes([header[11], header[12]]));
+        SEGMENT_TYPES.contains(&header[10]) && bytes::slice_at(buffer, 13 + len, 2) == Some(b"PG")
+    }
+
+    /// Checks for a VobSub index: the `# VobSub index file, v7` line.
+    /// Min Size of buffer is 23 bytes.
+    fn check_vobsub_idx(buffer: &[u8]) -> bool {
+        const VOBSUB_IDX_MAGIC_BYTES: &[u8; 22] = b"# VobSub index file, v";
+
+        match buffer.strip_prefix(VOBSUB_IDX_MAGIC_BYTES) {
+            Some(x) => text::skip_digits(x).is_some(),
+            None => false,
+        }
+    }
+
+    /// Checks for VobSub subpictures: an MPEG-2 PS starting with a pack header whose
+    /// first PES packets, padding aside, are private stream 1 with a subpicture
+    /// substream ID (0x20 to 0x3F). A DVD VOB starts with navigation packets instead.
+    /// Min Size of buffer is 24 bytes.
+    fn check_vobsub_ps(buffer: &[u8]) -> bool {
+        const PACKETS_TO_PROBE: usize = 4;
+
+        if !buffer.starts_with(&[0x00, 0x00, 0x01, 0xBA]) {
+            return false;
+        }
+        let mut pos = 0;
+        let mut subpictures = 0;
+        for _ in 0..PACKETS_TO_PROBE {
+            let header = match bytes::slice_at(buffer, pos, 9) {
+                Some(x) => x,
+                None => break,
+            };
+            let len = usize::from(u16::from_be_bytes([header[4], header[5]]));
+            pos = match *header {
+                // MPEG-2 pack header, then stuffing.
+                [0x00, 0x00, 0x01, 0xBA, x, ..] if x & 0xC0 == 0x40 => match buffer.get(pos + 13) {
+                    Some(x) => pos + 14 + usize::from(x & 0x07),
+                    None => break,
+                },
+                // Private stream 1, the substream ID follows the PES header data.
+                [0x00, 0x00, 0x01, 0xBD, ..] => {
+                    match buffer.get(pos + 9 + usize::from(header[8])) {
+                        Some(0x20..=0x3F) => subpictures += 1,
+                        Some(_) => return false,
+                        None => break,
+                    }
+                    pos + 6 + len
+                }
+                // Padding stream.
+                [0x00, 0x00, 0x01, 0xBE, ..] => pos + 6 + len,
+                _ => return false,
+            };
+        }
+        subpictures > 0
+    }
+
+    /// Checks for a DVD VOB: the navigation pack it starts with, an MPEG-2 pack header
+    /// at offset 0, the system header, then the private stream 2 PES packet holding the
+    /// PCI (substream 0). A plain PS has no navigation packs.
+    /// Min Size of buffer is 33 bytes.
+    fn check_vob(buffer: &[u8]) -> bool {
+        const PCI_SUBSTREAM: u8 = 0x00;
+
+        let pack = match bytes::slice_at(buffer, 0, 14) {
+            Some(x) if x[..4] == [0x00, 0x00, 0x01, 0xBA] && x[4] & 0xC0 == 0x40 => x,
+            _ => return false,
+        };
+        let pos = 14 + usize::from(pack[13] & 0x07);
+        let system = match bytes::slice_at(buffer, pos, 6) {
+            Some(x) if x[..4] == [0x00, 0x00, 0x01, 0xBB] => x,
+            _ => return false,
+        };
+        let pos = pos + 6 + usize::from(u16::from_be_bytes([system[4], system[5]]));
+        match bytes::slice_at(buffer, pos, 7) {
+            Some(x) => x[..4] == [0x00, 0x00, 0x01, 0xBF] && x[6] == PCI_SUBSTREAM,
+            None => false,
+        }
+    }
+
+    /// Checks for Tivo Program Stream
+    fn check_tivo_ps(buffer: &[u8]) -> bool {
+        const MAGIC_BYTES: [u8; 4] = [b'T', b'i', b'V', b'o'];
+
+        if buffer.len() >= MAGIC_BYTES.len() {
+            return MAGIC_BYTES == buffer[0..MAGIC_BYTES.len()];
+        }
+
+        false
     }
 
-    /// Function to infer Container from file.
-    /// Reads the starting bytes from an open file.
-    /// Throws IO error + error in indentification failure
-    pub fn from_file(file: &mut File) -> Result<Self, String> {
-        const START_BYTES_LENGTH: usize = 1024 * 1024;
+    /// Checks for the start of an elementary stream made of `00 00 01` start codes: zero
+    /// padding, a start code, then `header_len` bytes (the start code value or NAL unit
+    /// header and what follows) `first` accepts.
+    /// The next start codes within the first 4 KiB, up to 3 of them, need headers `valid`
+    /// accepts. The ones cut off by the end of the buffer are not looked at.
+    fn check_start_codes(
+        buffer: &[u8],
+        header_len: usize,
+        first: impl Fn(&[u8]) -> bool,
+        valid: impl Fn(&[u8]) -> bool,
+    ) -> bool {
+        const SEARCH_LEN: usize = 4096;
+        const MAX_NAL_UNITS: usize = 4;
 
-        let mut buffer: [u8; START_BYTES_LENGTH] = [0; START_BYTES_LENGTH];
-        if file.read(&mut buffer).is_err() {
-            return Err("Error in reading File".to_string());
+        let buffer = &buffer[..buffer.len().min(SEARCH_LEN)];
+        let zeros = buffer.iter().take_while(|x| **x == 0).count();
+        if zeros < 2 || buffer.get(zeros) != Some(&1) {
+            return false;
+        }
+        match bytes::slice_at(buffer, zeros + 1, header_len) {
+            Some(x) if first(x) => {}
+            _ => return false,
         }
+        scan::start_codes(buffer)
+            .skip(1)
+            .take(MAX_NAL_UNITS - 1)
+            .filter_map(|x| bytes::slice_at(buffer, x, header_len))
+            .all(valid)
+    }
 
-        Self::from_bytes(&buffer)
+    /// Checks for the start of an H.264 Annex B elementary stream, a first NAL unit an
+    /// encoder starts with: SPS, PPS, access unit delimiter or IDR slice.
+    /// Min Size of buffer is 4 bytes.
+    fn check_h264_es(buffer: &[u8]) -> bool {
+        Self::check_start_codes(
+            buffer,
+            1,
+            |x| heuristic::h264_header_valid(x[0]) && matches!(x[0] & 0x1F, 5 | 7 | 8 | 9),
+            |x| heuristic::h264_header_valid(x[0]),
+        )
     }
 
-    /// Function to infer Container from file.
-    /// Takes path of file and opens it itself.
-    /// Throws error in IO failure + identification failure.
-    pub fn from_file_path(path: &Path) -> Result<Self, String> {
-        let mut file = match File::open(path) {
-            Ok(x) => x,
-            Err(_) => return Err("Error in Opening File".to_string()),
-        };
-        Self::from_file(&mut file)
+    /// Checks for the start of an H.265 Annex B elementary stream, a first NAL unit an
+    /// encoder starts with: VPS, SPS, PPS or access unit delimiter.
+    /// Min Size of buffer is 5 bytes.
+    fn check_h265_es(buffer: &[u8]) -> bool {
+        Self::check_start_codes(
+            buffer,
+            2,
+            |x| heuristic::h265_header_valid(x[0], x[1]) && matches!(x[0] >> 1, 32..=35),
+            |x| heuristic::h265_header_valid(x[0], x[1]),
+        )
     }
 
-    /// Checks for ASF magic bytes
-    /// Min size of buffer is 4 bytes.
-    fn check_asf(buffer: &[u8]) -> bool {
-        const ASF_MAGIC_BYTES: [u8; 4] = [0x30, 0x26, 0xb2, 0x75];
+    /// Checks for the start of an MPEG-4 Part 2 elementary stream: a visual object
+    /// sequence start code with a profile and level, or, in dumps without headers, a
+    /// VOP start code.
+    /// Later start codes have to be MPEG-4 Visual ones (video object and layer, `B0` to
+    /// `B6`), so MPEG-1 and MPEG-2 slices do not pass.
+    /// Min Size of buffer is 5 bytes.
+    fn check_mpeg4_es(buffer: &[u8]) -> bool {
+        const VOS_START_CODE: u8 = 0xB0;
+        const VOP_START_CODE: u8 = 0xB6;
 
-        if buffer.len() >= ASF_MAGIC_BYTES.len() {
-            return ASF_MAGIC_BYTES == buffer[0..ASF_MAGIC_BYTES.len()];
-        }
-        false
+        Self::check_start_codes(
+            buffer,
+            2,
+            // Profile and level 0 is reserved.
+            |x| (x[0] == VOS_START_CODE && x[1] != 0) || x[0] == VOP_START_CODE,
+            |x| matches!(x[0], 0x00..=0x2F | 0xB0..=0xB6),
+        )
     }
 
-    /// Checks for MKV Magic bytes.
-    /// Contains two tests. One for EMBL bytes and other for segment bytes.
+    /// Checks for McPoodle's raw caption dumps: the DVD flavour starts with the `CC` user
+    /// data header `00 00 01 B2 43 43 01 F8`, the broadcast one with `FF FF` and then
+    /// line 21 byte pairs, whose bytes all have odd parity.
     /// Min Size of buffer is 4 bytes.
-    fn check_mkv(buffer: &[u8]) -> bool {
-        const MAGIC_BYTES_LEN: usize = 4;
-        const MKV_EMBL_MAGIC_BYTES: [u8; MAGIC_BYTES_LEN] = [0x1a, 0x45, 0xdf, 0xa3];
-        const MKV_SEGMENT_MAGIC_BYTES: [u8; MAGIC_BYTES_LEN] = [0x18, 0x53, 0x80, 0x67];
+    fn check_mcpoodles_raw(buffer: &[u8]) -> bool {
+        const DVD_MAGIC_BYTES: [u8; 8] = [0x00, 0x00, 0x01, 0xB2, 0x43, 0x43, 0x01, 0xF8];
+        const PAIRS_TO_PROBE: usize = 8;
 
-        if buffer.len() >= MAGIC_BYTES_LEN {
-            let buf = &buffer[0..MAGIC_BYTES_LEN];
-            return MKV_EMBL_MAGIC_BYTES == buf || MKV_SEGMENT_MAGIC_BYTES == buf;
+        if buffer.starts_with(&DVD_MAGIC_BYTES) {
+            return true;
+        }
+        match buffer.strip_prefix(&[0xFF, 0xFF]) {
+            Some(pairs) if pairs.len() >= 2 => pairs
+                .iter()
+                .take(2 * PAIRS_TO_PROBE)
+                .all(|x| x.count_ones() % 2 == 1),
+            _ => false,
         }
-        false
     }
 
-    /// Checks for GXF Magic bytes.
-    /// Min Size of buffer is 6 bytes.
-    fn check_gxf(buffer: &[u8]) -> bool {
-        const GXF_MAGIC_BYTES: [u8; 6] = [0, 0, 0, 0, 1, 0xbc];
+    /// Checks for an AV1 low overhead bitstream: a temporal delimiter OBU, then a sequence
+    /// header within the first 4 OBUs.
+    /// Every OBU header needs the forbidden and reserved bits clear, a known type and a
+    /// size field, and the temporal delimiters have to be empty.
+    /// Min Size of buffer is 3 bytes.
+    fn check_av1_obu(buffer: &[u8]) -> bool {
+        const MAX_OBUS: usize = 4;
+        const SEQUENCE_HEADER: u8 = 1;
+        const TEMPORAL_DELIMITER: u8 = 2;
+        const EXTENSION_FLAG: u8 = 0x04;
+        const HAS_SIZE_FIELD: u8 = 0x02;
 
-        if buffer.len() >= GXF_MAGIC_BYTES.len() {
-            return GXF_MAGIC_BYTES == buffer[0..GXF_MAGIC_BYTES.len()];
+        let mut pos = 0;
+        for i in 0..MAX_OBUS {
+            let header = match buffer.get(pos) {
+                Some(x) => *x,
+                None => return false,
+            };
+            let kind = header >> 3 & 0x0F;
+            if header & 0x81 != 0 || header & HAS_SIZE_FIELD == 0 || !matches!(kind, 1..=8 | 15) {
+                return false;
+            }
+            match kind {
+                _ if i == 0 && kind != TEMPORAL_DELIMITER => return false,
+                SEQUENCE_HEADER => return true,
+                _ => {}
+            }
+            let size_pos = pos + 1 + usize::from(header & EXTENSION_FLAG != 0);
+            let (size, len) = match bytes::leb128_at(buffer, size_pos) {
+                Some(x) => x,
+                None => return false,
+            };
+            if kind == TEMPORAL_DELIMITER && size != 0 {
+                return false;
+            }
+            pos = match usize::try_from(size)
+                .ok()
+                .and_then(|x| (size_pos + len).checked_add(x))
+            {
+                Some(x) => x,
+                None => return false,
+            };
         }
-
         false
     }
 
-    /// Checks for WTV Magic Bytes.
-    /// Min Size of buffer is 4 bytes.
-    fn check_wtv(buffer: &[u8]) -> bool {
-        const WTV_MAGIC_BYTES: [u8; 4] = [0xb7, 0xd8, 0x00, 0x20];
+    /// Length of the ID3v2 tag `buffer` starts with, footer included, 0 without one.
+    /// The size has to be syncsafe (high bit of each byte clear).
+    fn id3v2_len(buffer: &[u8]) -> usize {
+        const HEADER_LEN: usize = 10;
+        const FOOTER_FLAG: u8 = 0x10;
 
-        if buffer.len() >= WTV_MAGIC_BYTES.len() {
-            return WTV_MAGIC_BYTES == buffer[0..WTV_MAGIC_BYTES.len()];
+        match buffer.get(..HEADER_LEN) {
+            Some(x) if x.starts_with(b"ID3") && x[6..].iter().all(|x| x & 0x80 == 0) => {
+                let size = x[6..].iter().fold(0, |acc, x| acc << 7 | usize::from(*x));
+                let footer = if x[5] & FOOTER_FLAG != 0 {
+                    HEADER_LEN
+                } else {
+                    0
+                };
+                HEADER_LEN + size + footer
+            }
+            _ => 0,
         }
-        false
     }
 
-    /// Checks for CCExtractor Magic Bytes.
-    /// Min Size of buffer is 11 bytes.
-    fn check_rcwt(buffer: &[u8]) -> bool {
-        const MIN_LEN: usize = 11;
-        const RCWT_MAGIC_BYTES: [(usize, u8); 6] =
-            [(0, 0xCC), (1, 0xCC), (2, 0xED), (8, 0), (9, 0), (10, 0)];
+    /// Sampling frequency index and frame length of the ADTS header at `pos`.
+    /// `None` without the sync word, with a layer other than 0, a reserved sampling
+    /// frequency index or a frame length shorter than the header.
+    fn adts_header_at(buffer: &[u8], pos: usize) -> Option<(u8, usize)> {
+        const HEADER_LEN: usize = 7;
 
-        if buffer.len() >= MIN_LEN {
-            return RCWT_MAGIC_BYTES.iter().all(|x| buffer[x.0] == x.1);
+        let x = bytes::slice_at(buffer, pos, HEADER_LEN)?;
+        let sampling = x[2] >> 2 & 0x0F;
+        let len = usize::from(x[3] & 0x03) << 11 | usize::from(x[4]) << 3 | usize::from(x[5] >> 5);
+        if x[0] != 0xFF || x[1] & 0xF6 != 0xF0 || sampling > 12 || len < HEADER_LEN {
+            return None;
         }
-        false
+        Some((sampling, len))
     }
 
-    /// Checks for MP4 magic bytes.
-    /// [Magic Bytes List](https://www.garykessler.net/library/file_sigs.html)
-    fn check_mp4(buffer: &[u8]) -> bool {
-        const MIN_LEN: usize = 12;
-        const MP4_MAGIC_BYTES_1: [u8; 8] = [0x66, 0x74, 0x79, 0x70, 0x4D, 0x53, 0x4E, 0x56];
-        const MP4_MAGIC_BYTES_2: [u8; 8] = [0x66, 0x74, 0x79, 0x70, 0x69, 0x73, 0x6F, 0x6D];
+    /// Checks for AAC in ADTS frames: after an optional ID3v2 tag, an ADTS header (sync
+    /// word, layer 0, a valid sampling frequency index) and a second one with the same
+    /// sampling frequency exactly its frame length later.
+    /// The frames have to follow the tag directly, a sync word further in can be AAC
+    /// inside another container.
+    /// Min Size of buffer is 14 bytes.
+    fn check_aac_adts(buffer: &[u8]) -> bool {
+        let pos = Self::id3v2_len(buffer);
+        let (sampling, len) = match Self::adts_header_at(buffer, pos) {
+            Some(x) => x,
+            None => return false,
+        };
+        match pos
+            .checked_add(len)
+            .and_then(|x| Self::adts_header_at(buffer, x))
+        {
+            Some((x, _)) => x == sampling,
+            None => false,
+        }
+    }
 
-        if buffer.len() >= MIN_LEN {
-            let buffer = &buffer[4..MIN_LEN];
-            return MP4_MAGIC_BYTES_1 == buffer || MP4_MAGIC_BYTES_2 == buffer;
+    /// Checks for MPEG audio: after an optional ID3v2 tag, a frame header and a second
+    /// one of the same stream exactly its frame length later.
+    /// After an ID3v2 tag the first header is enough when `buffer` ends before the second
+    /// one. A tag reaching past the end of `buffer` is not, AAC is tagged too.
+    /// Min Size of buffer is 10 bytes.
+    fn check_mp3(buffer: &[u8]) -> bool {
+        const HEADER_LEN: usize = 4;
+
+        let pos = Self::id3v2_len(buffer);
+        let tagged = pos > 0;
+        let first = match mpeg_audio::frame_header_at(buffer, pos) {
+            Some(x) => x,
+            None => return false,
+        };
+        let next = pos + first.len;
+        if tagged && buffer.len() < next.saturating_add(HEADER_LEN) {
+            return true;
+        }
+        match mpeg_audio::frame_header_at(buffer, next) {
+            Some(x) => x.stream == first.stream,
+            None => false,
         }
-        false
     }
 
-    /// Checks for Material Exchange Format
-    fn check_mxf(buffer: &[u8]) -> bool {
-        const BYTES_LEN: usize = 14;
-        const MXF_MAGIC_BYTES: [u8; BYTES_LEN] = [
-            0x06, 0x0e, 0x2b, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01, 0x02,
+    /// Flavour, sample rate code and frame length of the Dolby sync frame at `pos`, told
+    /// apart by the bit stream ID: AC-3 up to 8, E-AC-3 from 11 to 16. `None` without the
+    /// sync word, with another bit stream ID or a reserved field.
+    fn dolby_frame_at(buffer: &[u8], pos: usize) -> Option<(Self, u8, usize)> {
+        /// AC-3 bitrates in kbit/s, by frame size code halved.
+        const BITRATES: [usize; 19] = [
+            32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384, 448, 512, 576, 640,
         ];
+        const SAMPLE_RATES: [usize; 3] = [48000, 44100, 32000];
 
-        if buffer.len() >= BYTES_LEN {
-            return (0..(buffer.len() - BYTES_LEN))
-                .any(|x| MXF_MAGIC_BYTES == buffer[x..(x + BYTES_LEN)]);
+        let x = bytes::slice_at(buffer, pos, 6)?;
+        if x[..2] != [0x0B, 0x77] {
+            return None;
+        }
+        let fscod = x[4] >> 6;
+        match x[5] >> 3 {
+            0..=8 if fscod != 3 => {
+                let frmsizecod = usize::from(x[4] & 0x3F);
+                let sample_rate = SAMPLE_RATES[usize::from(fscod)];
+                // 1536 samples a frame, in 16 bit words. 44.1 kHz frames alternate
+                // between two lengths to keep the bitrate.
+                let words = *BITRATES.get(frmsizecod / 2)? * 96_000 / sample_rate
+                    + usize::from(sample_rate == 44100) * (frmsizecod & 1);
+                Some((Self::AC3, fscod, words * 2))
+            }
+            11..=16 => {
+                let strmtyp = x[2] >> 6;
+                let words = (usize::from(x[2] & 0x07) << 8 | usize::from(x[3])) + 1;
+                // Reduced sample rates (fscod 3) are coded in fscod2, where 3 is reserved.
+                let fscod = match (fscod, x[4] >> 4 & 0x03) {
+                    (3, 3) => return None,
+                    (3, fscod2) => 3 + fscod2,
+                    (x, _) => x,
+                };
+                if strmtyp == 3 || words * 2 < x.len() {
+                    return None;
+                }
+                Some((Self::EAC3, fscod, words * 2))
+            }
+            _ => None,
         }
+    }
 
-        false
+    /// Flavour of the Dolby stream `buffer` starts with: a sync frame and a second one of
+    /// the same flavour and sample rate exactly its frame length later.
+    fn dolby_kind(buffer: &[u8]) -> Option<Self> {
+        let (kind, fscod, len) = Self::dolby_frame_at(buffer, 0)?;
+        match Self::dolby_frame_at(buffer, len)? {
+            (x, y, _) if x == kind && y == fscod => Some(kind),
+            _ => None,
+        }
     }
 
-    /// Checks for Transport stream.
-    /// Currently checks for 8 sync bytes.
-    fn check_ts(buffer: &[u8]) -> bool {
-        const TS_MAGIC_POS: usize = 188;
-        const MAGIC_BYTE: u8 = 0x47;
-        const SYNC_BYTES_TO_CHECK: usize = 8;
+    /// Checks for an AC-3 elementary stream, see `dolby_kind`.
+    /// Min Size of buffer is 134 bytes.
+    fn check_ac3(buffer: &[u8]) -> bool {
+        Self::dolby_kind(buffer) == Some(Self::AC3)
+    }
 
-        if buffer.len() > TS_MAGIC_POS * SYNC_BYTES_TO_CHECK {
-            return (0..TS_MAGIC_POS).any(|x| {
-                (0..SYNC_BYTES_TO_CHECK).all(|y| buffer[x + y * TS_MAGIC_POS] == MAGIC_BYTE)
-            });
-        }
+    /// Checks for an E-AC-3 elementary stream, see `dolby_kind`.
+    /// Min Size of buffer is 12 bytes.
+    fn check_eac3(buffer: &[u8]) -> bool {
+        Self::dolby_kind(buffer) == Some(Self::EAC3)
+    }
 
-        false
+    /// Checks for a DTS stream: one of the DTS sync words, in any of its four packings,
+    /// at the start or after a few zero bytes, and no DTS-HD extension substream.
+    /// See `dts_sync`.
+    /// Min Size of buffer is 4 bytes.
+    fn check_dts(buffer: &[u8]) -> bool {
+        dts::dts_sync(buffer).is_some() && !dts::has_extension(buffer)
     }
 
-    /// Checks for M2TS
-    /// Seperating from TS seemed better.
-    fn check_m2ts(buffer: &[u8]) -> bool {
-        const M2TS_MAGIC_POS: usize = 192;
-        const MAGIC_BYTE: u8 = 0x47;
-        const SYNC_BYTES_TO_CHECK: usize = 8;
+    /// Checks for a Screen Electronics PAC file: `01` and a run of 20 zero bytes, then the
+    /// first subtitle block. The block has its number, a `60` or `61` before the in and
+    /// out timecodes, the text length, 4 bytes of position and styling and the `FE`
+    /// starting its text at offset 38. A timecode is two little endian words, hours and
+    /// minutes as `hhmm` and seconds and frames as `ssff`, so each pair of digits is
+    /// checked for a valid time.
+    /// PAC has no magic. A zero padded binary header with an `FE` somewhere does not
+    /// match, but files whose first block is laid out differently are missed.
+    /// Min Size of buffer is 39 bytes.
+    fn check_pac(buffer: &[u8]) -> bool {
+        const PAC_HEADER_LEN: usize = 21;
+        const TIMECODE_MARKERS: [u8; 2] = [0x60, 0x61];
+        const TEXT_START: usize = 38;
 
-        if buffer.len() > M2TS_MAGIC_POS * SYNC_BYTES_TO_CHECK + 4 {
-            return (0..M2TS_MAGIC_POS).any(|x| {
-                (0..SYNC_BYTES_TO_CHECK).all(|y| buffer[x + 4 + M2TS_MAGIC_POS * y] == MAGIC_BYTE)
-            });
-        }
+        let block = match buffer.get(..=TEXT_START) {
+            Some(x) if x[0] == 0x01 && x[1..PAC_HEADER_LEN].iter().all(|x| *x == 0) => x,
+            _ => return false,
+        };
+        let word = |pos: usize| u16::from_le_bytes([block[pos], block[pos + 1]]);
+        let timecode = |pos: usize| {
+            let (hhmm, ssff) = (word(pos), word(pos + 2));
+            hhmm % 100 < 60 && ssff / 100 < 60 && ssff % 100 < 30
+        };
+        TIMECODE_MARKERS.contains(&block[23])
+            && timecode(24)
+            && timecode(28)
+            && word(32) > 0
+            && block[TEXT_START] == 0xFE
+    }
 
-        false
+    /// Checks for a tracker module: the XM or IT magic at the start, `SCRM` at offset 44
+    /// for S3M or a channel tag at offset 1080 for MOD. See `tracker_format`.
+    /// Min Size of buffer is 4 bytes, 1084 bytes for MOD.
+    fn check_tracker_module(buffer: &[u8]) -> bool {
+        tracker::tracker_format(buffer).is_some()
     }
 
-    /// Checks for PS (Needs PACK header)
-    fn check_ps(buffer: &[u8]) -> bool {
-        const MAGIC_NUMBER: usize = 50000;
-        const PS_MAGIC_BYTES: [u8; 4] = [0x00, 0x00, 0x01, 0xBA];
+    /// Checks for a DTS-HD stream: a DTS-HD extension substream at the start, after a few
+    /// zero bytes, or after the first DTS core frame.
+    /// Min Size of buffer is 4 bytes.
+    fn check_dtshd(buffer: &[u8]) -> bool {
+        dts::has_extension(buffer)
+    }
 
-        let len = buffer.len();
+    /// Checks for a TrueHD or MLP stream: a major sync (`F8 72 6F BA` for TrueHD, `BB` for
+    /// MLP) at an even offset in the first 512 bytes, usually right after the header of
+    /// the first access unit, with a valid sampling frequency in the format info
+    /// following it and the `B7 52` major sync signature after that.
+    /// Min Size of buffer is 10 bytes.
+    fn check_truehd(buffer: &[u8]) -> bool {
+        const SEARCH_LEN: usize = 512;
+        /// 48, 96 and 192 kHz, then 44.1, 88.2 and 176.4 kHz.
+        const SAMPLING_FREQUENCIES: [u8; 6] = [0, 1, 2, 8, 9, 10];
 
-        if len >= PS_MAGIC_BYTES.len() {
-            let limit = if len < MAGIC_NUMBER {
-                len - 3
-            } else {
-                MAGIC_NUMBER - 3
+        let end = buffer.len().min(SEARCH_LEN);
+        (0..end).step_by(2).any(|pos| {
+            let x = match bytes::slice_at(buffer, pos, 10) {
+                Some(x) => x,
+                None => return false,
             };
-            return (0..limit).any(|x| PS_MAGIC_BYTES == buffer[x..(x + PS_MAGIC_BYTES.len())]);
-        }
+            let frequency = match x[..4] {
+                [0xF8, 0x72, 0x6F, 0xBA] => x[4] >> 4,
+                [0xF8, 0x72, 0x6F, 0xBB] => x[5] >> 4,
+                _ => return false,
+            };
+            SAMPLING_FREQUENCIES.contains(&frequency) && x[8..10] == [0xB7, 0x52]
+        })
+    }
 
-        false
+    /// Length of the `AudioMuxElement` following the LOAS header at `pos`, `None` without
+    /// the 11 bit sync word 0x2B7 or with an empty element.
+    fn loas_len_at(buffer: &[u8], pos: usize) -> Option<usize> {
+        let x = bytes::slice_at(buffer, pos, 3)?;
+        let len = usize::from(x[1] & 0x1F) << 8 | usize::from(x[2]);
+        if x[0] != 0x56 || x[1] & 0xE0 != 0xE0 || len == 0 {
+            return None;
+        }
+        Some(len)
     }
 
-    /// Checks for Tivo Program Stream
-    fn check_tivo_ps(buffer: &[u8]) -> bool {
-        const MAGIC_BYTES: [u8; 4] = [b'T', b'i', b'V', b'o'];
+    /// Checks for AAC in LOAS framing: a LOAS header at the start and a second one
+    /// right after the `AudioMuxElement` of the first.
+    /// Min Size of buffer is 7 bytes.
+    fn check_aac_latm(buffer: &[u8]) -> bool {
+        const HEADER_LEN: usize = 3;
 
-        if buffer.len() >= MAGIC_BYTES.len() {
-            return MAGIC_BYTES == buffer[0..MAGIC_BYTES.len()];
+        match Self::loas_len_at(buffer, 0) {
+            Some(len) => Self::loas_len_at(buffer, HEADER_LEN + len).is_some(),
+            None => false,
         }
-
-        false
     }
 
     /// Checks for Elementary Stream
@@ -288,6 +2333,27 @@ impl ContainerType {
     }
 }
 
+/// Reads from `offset` without using the cursor of `file`.
+#[cfg(unix)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+/// Reads from `offset`. Moves the cursor of `file`.
+#[cfg(windows)]
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+/// Reads from `offset` by seeking. Moves the cursor of `file`.
+#[cfg(not(any(unix, windows)))]
+fn read_at(mut file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::io::{Read, Seek};
+
+    file.seek(io::SeekFrom::Start(offset))?;
+    file.read(buf)
+}
+
 impl fmt::Display for ContainerType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let name = match *self {
@@ -304,6 +2370,69 @@ impl fmt::Display for ContainerType {
             Self::MXF => "Material Exchange Format (MXF)",
             Self::McPoodlesRaw => "McPoodle's Raw File",
             Self::ES => "Elementary Stream (ES)",
+            Self::H264ES => "H.264 Elementary Stream (H264 ES)",
+            Self::H265ES => "H.265 Elementary Stream (H265 ES)",
+            Self::AVI => "Audio Video Interleave (AVI)",
+            Self::WAV => "Waveform Audio (WAV)",
+            Self::FLV => "Flash Video (FLV)",
+            Self::OGG => "Ogg (OGG)",
+            Self::WEBM => "WebM (WEBM)",
+            Self::MKA => "Matroska Audio (MKA)",
+            Self::MOV => "QuickTime (MOV)",
+            Self::ThreeGP => "3GPP (3GP)",
+            Self::ThreeG2 => "3GPP2 (3G2)",
+            Self::M4A => "MPEG-4 Audio (M4A)",
+            Self::FragmentedMP4 => "Fragmented MP4 (fMP4)",
+            Self::HEIF => "HEIF image (HEIC)",
+            Self::AVIF => "AVIF image",
+            Self::AVIFSequence => "AVIF image sequence",
+            Self::RealMedia => "RealMedia (RM)",
+            Self::IVF => "IVF",
+            Self::Y4M => "YUV4MPEG2 (Y4M)",
+            Self::MPEG4ES => "MPEG-4 Part 2 Elementary Stream (MPEG4 ES)",
+            Self::AV1OBU => "AV1 Low Overhead Bitstream (OBU)",
+            Self::AACADTS => "AAC Audio Data Transport Stream (ADTS)",
+            Self::AACLATM => "AAC LATM in LOAS framing",
+            Self::MP3 => "MPEG Audio (MP3)",
+            Self::FLAC => "Free Lossless Audio Codec (FLAC)",
+            Self::AC3 => "Dolby Digital (AC-3)",
+            Self::EAC3 => "Dolby Digital Plus (E-AC-3)",
+            Self::DTS => "DTS Coherent Acoustics (DTS)",
+            Self::TrueHD => "Dolby TrueHD / MLP",
+            Self::DTSHD => "DTS-HD",
+            Self::AIFF => "Audio Interchange File Format (AIFF)",
+            Self::AU => "Sun/NeXT Audio (AU)",
+            Self::AMR => "Adaptive Multi-Rate (AMR)",
+            Self::AMRWB => "Adaptive Multi-Rate Wideband (AMR-WB)",
+            Self::CAF => "Core Audio Format (CAF)",
+            Self::WavPack => "WavPack",
+            Self::APE => "Monkey's Audio (APE)",
+            Self::Musepack => "Musepack",
+            Self::TTA => "True Audio (TTA)",
+            Self::Shorten => "Shorten (SHN)",
+            Self::MIDI => "Standard MIDI File (SMF)",
+            Self::DSF => "DSD Stream File (DSF)",
+            Self::DFF => "DSD Interchange File Format (DSDIFF)",
+            Self::RF64 => "RF64 Waveform Audio (RF64)",
+            Self::W64 => "Sony Wave64 (W64)",
+            Self::TrackerModule => "Tracker Module (MOD, XM, S3M, IT)",
+            Self::WebVTT => "WebVTT",
+            Self::SCC => "Scenarist Closed Captions (SCC)",
+            Self::MCC => "MacCaption (MCC)",
+            Self::TTML => "Timed Text Markup Language (TTML)",
+            Self::EbuStl => "EBU Subtitle Data Exchange (EBU STL)",
+            Self::SAMI => "Synchronized Accessible Media Interchange (SAMI)",
+            Self::MicroDVD => "MicroDVD Subtitles",
+            Self::SubViewer => "SubViewer Subtitles",
+            Self::LRC => "LRC Lyrics",
+            Self::PGS => "Blu-ray Presentation Graphic Stream (PGS)",
+            Self::VobSubIdx => "VobSub Index (IDX)",
+            Self::VobSubPS => "VobSub Subpictures (SUB)",
+            Self::CheetahCAP => "Cheetah Closed Captions (CAP)",
+            Self::PAC => "Screen Electronics Subtitles (PAC)",
+            Self::SpruceSTL => "Spruce Subtitle Script (STL)",
+            Self::CDXA => "Video CD (RIFF CDXA)",
+            Self::VOB => "DVD-Video Object (VOB)",
         };
         write!(f, "{}", name)
     }
@@ -327,6 +2456,69 @@ impl FromStr for ContainerType {
             "mxf" => Ok(Self::MXF),
             "raw" => Ok(Self::McPoodlesRaw),
             "es" => Ok(Self::ES),
+            "h264" => Ok(Self::H264ES),
+            "h265" => Ok(Self::H265ES),
+            "avi" => Ok(Self::AVI),
+            "wav" => Ok(Self::WAV),
+            "flv" => Ok(Self::FLV),
+            "ogg" | "ogv" | "oga" => Ok(Self::OGG),
+            "webm" => Ok(Self::WEBM),
+            "mka" => Ok(Self::MKA),
+            "mov" | "qt" => Ok(Self::MOV),
+            "3gp" => Ok(Self::ThreeGP),
+            "3g2" => Ok(Self::ThreeG2),
+            "m4a" | "m4b" => Ok(Self::M4A),
+            "fmp4" | "m4s" => Ok(Self::FragmentedMP4),
+            "heif" | "heic" => Ok(Self::HEIF),
+            "avif" => Ok(Self::AVIF),
+            "avis" => Ok(Self::AVIFSequence),
+            "rm" | "rmvb" => Ok(Self::RealMedia),
+            "ivf" => Ok(Self::IVF),
+            "y4m" => Ok(Self::Y4M),
+            "mpeg4es" | "m4v-es" => Ok(Self::MPEG4ES),
+            "obu" | "av1" => Ok(Self::AV1OBU),
+            "aac" | "adts" => Ok(Self::AACADTS),
+            "latm" | "loas" => Ok(Self::AACLATM),
+            "mp3" | "mp2" | "mpa" => Ok(Self::MP3),
+            "flac" => Ok(Self::FLAC),
+            "ac3" => Ok(Self::AC3),
+            "eac3" | "ec3" => Ok(Self::EAC3),
+            "dts" => Ok(Self::DTS),
+            "dtshd" | "dts-hd" | "dtsma" => Ok(Self::DTSHD),
+            "aiff" | "aif" | "aifc" => Ok(Self::AIFF),
+            "au" | "snd" => Ok(Self::AU),
+            "amr" => Ok(Self::AMR),
+            "awb" | "amr-wb" => Ok(Self::AMRWB),
+            "caf" => Ok(Self::CAF),
+            "wv" | "wavpack" => Ok(Self::WavPack),
+            "ape" => Ok(Self::APE),
+            "mpc" | "musepack" => Ok(Self::Musepack),
+            "tta" => Ok(Self::TTA),
+            "shn" | "shorten" => Ok(Self::Shorten),
+            "mid" | "midi" => Ok(Self::MIDI),
+            "dsf" => Ok(Self::DSF),
+            "dff" | "dsdiff" => Ok(Self::DFF),
+            "rf64" => Ok(Self::RF64),
+            "w64" | "wave64" => Ok(Self::W64),
+            "mod" | "xm" | "s3m" | "it" => Ok(Self::TrackerModule),
+            "vtt" | "webvtt" => Ok(Self::WebVTT),
+            "scc" => Ok(Self::SCC),
+            "mcc" => Ok(Self::MCC),
+            "ttml" | "dfxp" => Ok(Self::TTML),
+            "ebustl" | "stl" => Ok(Self::EbuStl),
+            "smi" | "sami" => Ok(Self::SAMI),
+            "microdvd" | "sub-microdvd" => Ok(Self::MicroDVD),
+            "sbv" | "subviewer" => Ok(Self::SubViewer),
+            "lrc" => Ok(Self::LRC),
+            "sup" | "pgs" => Ok(Self::PGS),
+            "idx" | "vobsub-idx" => Ok(Self::VobSubIdx),
+            "vobsub" | "vobsub-sub" => Ok(Self::VobSubPS),
+            "cap" | "cheetah-cap" => Ok(Self::CheetahCAP),
+            "pac" => Ok(Self::PAC),
+            "spruce-stl" => Ok(Self::SpruceSTL),
+            "dat" | "cdxa" => Ok(Self::CDXA),
+            "vob" => Ok(Self::VOB),
+            "truehd" | "thd" | "mlp" => Ok(Self::TrueHD),
             _ => Err(format!("Failed to parse {}", s)),
         }
     }
@@ -338,8 +2530,14 @@ mod tests {
 
     #[test]
     fn asf() {
-        let t = ContainerType::check_asf(&[0x30, 0x26, 0xb2, 0x75, 0x34, 0]);
+        let t = ContainerType::check_asf(&[
+            0x30, 0x26, 0xb2, 0x75, 0x8e, 0x66, 0xcf, 0x11, 0xa6, 0xd9, 0x00, 0xaa, 0x00, 0x62,
+            0xce, 0x6c, 0x34, 0,
+        ]);
         assert!(t);
+
+        let t = ContainerType::check_asf(&[0x30, 0x26, 0xb2, 0x75, 0x34, 0]);
+        assert!(!t);
     }
 
     #[test]
@@ -347,7 +2545,31 @@ mod tests {
         let t1 = ContainerType::check_mkv(&[0x1a, 0x45, 0xdf, 0xa3, 0, 1]);
         assert!(t1);
         let t2 = ContainerType::check_mkv(&[0x18, 0x53, 0x80, 0x67, 10]);
+        assert!(!t2);
+    }
+
+    #[test]
+    fn webm() {
+        let mut buffer = vec![0x1a, 0x45, 0xdf, 0xa3, 0x87, 0x42, 0x82, 0x84];
+        buffer.extend_from_slice(b"webm");
+        assert!(ContainerType::check_webm(&buffer));
+        assert!(!ContainerType::check_webm(&buffer[..11]));
+        buffer[8..12].copy_from_slice(b"mkv\0");
+        assert!(!ContainerType::check_webm(&buffer));
+    }
+
+    #[test]
+    fn mkv_segment() {
+        let t1 = ContainerType::check_mkv_segment(&[
+            0x18, 0x53, 0x80, 0x67, 0x01, 0, 0, 0, 0, 0, 0x10, 0, 0x11, 0x4d, 0x9b, 0x74,
+        ]);
+        assert!(t1);
+        let t2 = ContainerType::check_mkv_segment(&[0x18, 0x53, 0x80, 0x67, 0x81, 0xec]);
         assert!(t2);
+        let t3 = ContainerType::check_mkv_segment(&[0x18, 0x53, 0x80, 0x67, 0x81, 0x3e, 0x92]);
+        assert!(!t3);
+        let t4 = ContainerType::check_mkv_segment(&[0x18, 0x53, 0x80, 0x67, 0]);
+        assert!(!t4);
     }
 
     #[test]
@@ -358,14 +2580,37 @@ mod tests {
 
     #[test]
     fn wtv() {
-        let t = ContainerType::check_wtv(&[0xb7, 0xd8, 0x00, 0x20, 0]);
+        let t = ContainerType::check_wtv(&[
+            0xb7, 0xd8, 0x00, 0x20, 0x37, 0x49, 0xda, 0x11, 0xa6, 0x4e, 0x00, 0x07, 0xe9, 0x5e,
+            0xad, 0x8d, 0, 0x10,
+        ]);
         assert!(t);
+
+        let t = ContainerType::check_wtv(&[
+            0xb7, 0xd8, 0x00, 0x20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+        assert!(!t);
+
+        let t = ContainerType::check_wtv(&[0xb7, 0xd8, 0x00, 0x20, 0]);
+        assert!(!t);
     }
 
     #[test]
     fn rcwt() {
-        let t = ContainerType::check_rcwt(&[0xCC, 0xCC, 0xED, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        let t = ContainerType::check_rcwt(&[
+            0xCC, 0xCC, 0xED, 0xCC, 0x00, 0x50, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+        ]);
         assert!(t);
+
+        let future = ContainerType::check_rcwt(&[
+            0xCC, 0xCC, 0xED, 0xCC, 0x00, 0x50, 0, 2, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+        assert!(!future);
+
+        let corrupted = ContainerType::check_rcwt(&[
+            0xCC, 0xCC, 0xED, 0x12, 0x00, 0x50, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+        assert!(!corrupted);
     }
 
     #[test]
@@ -380,6 +2625,43 @@ mod tests {
         assert!(t2);
     }
 
+    #[test]
+    fn ftyp_brands() {
+        let (major, compatible) =
+            ContainerType::ftyp_brands(b"\x00\x00\x00\x18ftyp3gp4\x00\x00\x02\x00isom3gp4")
+                .unwrap();
+        assert_eq!(major, b"3gp4");
+        assert_eq!(compatible, b"isom3gp4");
+        // 64 bit size, compatible brands cut by the buffer.
+        let t = ContainerType::ftyp_brands(
+            b"\x00\x00\x00\x01ftyp\x00\x00\x00\x00\x00\x00\x00\x20mp42\x00\x00\x00\x00iso",
+        );
+        assert_eq!(t, Some((&b"mp42"[..], &b"iso"[..])));
+        // Size too small for the major brand and minor version.
+        let t = ContainerType::ftyp_brands(b"\x00\x00\x00\x0cftyp3gp4\x00\x00\x02\x00");
+        assert_eq!(t, None);
+    }
+
+    #[test]
+    fn mov() {
+        assert!(ContainerType::check_mov(b"\x00\x00\x00\x14ftypqt  "));
+        assert!(!ContainerType::check_mov(b"\x00\x00\x00\x14ftypisom"));
+
+        let mut buffer = *b"\x00\x00\x00\x08widemdat";
+        assert!(ContainerType::check_mov(&buffer));
+        // Too small for an atom header.
+        buffer[3] = 4;
+        assert!(!ContainerType::check_mov(&buffer));
+        buffer[3] = 0;
+        assert!(!ContainerType::check_mov(&buffer));
+        // Unknown top level type.
+        assert!(!ContainerType::check_mov(b"\x00\x00\x00\x08trakmdat"));
+        // Next atom type is not printable.
+        assert!(!ContainerType::check_mov(
+            b"\x00\x00\x00\x08wide\x00\x00\x00\x08\x01\x02\x03\x04"
+        ));
+    }
+
     #[test]
     fn mxf() {
         let t = ContainerType::check_mxf(&[
@@ -394,31 +2676,105 @@ mod tests {
         let mut buffer = [0; 192 * 9];
         for i in 0..8 {
             buffer[2 + i * 188] = 0x47;
+            buffer[2 + i * 188 + 3] = 0x10;
         }
-        let t = ContainerType::check_ts(&buffer);
+        let t = ContainerType::check_ts(&buffer, DEFAULT_TS_SYNC_THRESHOLD);
         assert!(t);
     }
 
+    #[test]
+    fn ts_corrupted() {
+        let mut buffer = [0; 188 * 10];
+        for i in 0..10 {
+            buffer[i * 188..i * 188 + 4].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+        }
+        buffer[3 * 188] = 0x46;
+        assert!(ContainerType::check_ts(&buffer, DEFAULT_TS_SYNC_THRESHOLD));
+        assert!(!ContainerType::check_ts(&buffer, 10));
+
+        buffer[5 * 188] = 0x46;
+        buffer[7 * 188] = 0x46;
+        assert!(ContainerType::check_ts(&buffer, DEFAULT_TS_SYNC_THRESHOLD));
+        buffer[8 * 188] = 0x46;
+        assert!(!ContainerType::check_ts(&buffer, DEFAULT_TS_SYNC_THRESHOLD));
+    }
+
+    #[test]
+    fn ts_family() {
+        // 8 M2TS packets with ascending arrival timestamps, and 8 TS packets at
+        // offset 100 on the 188 byte stride in between them.
+        let mut buffer = [0; 192 * 8 + 8];
+        for i in 0..8 {
+            buffer[i * 192..i * 192 + 4].copy_from_slice(&(1000 * i as u32 + 1).to_be_bytes());
+            buffer[i * 192 + 4] = 0x47;
+            buffer[i * 192 + 4 + 3] = 0x10;
+            buffer[100 + i * 188] = 0x47;
+            buffer[100 + i * 188 + 3] = 0x10;
+        }
+        assert!(ContainerType::check_ts(&buffer, DEFAULT_TS_SYNC_THRESHOLD));
+        assert!(ContainerType::check_m2ts(
+            &buffer,
+            DEFAULT_TS_SYNC_THRESHOLD
+        ));
+        let t = ContainerType::check_ts_family(&buffer, DEFAULT_TS_SYNC_THRESHOLD);
+        assert_eq!(t, Some(ContainerType::M2TS));
+
+        // Same packets without timestamps fall back to TS.
+        for i in 0..8 {
+            buffer[i * 192..i * 192 + 4].copy_from_slice(&[0; 4]);
+        }
+        let t = ContainerType::check_ts_family(&buffer, DEFAULT_TS_SYNC_THRESHOLD);
+        assert_eq!(t, Some(ContainerType::TS));
+    }
+
+    #[test]
+    fn ts_text() {
+        let t = ContainerType::check_ts(&[b'G'; 2048], DEFAULT_TS_SYNC_THRESHOLD);
+        assert!(!t);
+        let t = ContainerType::check_m2ts(&[b'G'; 2048], DEFAULT_TS_SYNC_THRESHOLD);
+        assert!(!t);
+    }
+
     #[test]
     fn m2ts() {
         let mut buffer = [0; 192 * 9];
         for i in 0..8 {
             buffer[2 + 4 + i * 192] = 0x47;
+            buffer[2 + 4 + i * 192 + 3] = 0x10;
         }
-        let t = ContainerType::check_m2ts(&buffer);
+        let t = ContainerType::check_m2ts(&buffer, DEFAULT_TS_SYNC_THRESHOLD);
         assert!(t);
     }
 
     #[test]
     fn ps() {
-        let t = ContainerType::check_ps(&[0, 0, 0x00, 0x00, 0x01, 0xBA, 0, 0]);
+        let t = ContainerType::check_ps(&[0, 0, 0x00, 0x00, 0x01, 0xBA, 0, 0], DEFAULT_PS_WINDOW);
         assert!(t);
 
         let mut buffer = [0; 50100];
         buffer[1000] = 0x01;
         buffer[1001] = 0xBA;
-        let t = ContainerType::check_ps(&buffer);
+        let t = ContainerType::check_ps(&buffer, DEFAULT_PS_WINDOW);
         assert!(t);
+
+        let mut buffer = vec![0; 70000];
+        buffer[60000..60004].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+        assert!(!ContainerType::check_ps(&buffer, DEFAULT_PS_WINDOW));
+        assert!(ContainerType::check_ps(&buffer, 64 * 1024));
+        assert!(!ContainerType::check_ps(&buffer, 60003));
+        assert!(ContainerType::check_ps(&buffer[..60004], usize::MAX));
+    }
+
+    #[test]
+    fn y4m() {
+        assert!(ContainerType::check_y4m(
+            b"YUV4MPEG2 W352 H288 F30:1 Ip A1:1 C420\n"
+        ));
+        // Header line not in the buffer yet.
+        assert!(ContainerType::check_y4m(b"YUV4MPEG2 W352"));
+        assert!(!ContainerType::check_y4m(b"YUV4MPEG2 W352 F30:1\n"));
+        assert!(!ContainerType::check_y4m(b"YUV4MPEG2 Wide H288\n"));
+        assert!(!ContainerType::check_y4m(b"YUV4MPEG2"));
     }
 
     #[test]
@@ -427,9 +2783,445 @@ mod tests {
         assert!(t);
     }
 
+    #[test]
+    fn avi() {
+        let mut buffer = *b"RIFF\x58\x00\x00\x00AVI LIST";
+        assert!(ContainerType::check_avi(&buffer));
+        buffer[8..12].copy_from_slice(b"WAVE");
+        assert!(!ContainerType::check_avi(&buffer));
+        buffer[8..12].copy_from_slice(b"AVI ");
+        buffer[4..8].copy_from_slice(&4u32.to_le_bytes());
+        assert!(!ContainerType::check_avi(&buffer));
+        assert!(!ContainerType::check_avi(&buffer[..8]));
+    }
+
+    #[test]
+    fn wav() {
+        let mut buffer = *b"RIFF\x24\x08\x00\x00WAVEfmt ";
+        assert!(ContainerType::check_wav(&buffer));
+        for len in 0..12 {
+            assert!(!ContainerType::check_wav(&buffer[..len]));
+        }
+        buffer[8..12].copy_from_slice(b"AVI ");
+        assert!(!ContainerType::check_wav(&buffer));
+        assert!(ContainerType::check_avi(&buffer));
+    }
+
+    #[test]
+    fn flv() {
+        let mut buffer = [b'F', b'L', b'V', 0x01, 0x05, 0, 0, 0, 0x09];
+        assert!(ContainerType::check_flv(&buffer));
+        buffer[4] = 0x08;
+        assert!(!ContainerType::check_flv(&buffer));
+        buffer[4] = 0x01;
+        buffer[8] = 0x08;
+        assert!(!ContainerType::check_flv(&buffer));
+        assert!(!ContainerType::check_flv(b"FLV files are old"));
+    }
+
+    #[test]
+    fn rm() {
+        let mut buffer = [0x2E, 0x52, 0x4D, 0x46, 0, 0, 0, 0x12, 0, 1];
+        assert!(ContainerType::check_rm(&buffer));
+        buffer[9] = 2;
+        assert!(!ContainerType::check_rm(&buffer));
+        buffer[9] = 0;
+        buffer[7] = 0x08;
+        assert!(!ContainerType::check_rm(&buffer));
+        assert!(!ContainerType::check_rm(b".RM"));
+        assert!(!ContainerType::check_rm(b".RMF is a chunk"));
+    }
+
+    #[test]
+    fn ivf() {
+        let mut buffer = *b"DKIF\x00\x00\x20\x00VP80";
+        assert!(ContainerType::check_ivf(&buffer));
+        buffer[4] = 1;
+        assert!(!ContainerType::check_ivf(&buffer));
+        buffer[4] = 0;
+        buffer[8] = 0;
+        assert!(!ContainerType::check_ivf(&buffer));
+        assert!(!ContainerType::check_ivf(b"DKIF\x00\x00"));
+    }
+
+    #[test]
+    fn ogg() {
+        let mut buffer = *b"OggS\x00\x02\x00\x00";
+        assert!(ContainerType::check_ogg(&buffer, false));
+        buffer[4] = 1;
+        assert!(!ContainerType::check_ogg(&buffer, false));
+        buffer[4] = 0;
+        buffer[5] = 0x00;
+        assert!(!ContainerType::check_ogg(&buffer, false));
+        assert!(ContainerType::check_ogg(&buffer, true));
+        buffer[5] = 0x08;
+        assert!(!ContainerType::check_ogg(&buffer, true));
+    }
+
     #[test]
     fn es() {
         let t = ContainerType::check_es(&[0, 0, 1, 0xB3, 0, 0]);
         assert!(t);
     }
+
+    #[test]
+    fn h264_es() {
+        let mut buffer = vec![0, 0, 0, 1, 0x67, 0x42, 0, 0x1E, 0, 0, 0, 1, 0x68, 0xCE];
+        assert!(ContainerType::check_h264_es(&buffer));
+        // Zero padding.
+        buffer.splice(0..0, [0; 100]);
+        assert!(ContainerType::check_h264_es(&buffer));
+        // Non IDR slice first.
+        buffer[104] = 0x41;
+        assert!(!ContainerType::check_h264_es(&buffer));
+        buffer[104] = 0x67;
+        // Forbidden bit set in the next NAL unit.
+        buffer[112] = 0xE8;
+        assert!(!ContainerType::check_h264_es(&buffer));
+        // Padding that is not zeros.
+        assert!(!ContainerType::check_h264_es(&[0xFF, 0, 0, 1, 0x67, 0x42]));
+        assert!(!ContainerType::check_h264_es(&[0; 16]));
+    }
+
+    #[test]
+    fn h265_es() {
+        // VPS, SPS and PPS, layer 0, temporal id 0.
+        let mut buffer = vec![0, 0, 0, 1, 0x40, 0x01, 0x0C, 0, 0, 0, 1, 0x42, 0x01, 0x01];
+        buffer.extend_from_slice(&[0, 0, 0, 1, 0x44, 0x01, 0xC1]);
+        assert!(ContainerType::check_h265_es(&buffer));
+        assert!(!ContainerType::check_h264_es(&buffer));
+        // Temporal id plus 1 of 0.
+        buffer[5] = 0x00;
+        assert!(!ContainerType::check_h265_es(&buffer));
+        buffer[5] = 0x01;
+        // Layer 1 in the next NAL unit.
+        buffer[12] = 0x09;
+        assert!(!ContainerType::check_h265_es(&buffer));
+        // Trail slice first.
+        assert!(!ContainerType::check_h265_es(&[0, 0, 1, 0x02, 0x01, 0xD0]));
+        // Header cut off.
+        assert!(!ContainerType::check_h265_es(&[0, 0, 1, 0x40]));
+    }
+
+    #[test]
+    fn aac_adts() {
+        // AAC LC, 44.1 kHz, stereo, 9 byte frames.
+        let frame = [0xFF, 0xF1, 0x50, 0x80, 0x01, 0x3F, 0xFC, 0x21, 0x00];
+        let buffer = [frame, frame].concat();
+        assert!(ContainerType::check_aac_adts(&buffer));
+        // Second frame missing.
+        assert!(!ContainerType::check_aac_adts(&buffer[..14]));
+        // Sampling frequency changes.
+        let mut t = buffer.clone();
+        t[11] = 0x4C;
+        assert!(!ContainerType::check_aac_adts(&t));
+        // Reserved sampling frequency index.
+        let mut t = buffer.clone();
+        t[2] = 0x74;
+        t[11] = 0x74;
+        assert!(!ContainerType::check_aac_adts(&t));
+        // Layer 1.
+        let mut t = buffer;
+        t[1] = 0xF3;
+        t[10] = 0xF3;
+        assert!(!ContainerType::check_aac_adts(&t));
+    }
+
+    #[test]
+    fn ac3_frame_len() {
+        let len = |fscod: u8, frmsizecod: u8| {
+            let header = [0x0B, 0x77, 0, 0, fscod << 6 | frmsizecod, 0x40];
+            ContainerType::dolby_frame_at(&header, 0).map(|x| x.2)
+        };
+        assert_eq!(len(0, 0), Some(128));
+        assert_eq!(len(0, 37), Some(2560));
+        assert_eq!(len(1, 0), Some(138));
+        assert_eq!(len(1, 1), Some(140));
+        assert_eq!(len(1, 37), Some(2788));
+        assert_eq!(len(2, 20), Some(1152));
+        assert_eq!(len(0, 38), None);
+        assert_eq!(len(3, 0), None);
+    }
+
+    #[test]
+    fn truehd() {
+        // Access unit header, then a TrueHD major sync at 48 kHz.
+        let mut buffer = vec![0xF0, 0x80, 0x00, 0x00, 0xF8, 0x72, 0x6F, 0xBA];
+        buffer.extend_from_slice(&[0x00, 0x00, 0x0F, 0x4F, 0xB7, 0x52, 0x00, 0x00]);
+        assert!(ContainerType::check_truehd(&buffer));
+        assert!(!ContainerType::check_truehd(&buffer[..13]));
+        // Reserved sampling frequency.
+        let mut t = buffer.clone();
+        t[8] = 0x30;
+        assert!(!ContainerType::check_truehd(&t));
+        // No signature.
+        let mut t = buffer.clone();
+        t[13] = 0x53;
+        assert!(!ContainerType::check_truehd(&t));
+        // Odd offset.
+        buffer.insert(0, 0);
+        assert!(!ContainerType::check_truehd(&buffer));
+    }
+
+    #[test]
+    fn eac3_frame() {
+        // Independent substream, 48 kHz, six blocks, bsid 16: 768 bytes.
+        let mut header = [0x0B, 0x77, 0x01, 0x7F, 0x34, 0x80];
+        let t = ContainerType::dolby_frame_at(&header, 0);
+        assert_eq!(t, Some((ContainerType::EAC3, 0, 768)));
+        // 24 kHz, coded in fscod2.
+        header[4] = 0xD4;
+        let t = ContainerType::dolby_frame_at(&header, 0);
+        assert_eq!(t, Some((ContainerType::EAC3, 4, 768)));
+        // Reserved fscod2, reserved stream type, bsid 9.
+        header[4] = 0xF4;
+        assert_eq!(ContainerType::dolby_frame_at(&header, 0), None);
+        header[4] = 0x34;
+        header[2] = 0xC1;
+        assert_eq!(ContainerType::dolby_frame_at(&header, 0), None);
+        header[2] = 0x01;
+        header[5] = 0x48;
+        assert_eq!(ContainerType::dolby_frame_at(&header, 0), None);
+    }
+
+    #[test]
+    fn flac() {
+        assert!(ContainerType::check_flac(b"fLaC\x00\x00\x00\x22"));
+        // Only metadata block.
+        assert!(ContainerType::check_flac(b"fLaC\x80\x00\x00\x22"));
+        // VORBIS_COMMENT first, or a STREAMINFO of the wrong length.
+        assert!(!ContainerType::check_flac(b"fLaC\x04\x00\x00\x22"));
+        assert!(!ContainerType::check_flac(b"fLaC\x00\x00\x00\x20"));
+        assert!(!ContainerType::check_flac(b"fLaC\x00\x00\x00"));
+    }
+
+    #[test]
+    fn mp3() {
+        // MPEG-1 Layer III, 32 kbit/s at 48 kHz: 96 byte frames.
+        let mut frame = vec![0; 96];
+        frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x14, 0xC4]);
+        let buffer = [&frame[..], &frame].concat();
+        assert!(ContainerType::check_mp3(&buffer));
+        assert!(!ContainerType::check_mp3(&buffer[..99]));
+        // Second frame of another sampling rate.
+        let mut t = buffer.clone();
+        t[98] = 0x10;
+        assert!(!ContainerType::check_mp3(&t));
+
+        let tag = b"ID3\x03\x00\x00\x00\x00\x00\x10";
+        let tagged = [&tag[..], &[0; 16], &buffer].concat();
+        assert!(ContainerType::check_mp3(&tagged));
+        // First frame only, or the tag only.
+        assert!(ContainerType::check_mp3(&tagged[..130]));
+        assert!(!ContainerType::check_mp3(&tagged[..20]));
+        // Tag followed by something else.
+        let mut t = tagged;
+        t[26] = 0;
+        assert!(!ContainerType::check_mp3(&t));
+    }
+
+    #[test]
+    fn aac_latm() {
+        let frame = [0x56, 0xE0, 0x04, 0x47, 0xFC, 0x00, 0x00];
+        let buffer = [frame, frame].concat();
+        assert!(ContainerType::check_aac_latm(&buffer));
+        // Second frame missing or cut before its length.
+        assert!(!ContainerType::check_aac_latm(&buffer[..9]));
+        // Second sync word off by one byte.
+        let buffer = [&frame[..], &[0], &frame].concat();
+        assert!(!ContainerType::check_aac_latm(&buffer));
+        // Empty element.
+        let frame = [0x56, 0xE0, 0x00];
+        assert!(!ContainerType::check_aac_latm(&[frame, frame].concat()));
+    }
+
+    #[test]
+    fn id3v2_len() {
+        assert_eq!(
+            ContainerType::id3v2_len(b"ID3\x04\x00\x00\x00\x00\x02\x01"),
+            267
+        );
+        assert_eq!(
+            ContainerType::id3v2_len(b"ID3\x04\x00\x10\x00\x00\x00\x01"),
+            21
+        );
+        // Not syncsafe.
+        assert_eq!(
+            ContainerType::id3v2_len(b"ID3\x04\x00\x00\x00\x00\x00\x81"),
+            0
+        );
+        assert_eq!(ContainerType::id3v2_len(b"ID3\x04"), 0);
+    }
+
+    #[test]
+    fn av1_obu() {
+        // Temporal delimiter, then a sequence header.
+        let mut buffer = vec![0x12, 0x00, 0x0A, 0x0B, 0, 0, 0, 0x42, 0x22, 0xBF];
+        assert!(ContainerType::check_av1_obu(&buffer));
+        // Padding OBU in between, with a 2 byte size.
+        buffer.splice(2..2, [0x7A, 0x81, 0x00, 0xAA]);
+        assert!(ContainerType::check_av1_obu(&buffer));
+        // Size field cut off.
+        assert!(!ContainerType::check_av1_obu(&buffer[..3]));
+        assert!(!ContainerType::check_av1_obu(&buffer[..4]));
+        // Padding longer than the buffer.
+        buffer[4] = 0x10;
+        assert!(!ContainerType::check_av1_obu(&buffer));
+        // Sequence header first, temporal delimiter with a payload, no size field.
+        assert!(!ContainerType::check_av1_obu(&[0x0A, 0x0B, 0x00, 0x00]));
+        assert!(!ContainerType::check_av1_obu(&[
+            0x12, 0x01, 0x00, 0x0A, 0x0B
+        ]));
+        assert!(!ContainerType::check_av1_obu(&[0x12, 0x00, 0x08, 0x00]));
+        // LEB128 longer than 8 bytes.
+        let mut buffer = vec![0x12, 0x00, 0x7A];
+        buffer.extend([0xFF; 9]);
+        assert!(!ContainerType::check_av1_obu(&buffer));
+    }
+
+    #[test]
+    fn mpeg4_es() {
+        // VOS (Advanced Simple, level 5), VO, then VOL.
+        let mut buffer = vec![
+            0, 0, 1, 0xB0, 0xF5, 0, 0, 1, 0xB5, 0x09, 0, 0, 1, 0x20, 0x08,
+        ];
+        assert!(ContainerType::check_mpeg4_es(&buffer));
+        buffer[4] = 0;
+        assert!(!ContainerType::check_mpeg4_es(&buffer));
+        buffer[4] = 0xF5;
+        // MPEG-2 slice after the VOS.
+        buffer[13] = 0x41;
+        assert!(!ContainerType::check_mpeg4_es(&buffer));
+        assert!(ContainerType::check_mpeg4_es(&[0, 0, 0, 1, 0xB6, 0x10]));
+        assert!(!ContainerType::check_mpeg4_es(&[0, 0, 1, 0xB3, 0x2D, 0x02]));
+    }
+
+    #[test]
+    fn short_name() {
+        for kind in DEFAULT_PRIORITY {
+            assert_eq!(kind.short_name().parse(), Ok(*kind));
+        }
+    }
+
+    #[test]
+    fn ffmpeg_format() {
+        for kind in DEFAULT_PRIORITY.iter().copied() {
+            let expected = match kind {
+                ContainerType::M2TS => ContainerType::TS,
+                ContainerType::WEBM | ContainerType::MKA => ContainerType::MKV,
+                ContainerType::MOV
+                | ContainerType::ThreeGP
+                | ContainerType::ThreeG2
+                | ContainerType::M4A
+                | ContainerType::FragmentedMP4
+                | ContainerType::HEIF
+                | ContainerType::AVIF
+                | ContainerType::AVIFSequence => ContainerType::MP4,
+                ContainerType::DTSHD => ContainerType::DTS,
+                ContainerType::AMRWB => ContainerType::AMR,
+                ContainerType::RF64 => ContainerType::WAV,
+                ContainerType::VobSubPS | ContainerType::CDXA | ContainerType::VOB => {
+                    ContainerType::PS
+                }
+                x => x,
+            };
+            let name = match kind.ffmpeg_format() {
+                Some(x) => x,
+                None => continue,
+            };
+            let t = ContainerType::from_ffmpeg_format(name);
+            assert_eq!(t, Some(expected), "{}", name);
+        }
+        assert_eq!(ContainerType::RCWT.ffmpeg_format(), None);
+        assert_eq!(ContainerType::from_ffmpeg_format("rcwt"), None);
+        assert_eq!(ContainerType::from_ffmpeg_format("midi"), None);
+
+        let t = ContainerType::from_ffmpeg_format("WebM");
+        assert_eq!(t, Some(ContainerType::MKV));
+        let t = ContainerType::from_ffmpeg_format("mp4");
+        assert_eq!(t, Some(ContainerType::MP4));
+        let t = ContainerType::from_ffmpeg_format("nut, MPEGTS");
+        assert_eq!(t, Some(ContainerType::TS));
+        assert_eq!(ContainerType::from_ffmpeg_format("nut"), None);
+        assert_eq!(
+            ContainerType::from_ffmpeg_format("flac"),
+            Some(ContainerType::FLAC)
+        );
+        assert_eq!(ContainerType::from_ffmpeg_format(""), None);
+    }
+
+    #[test]
+    fn gst_caps() {
+        // Containers typefind reports as another one.
+        let found_as = |x: ContainerType| match x {
+            ContainerType::VOB | ContainerType::VobSubPS | ContainerType::CDXA => ContainerType::PS,
+            ContainerType::SubViewer => ContainerType::MicroDVD,
+            x => x,
+        };
+        for x in DEFAULT_PRIORITY.iter().copied() {
+            let media_type = x.gst_caps().split(',').next().unwrap();
+            assert_eq!(media_type.matches('/').count(), 1, "{}", x.gst_caps());
+            for y in DEFAULT_PRIORITY.iter().copied() {
+                let same = x.gst_caps() == y.gst_caps();
+                assert_eq!(same, found_as(x) == found_as(y), "{} {}", x, y);
+            }
+        }
+        assert!(ContainerType::TS
+            .gst_caps()
+            .ends_with("packetsize=(int)188"));
+        assert!(ContainerType::M2TS
+            .gst_caps()
+            .ends_with("packetsize=(int)192"));
+    }
+
+    #[test]
+    fn truncated() {
+        let mut ts = vec![0; 192 * 11 + 8];
+        for i in 0..11 {
+            ts[i * 192 + 4] = 0x47;
+            ts[i * 192 + 7] = 0x10;
+            ts[i * 188] = 0x47;
+            ts[i * 188 + 3] = 0x10;
+        }
+        let seeds: Vec<Vec<u8>> = vec![
+            vec![
+                0x18, 0x53, 0x80, 0x67, 0x01, 0, 0, 0, 0, 0, 0x10, 0, 0x11, 0x4d,
+            ],
+            vec![
+                0xCC, 0xCC, 0xED, 0xCC, 0x00, 0x50, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+            ],
+            vec![
+                0, 0, 1, 0xBA, 0x44, 0, 4, 0, 4, 1, 0, 0, 3, 0xF8, 0, 0, 1, 0xE0,
+            ],
+            ts,
+            vec![0; 2200],
+            vec![0xFF; 2200],
+        ];
+        let detector = crate::Detector::builder().lenient(true).build();
+        for seed in &seeds {
+            for len in 0..=seed.len() {
+                let buffer = &seed[..len];
+                ContainerType::check_mkv_segment(buffer);
+                ContainerType::check_mxf(buffer);
+                ContainerType::check_ts_family(buffer, DEFAULT_TS_SYNC_THRESHOLD);
+                ContainerType::check_ps(buffer, DEFAULT_PS_WINDOW);
+                ContainerType::check_rcwt(buffer);
+                ContainerType::check_fmp4(buffer);
+                detector.detect_all(buffer);
+            }
+        }
+    }
+
+    #[test]
+    fn huge_offsets() {
+        let buffer = [0x47, 0, 0, 0x10];
+        assert_eq!(ContainerType::ts_packet_pid(&buffer, usize::MAX - 2), None);
+        assert!(!ContainerType::check_ts_packets(
+            &buffer,
+            usize::MAX - 10,
+            188,
+            DEFAULT_TS_SYNC_THRESHOLD
+        ));
+        assert!(!ContainerType::m2ts_ascending_ats(&buffer, usize::MAX - 1));
+    }
 }
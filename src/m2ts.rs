@@ -0,0 +1,163 @@
+//! Profile of an M2TS stream, see [`m2ts_profile`].
+
+use crate::{bytes, ContainerType, DEFAULT_TS_SYNC_THRESHOLD, TS_PACKET_LEN};
+
+/// Profile of an M2TS stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum M2tsProfile {
+    /// BDAV stream of Blu-ray discs and AVCHD camcorders
+    Bdav,
+    /// Only the 192 byte packet cadence
+    Generic,
+}
+
+/// Length of an M2TS packet, the 4 byte TP_extra_header and a TS packet.
+const M2TS_PACKET_LEN: usize = 192;
+
+/// TP_extra_headers looked at for the copy permission and arrival timestamps.
+const HEADERS_TO_CHECK: usize = 8;
+
+/// Profile of the M2TS stream in `buffer`. BDAV needs the same copy_permission_indicator
+/// and strictly ascending 30 bit arrival timestamps in the TP_extra_headers of the first
+/// 8 packets, and a PAT section starting in the first packet with PID 0. `None` if
+/// `buffer` is not M2TS.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{m2ts_profile, M2tsProfile};
+///
+/// let mut buffer = vec![0xFF; 192 * 10];
+/// for i in 0..10 {
+///     let packet = &mut buffer[i * 192..];
+///     packet[..4].copy_from_slice(&(1000 * i as u32).to_be_bytes());
+///     packet[4..13].copy_from_slice(&[0x47, 0x40, 0x00, 0x10, 0x00, 0x00, 0xB0, 0x0D, 0x00]);
+/// }
+/// assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Bdav));
+/// ```
+pub fn m2ts_profile(buffer: &[u8]) -> Option<M2tsProfile> {
+    if ContainerType::check_ts_family(buffer, DEFAULT_TS_SYNC_THRESHOLD)
+        != Some(ContainerType::M2TS)
+    {
+        return None;
+    }
+    let (start, run) =
+        ContainerType::ts_best_run(buffer, 4, M2TS_PACKET_LEN, DEFAULT_TS_SYNC_THRESHOLD);
+    let bdav = same_copy_permission(buffer, start)
+        && ContainerType::m2ts_ascending_ats(buffer, start)
+        && first_pat(buffer, start, run);
+    Some(if bdav {
+        M2tsProfile::Bdav
+    } else {
+        M2tsProfile::Generic
+    })
+}
+
+/// Same copy_permission_indicator, the top 2 bits of the TP_extra_header, in the first
+/// 8 packets starting at `start`.
+fn same_copy_permission(buffer: &[u8], start: usize) -> bool {
+    let cpi = |i: usize| {
+        let pos = bytes::stride_pos(start, i, M2TS_PACKET_LEN)?;
+        buffer.get(pos).map(|x| x >> 6)
+    };
+    match cpi(0) {
+        Some(first) => (1..HEADERS_TO_CHECK).all(|i| cpi(i) == Some(first)),
+        None => false,
+    }
+}
+
+/// The first packet with PID 0 in the `run` packets starting at `start` starts a PAT
+/// section: payload_unit_start_indicator set and table_id 0 after the pointer field.
+fn first_pat(buffer: &[u8], start: usize, run: usize) -> bool {
+    let pat = (0..run)
+        .filter_map(|i| bytes::stride_pos(start + 4, i, M2TS_PACKET_LEN))
+        .find(|pos| ContainerType::ts_packet_pid(buffer, *pos) == Some(0));
+    match pat {
+        Some(pos) => starts_pat(buffer, pos),
+        None => false,
+    }
+}
+
+/// PAT section starting in the TS packet at `pos`.
+fn starts_pat(buffer: &[u8], pos: usize) -> bool {
+    let packet = match bytes::slice_at(buffer, pos, TS_PACKET_LEN) {
+        Some(x) => x,
+        None => return false,
+    };
+    let pusi = packet[1] & 0x40 != 0;
+    let afc = (packet[3] >> 4) & 0x3;
+    if !pusi || afc & 0x1 == 0 {
+        return false;
+    }
+    let payload = if afc & 0x2 != 0 {
+        5 + usize::from(packet[4])
+    } else {
+        4
+    };
+    let table = packet
+        .get(payload)
+        .map(|pointer| payload + 1 + usize::from(*pointer));
+    match table {
+        Some(x) => packet.get(x) == Some(&0x00),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10 M2TS packets, PID 0 with a PAT section in the first one and PID 0x1011 in the
+    /// others, with arrival timestamps from `ats`.
+    fn stream(ats: impl Fn(usize) -> u32) -> Vec<u8> {
+        let mut buffer = vec![0xFF; M2TS_PACKET_LEN * 10];
+        for (i, packet) in buffer.chunks_mut(M2TS_PACKET_LEN).enumerate() {
+            packet[..4].copy_from_slice(&ats(i).to_be_bytes());
+            packet[4..8].copy_from_slice(&[0x47, 0x50, 0x11, 0x10]);
+        }
+        buffer[4..13].copy_from_slice(&[0x47, 0x40, 0x00, 0x10, 0x00, 0x00, 0xB0, 0x0D, 0x00]);
+        buffer
+    }
+
+    #[test]
+    fn bdav() {
+        let buffer = stream(|i| 0x4000_0000 | (1800 * i as u32));
+        assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Bdav));
+        // Copy permission changes.
+        let mut changed = buffer.clone();
+        changed[M2TS_PACKET_LEN * 3] = 0x80;
+        assert_eq!(m2ts_profile(&changed), Some(M2tsProfile::Generic));
+        // Timestamps go back.
+        let buffer = stream(|i| 1000 - 10 * i as u32);
+        assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Generic));
+    }
+
+    #[test]
+    fn pat() {
+        let mut buffer = stream(|i| 1000 * i as u32);
+        // Adaptation field before the payload.
+        buffer[7] = 0x30;
+        buffer[8..16].copy_from_slice(&[0x01, 0x00, 0x00, 0x00, 0xB0, 0x0D, 0x00, 0x01]);
+        assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Bdav));
+        // PMT section on PID 0.
+        buffer[11] = 0x02;
+        assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Generic));
+        // No payload_unit_start_indicator.
+        let mut buffer = stream(|i| 1000 * i as u32);
+        buffer[5] = 0x00;
+        assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Generic));
+        // Pointer field past the end of the packet.
+        let mut buffer = stream(|i| 1000 * i as u32);
+        buffer[8] = 0xFF;
+        assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Generic));
+    }
+
+    #[test]
+    fn not_m2ts() {
+        let mut buffer = vec![0xFF; TS_PACKET_LEN * 10];
+        for packet in buffer.chunks_mut(TS_PACKET_LEN) {
+            packet[..4].copy_from_slice(&[0x47, 0x40, 0x00, 0x10]);
+        }
+        assert_eq!(m2ts_profile(&buffer), None);
+        assert_eq!(m2ts_profile(b"\x47\x40\x00\x10"), None);
+    }
+}
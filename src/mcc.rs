@@ -0,0 +1,61 @@
+//! Version of a MacCaption file, see [`mcc_version`].
+
+/// Version of a MacCaption (MCC) file, from its header line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MccVersion {
+    /// `V1.0`, 29.97 fps drop frame only
+    V1,
+    /// `V2.0`, other frame rates and the `Time Code Rate` header
+    V2,
+}
+
+/// Header line of a MacCaption file, up to the version.
+const MCC_MAGIC_BYTES: &[u8; 28] = b"File Format=MacCaption_MCC V";
+
+/// Version of the MacCaption file `buffer` starts with: after an optional UTF-8 BOM,
+/// `File Format=MacCaption_MCC V1.0` or `V2.0`, then a line break or the end of the
+/// buffer. `None` if `buffer` does not start with that line.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{mcc_version, MccVersion};
+///
+/// let header = b"File Format=MacCaption_MCC V2.0\r\n";
+/// assert_eq!(mcc_version(header), Some(MccVersion::V2));
+/// ```
+pub fn mcc_version(buffer: &[u8]) -> Option<MccVersion> {
+    let buffer = buffer.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(buffer);
+    let rest = buffer.strip_prefix(MCC_MAGIC_BYTES)?;
+    let version = match rest.get(..3)? {
+        b"1.0" => MccVersion::V1,
+        b"2.0" => MccVersion::V2,
+        _ => return None,
+    };
+    match rest.get(3) {
+        None | Some(b'\r' | b'\n') => Some(version),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions() {
+        let mut header = b"File Format=MacCaption_MCC V1.0\n".to_vec();
+        assert_eq!(mcc_version(&header), Some(MccVersion::V1));
+        header.insert(0, 0xBF);
+        assert_eq!(mcc_version(&header), None);
+        header.splice(0..1, *b"\xEF\xBB\xBF");
+        assert_eq!(mcc_version(&header), Some(MccVersion::V1));
+        assert_eq!(
+            mcc_version(b"File Format=MacCaption_MCC V2.0"),
+            Some(MccVersion::V2)
+        );
+        assert_eq!(mcc_version(b"File Format=MacCaption_MCC V3.0\n"), None);
+        assert_eq!(mcc_version(b"File Format=MacCaption_MCC V1.01\n"), None);
+        // Cut in the version.
+        assert_eq!(mcc_version(b"File Format=MacCaption_MCC V1."), None);
+    }
+}
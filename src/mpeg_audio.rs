@@ -0,0 +1,123 @@
+//! MPEG audio (Layer I, II and III) frame headers, enough to find where the next frame
+//! starts.
+
+use crate::bytes::slice_at;
+
+/// Bitrates in kbit/s by bitrate index, for MPEG-1 Layer I, II and III and for
+/// MPEG-2 and 2.5 Layer I and Layer II/III. Index 0 (free format) and 15 are left out.
+const BITRATES: [[u16; 14]; 5] = [
+    [
+        32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+    ],
+    [
+        32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+    ],
+    [
+        32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ],
+    [
+        32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+    ],
+    [8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+];
+
+/// Sampling rates of MPEG-1 by sampling rate index, MPEG-2 halves and MPEG-2.5
+/// quarters them.
+const SAMPLING_RATES: [u32; 3] = [44100, 48000, 32000];
+
+/// Version bits of MPEG-1, MPEG-2 is 2 and MPEG-2.5 is 0, 1 is reserved.
+const VERSION_1: u8 = 3;
+/// Layer bits of Layer I, Layer II is 2 and Layer III is 1, 0 is reserved.
+const LAYER_1: u8 = 3;
+
+/// Frame header of MPEG audio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FrameHeader {
+    /// Version, layer and sampling rate index, the same for every frame of a stream.
+    pub(crate) stream: (u8, u8, u8),
+    /// Length of the frame, header included.
+    pub(crate) len: usize,
+}
+
+/// Frame header at `pos`. `None` without the 11 bit sync word, with a reserved
+/// version, layer, sampling rate or emphasis, or a free format or invalid bitrate.
+pub(crate) fn frame_header_at(buffer: &[u8], pos: usize) -> Option<FrameHeader> {
+    let x = slice_at(buffer, pos, 4)?;
+    let version = x[1] >> 3 & 0x03;
+    let layer = x[1] >> 1 & 0x03;
+    let bitrate = usize::from(x[2] >> 4);
+    let sampling = x[2] >> 2 & 0x03;
+    let padding = usize::from(x[2] >> 1 & 0x01);
+    if x[0] != 0xFF
+        || x[1] & 0xE0 != 0xE0
+        || version == 1
+        || layer == 0
+        || !(1..=14).contains(&bitrate)
+        || sampling == 3
+        || x[3] & 0x03 == 2
+    {
+        return None;
+    }
+
+    let table = match (version == VERSION_1, layer) {
+        (true, _) => usize::from(LAYER_1 - layer),
+        (false, LAYER_1) => 3,
+        (false, _) => 4,
+    };
+    let bitrate = usize::from(BITRATES[table][bitrate - 1]) * 1000;
+    let shift = match version {
+        VERSION_1 => 0,
+        2 => 1,
+        _ => 2,
+    };
+    let sampling_rate = (SAMPLING_RATES[usize::from(sampling)] >> shift) as usize;
+    let len = match layer {
+        LAYER_1 => (12 * bitrate / sampling_rate + padding) * 4,
+        // Layer III frames of MPEG-2 and 2.5 have half the samples.
+        1 if version != VERSION_1 => 72 * bitrate / sampling_rate + padding,
+        _ => 144 * bitrate / sampling_rate + padding,
+    };
+    Some(FrameHeader {
+        stream: (version, layer, sampling),
+        len,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_len() {
+        let len = |x: [u8; 4]| frame_header_at(&x, 0).unwrap().len;
+        // MPEG-1 Layer III, 128 kbit/s, 44.1 kHz, then padded.
+        assert_eq!(len([0xFF, 0xFB, 0x90, 0x64]), 417);
+        assert_eq!(len([0xFF, 0xFB, 0x92, 0x64]), 418);
+        // MPEG-2 Layer III, 64 kbit/s, 22.05 kHz.
+        assert_eq!(len([0xFF, 0xF3, 0x80, 0xC4]), 208);
+        // MPEG-1 Layer II, 192 kbit/s, 48 kHz.
+        assert_eq!(len([0xFF, 0xFD, 0xA4, 0x04]), 576);
+        // MPEG-1 Layer I, 384 kbit/s, 44.1 kHz.
+        assert_eq!(len([0xFF, 0xFF, 0xC0, 0x00]), 416);
+    }
+
+    #[test]
+    fn reserved() {
+        let good = [0xFF, 0xFB, 0x90, 0x64];
+        assert!(frame_header_at(&good[..3], 0).is_none());
+        for (pos, byte) in [
+            // Version 1, layer 0 (ADTS), free format and bad bitrate, sampling rate 3,
+            // emphasis 2.
+            (1, 0xEB),
+            (1, 0xF9),
+            (2, 0x00),
+            (2, 0xF0),
+            (2, 0x9C),
+            (3, 0x66),
+        ] {
+            let mut t = good;
+            t[pos] = byte;
+            assert!(frame_header_at(&t, 0).is_none(), "{:x?}", t);
+        }
+    }
+}
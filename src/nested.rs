@@ -0,0 +1,141 @@
+//! Containers wrapped in other containers, see [`detect_nested`].
+
+use crate::bytes::{be_u32_at, slice_at, stride_pos};
+use crate::{ContainerType, Detected, Detector};
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+/// Most layers `detect_nested` reports.
+pub(crate) const MAX_DEPTH: usize = 4;
+
+/// Layers of containers in `buffer`, outermost first, with the default detector.
+/// Empty if the outer layer is not identified.
+///
+/// The payload of a layer is detected again where its framing is cheap to strip:
+/// - TiVo: the data at the MPEG offset of the TiVo header.
+/// - M2TS: the packets without their 4 byte timestamp prefixes.
+/// - Custom formats with a `payload_offset`, see `Detector::detect_nested`.
+///
+/// At most 4 layers are reported.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{detect_nested, ContainerType};
+///
+/// let mut buf = b"TiVo\x00\x00\x00\x00\x00\x00\x00\x00\x00\x10\x00\x00".to_vec();
+/// buf.extend_from_slice(&[0x00, 0x00, 0x01, 0xBA, 0x44, 0x00, 0x04, 0x00, 0x04, 0x01]);
+/// assert_eq!(detect_nested(&buf), [ContainerType::TivoPS, ContainerType::PS]);
+/// ```
+pub fn detect_nested(buffer: &[u8]) -> Vec<ContainerType> {
+    Detector::default().detect_nested(buffer)
+}
+
+/// Payload of `layer`, which `buffer` was identified as.
+/// `None` if it has no wrapped stream or it cannot be stripped cheaply.
+pub(crate) fn payload<'a>(
+    layer: &Detected,
+    buffer: &'a [u8],
+    ts_sync_threshold: usize,
+) -> Option<Cow<'a, [u8]>> {
+    match layer {
+        Detected::Container(ContainerType::TivoPS) => tivo(buffer).map(Cow::Borrowed),
+        Detected::Container(ContainerType::M2TS) => m2ts(buffer, ts_sync_threshold).map(Cow::Owned),
+        Detected::Container(_) => None,
+        Detected::Custom(format) => buffer.get(format.payload_start()?..).map(Cow::Borrowed),
+    }
+}
+
+/// Data after the TiVo header, whose big endian u32 at offset 10 is where the MPEG
+/// stream starts.
+fn tivo(buffer: &[u8]) -> Option<&[u8]> {
+    let offset = usize::try_from(be_u32_at(buffer, 10)?).ok()?;
+    buffer.get(offset..).filter(|x| !x.is_empty())
+}
+
+/// The TS packets of an M2TS stream, from its first whole packet on.
+fn m2ts(buffer: &[u8], threshold: usize) -> Option<Vec<u8>> {
+    const M2TS_PACKET_LEN: usize = 192;
+
+    let (start, run) = ContainerType::ts_best_run(buffer, 4, M2TS_PACKET_LEN, threshold);
+    if run == 0 {
+        return None;
+    }
+    let mut packets = Vec::with_capacity(buffer.len());
+    for i in 0.. {
+        let packet = stride_pos(start + 4, i, M2TS_PACKET_LEN)
+            .and_then(|pos| slice_at(buffer, pos, M2TS_PACKET_LEN - 4));
+        match packet {
+            Some(packet) => packets.extend_from_slice(packet),
+            None => break,
+        }
+    }
+    Some(packets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samples::generate_sample;
+    use crate::CustomFormat;
+
+    #[test]
+    fn tivo_ps() {
+        let mut buffer = b"TiVo".to_vec();
+        buffer.extend_from_slice(&[0; 6]);
+        buffer.extend_from_slice(&32u32.to_be_bytes());
+        buffer.resize(32, 0xFF);
+        buffer.extend(generate_sample(ContainerType::PS));
+        assert_eq!(
+            detect_nested(&buffer),
+            [ContainerType::TivoPS, ContainerType::PS]
+        );
+
+        // MPEG offset past the end.
+        buffer[10..14].copy_from_slice(&1000u32.to_be_bytes());
+        assert_eq!(detect_nested(&buffer), [ContainerType::TivoPS]);
+    }
+
+    #[test]
+    fn m2ts() {
+        let buffer = generate_sample(ContainerType::M2TS);
+        assert_eq!(
+            detect_nested(&buffer),
+            [ContainerType::M2TS, ContainerType::TS]
+        );
+        // Starting mid packet.
+        assert_eq!(
+            detect_nested(&buffer[100..]),
+            [ContainerType::M2TS, ContainerType::TS]
+        );
+        assert_eq!(
+            detect_nested(&generate_sample(ContainerType::TS)),
+            [ContainerType::TS]
+        );
+        assert!(detect_nested(&[0x42; 64]).is_empty());
+    }
+
+    #[test]
+    fn custom() {
+        let dvr = CustomFormat::new("dvr").rule(0, b"DVR1").payload_offset(64);
+        let detector = Detector::builder().custom_format(dvr).build();
+        let mut buffer = b"DVR1".to_vec();
+        buffer.resize(64, 0);
+        buffer.extend(generate_sample(ContainerType::TS));
+
+        let names: Vec<_> = detector
+            .detect_nested_named(&buffer)
+            .iter()
+            .map(|x| x.name().to_owned())
+            .collect();
+        assert_eq!(names, ["dvr", "ts"]);
+        assert_eq!(detector.detect_nested(&buffer), [ContainerType::TS]);
+    }
+
+    #[test]
+    fn depth() {
+        // A custom format wrapping itself.
+        let nest = CustomFormat::new("nest").rule(0, b"N").payload_offset(1);
+        let detector = Detector::builder().custom_format(nest).build();
+        assert_eq!(detector.detect_nested_named(&[b'N'; 16]).len(), MAX_DEPTH);
+    }
+}
@@ -0,0 +1,108 @@
+//! Node.js bindings, built with napi-rs.
+//!
+//! Containers are returned by their short name (`ContainerType::short_name`), `null`
+//! when nothing matched. I/O failures throw an `Error` with one of the `code`s below.
+
+use crate::{ContainerType, Detector, Error};
+use napi::bindgen_prelude::{AsyncTask, Buffer};
+use napi::{Env, JsError, Task};
+use napi_derive::napi;
+use std::path::PathBuf;
+
+/// Error thrown to JS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    code: &'static str,
+    message: String,
+}
+
+impl Failure {
+    fn into_napi(self, env: Env) -> napi::Error {
+        let error = napi::Error::new(self.code, self.message);
+        napi::Error::from(JsError::from(error).into_unknown(env))
+    }
+}
+
+/// What the JS side sees for a detection result.
+fn outcome(result: Result<ContainerType, Error>) -> Result<Option<&'static str>, Failure> {
+    let code = match &result {
+        Ok(kind) => return Ok(Some(kind.short_name())),
+        Err(Error::Unidentified) | Err(Error::NotMedia(_)) | Err(Error::Ambiguous(_)) => {
+            return Ok(None)
+        }
+        Err(Error::Open { .. }) => "ERR_MEDIA_INFER_OPEN",
+        Err(Error::Read(_)) => "ERR_MEDIA_INFER_READ",
+        Err(Error::IsADirectory { .. }) => "ERR_MEDIA_INFER_IS_DIRECTORY",
+        Err(Error::EmptyFile) => "ERR_MEDIA_INFER_EMPTY_FILE",
+        Err(Error::FileTooSmall { .. }) => "ERR_MEDIA_INFER_FILE_TOO_SMALL",
+        Err(Error::DuplicatePriority(_)) | Err(Error::InvalidDefinitions { .. }) => {
+            "ERR_MEDIA_INFER_INVALID_OPTIONS"
+        }
+    };
+    Err(Failure {
+        code,
+        message: result.unwrap_err().to_string(),
+    })
+}
+
+/// `detectBytes(Buffer): string | null`
+#[napi]
+pub fn detect_bytes(bytes: Buffer) -> Option<&'static str> {
+    outcome(Detector::default().detect(&bytes)).unwrap_or(None)
+}
+
+/// Probes a file on the libuv thread pool.
+pub struct DetectFile {
+    path: PathBuf,
+}
+
+impl Task for DetectFile {
+    type Output = Result<Option<&'static str>, Failure>;
+    type JsValue = Option<&'static str>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        Ok(outcome(ContainerType::from_file_path(&self.path)))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        output.map_err(|x| x.into_napi(env))
+    }
+}
+
+/// `detectFile(path): Promise<string | null>`
+#[napi(ts_return_type = "Promise<string | null>")]
+pub fn detect_file(path: String) -> AsyncTask<DetectFile> {
+    AsyncTask::new(DetectFile {
+        path: PathBuf::from(path),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn outcome() {
+        assert_eq!(
+            super::outcome(Ok(ContainerType::TivoPS)),
+            Ok(Some("tivops"))
+        );
+        assert_eq!(super::outcome(Err(Error::Unidentified)), Ok(None));
+        assert_eq!(
+            super::outcome(Err(Error::NotMedia("PDF document"))),
+            Ok(None)
+        );
+
+        let t = super::outcome(Err(Error::Open {
+            path: PathBuf::from("x.ts"),
+            source: io::Error::from(io::ErrorKind::NotFound),
+        }));
+        let t = t.unwrap_err();
+        assert_eq!(t.code, "ERR_MEDIA_INFER_OPEN");
+        assert!(t.message.starts_with("failed to open x.ts: "));
+
+        let t = super::outcome(Err(Error::EmptyFile)).unwrap_err();
+        assert_eq!(t.code, "ERR_MEDIA_INFER_EMPTY_FILE");
+    }
+}
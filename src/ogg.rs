@@ -0,0 +1,143 @@
+//! Codec of the first logical stream of an Ogg file, see [`ogg_codec`].
+
+use crate::bytes::slice_at;
+use crate::ContainerType;
+
+/// Codec of an Ogg logical stream, from the identification header in its first packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OggCodec {
+    /// Vorbis audio
+    Vorbis,
+    /// Opus audio
+    Opus,
+    /// Theora video
+    Theora,
+    /// FLAC audio, Ogg mapping
+    Flac,
+    /// Speex audio
+    Speex,
+}
+
+/// Start of the identification header of each codec.
+const ID_HEADERS: [(&[u8], OggCodec); 5] = [
+    (b"\x01vorbis", OggCodec::Vorbis),
+    (b"OpusHead", OggCodec::Opus),
+    (b"\x80theora", OggCodec::Theora),
+    (b"\x7FFLAC", OggCodec::Flac),
+    (b"Speex   ", OggCodec::Speex),
+];
+
+/// Size of an Ogg page header without its segment table.
+const PAGE_HEADER_LEN: usize = 27;
+
+/// Codec of the first logical stream, from the first packet of the page `buffer`
+/// starts with. `None` if `buffer` does not start with an Ogg page, the page does not
+/// start a packet, or the codec is not one of `OggCodec`.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{ogg_codec, OggCodec};
+///
+/// let mut page = b"OggS\x00\x02".to_vec();
+/// page.extend_from_slice(&[0; 20]);
+/// page.extend_from_slice(&[1, 19]);
+/// page.extend_from_slice(b"OpusHead\x01\x02\x38\x01\x80\xbb\x00\x00\x00\x00\x00");
+/// assert_eq!(ogg_codec(&page), Some(OggCodec::Opus));
+/// ```
+pub fn ogg_codec(buffer: &[u8]) -> Option<OggCodec> {
+    const CONTINUED: u8 = 0x01;
+
+    if !ContainerType::check_ogg(buffer, true) || buffer[5] & CONTINUED != 0 {
+        return None;
+    }
+    let segments = usize::from(*buffer.get(PAGE_HEADER_LEN - 1)?);
+    let table = slice_at(buffer, PAGE_HEADER_LEN, segments)?;
+    // Lacing values up to and including the first one below 255 make up the packet.
+    let end = table
+        .iter()
+        .position(|x| *x < 255)
+        .map_or(segments, |x| x + 1);
+    let packet_len: usize = table[..end].iter().map(|x| usize::from(*x)).sum();
+    let packet = buffer.get(PAGE_HEADER_LEN + segments..)?;
+    let packet = &packet[..packet.len().min(packet_len)];
+    ID_HEADERS
+        .iter()
+        .find(|x| packet.starts_with(x.0))
+        .map(|x| x.1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// BOS page with one packet, `packet`.
+    fn page(packet: &[u8]) -> Vec<u8> {
+        let mut out = b"OggS\x00\x02".to_vec();
+        out.extend_from_slice(&[0; 20]);
+        let mut len = packet.len();
+        let mut table = Vec::new();
+        while len >= 255 {
+            table.push(255);
+            len -= 255;
+        }
+        table.push(len as u8);
+        out.push(table.len() as u8);
+        out.extend(table);
+        out.extend_from_slice(packet);
+        out
+    }
+
+    #[test]
+    fn opus() {
+        // Version 1, stereo, pre-skip 312, 48 kHz, gain 0, mapping family 0.
+        let head = b"OpusHead\x01\x02\x38\x01\x80\xbb\x00\x00\x00\x00\x00";
+        assert_eq!(ogg_codec(&page(head)), Some(OggCodec::Opus));
+    }
+
+    #[test]
+    fn vorbis() {
+        // Version 0, stereo, 44.1 kHz, bitrates, block sizes, framing bit.
+        let mut head = b"\x01vorbis\x00\x00\x00\x00\x02\x44\xac\x00\x00".to_vec();
+        head.extend_from_slice(&[0, 0, 0, 0, 0x00, 0xe2, 0x04, 0, 0, 0, 0, 0, 0xb8, 0x01]);
+        assert_eq!(ogg_codec(&page(&head)), Some(OggCodec::Vorbis));
+    }
+
+    #[test]
+    fn others() {
+        assert_eq!(
+            ogg_codec(&page(b"\x80theora\x03\x02")),
+            Some(OggCodec::Theora)
+        );
+        assert_eq!(ogg_codec(&page(b"\x7FFLAC\x01\x00")), Some(OggCodec::Flac));
+        assert_eq!(ogg_codec(&page(b"Speex   1.2")), Some(OggCodec::Speex));
+        assert_eq!(ogg_codec(&page(b"fishead\x00")), None);
+        // Long first packet, spread over several lacing values.
+        let mut long = b"OpusTags".to_vec();
+        long.resize(600, 0);
+        assert_eq!(ogg_codec(&page(&long)), None);
+        long[..8].copy_from_slice(b"OpusHead");
+        assert_eq!(ogg_codec(&page(&long)), Some(OggCodec::Opus));
+    }
+
+    #[test]
+    fn malformed() {
+        let good = page(b"OpusHead\x01\x02");
+        // Every truncation before the end of the magic, including inside the segment
+        // table.
+        for len in 0..PAGE_HEADER_LEN + 1 + 8 {
+            assert_eq!(ogg_codec(&good[..len]), None, "{}", len);
+        }
+        // Segment table longer than the buffer.
+        let mut t = good.clone();
+        t[26] = 255;
+        assert_eq!(ogg_codec(&t), None);
+        // First packet shorter than the codec magic.
+        let mut t = good.clone();
+        t[27] = 4;
+        assert_eq!(ogg_codec(&t), None);
+        // Continued packet.
+        let mut t = good;
+        t[5] = 0x03;
+        assert_eq!(ogg_codec(&t), None);
+    }
+}
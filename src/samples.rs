@@ -0,0 +1,777 @@
+//! Minimal valid headers for every container, for tests and fixtures.
+//!
+//! Only built with the `samples` feature (and in the crate's own tests).
+
+use crate::ContainerType;
+
+/// Smallest buffer the check for `kind` accepts, built as a real, if tiny, header of
+/// that container.
+pub fn generate_sample(kind: ContainerType) -> Vec<u8> {
+    match kind {
+        ContainerType::ASF => asf(),
+        ContainerType::MKV => ebml_header(b"matroska"),
+        ContainerType::WEBM => ebml_header(b"webm"),
+        ContainerType::MKA => mka(),
+        ContainerType::MOV => mov(),
+        ContainerType::ThreeGP => ftyp(b"3gp4", b"isom3gp4"),
+        ContainerType::ThreeG2 => ftyp(b"3g2a", b"3g2amp42"),
+        ContainerType::M4A => ftyp(b"M4A ", b"M4A mp42isom"),
+        ContainerType::FragmentedMP4 => fmp4(),
+        ContainerType::HEIF => ftyp(b"heic", b"mif1heic"),
+        ContainerType::AVIF => ftyp(b"avif", b"avifmif1miaf"),
+        ContainerType::AVIFSequence => ftyp(b"avis", b"avismsf1miafavif"),
+        ContainerType::GXF => gxf(),
+        ContainerType::WTV => wtv(),
+        ContainerType::RCWT => rcwt(),
+        ContainerType::MP4 => ftyp(b"isom", b"isommp41"),
+        ContainerType::TS => ts_packets(0, TS_PACKETS),
+        ContainerType::M2TS => m2ts(),
+        ContainerType::PS => ps(),
+        ContainerType::MXF => mxf(),
+        ContainerType::TivoPS => b"TiVo".to_vec(),
+        ContainerType::ES => es(),
+        ContainerType::AVI => avi(),
+        ContainerType::WAV => wav(),
+        ContainerType::FLV => flv(),
+        ContainerType::RealMedia => rm(),
+        ContainerType::IVF => ivf(),
+        ContainerType::Y4M => b"YUV4MPEG2 W352 H288 F30:1 Ip A1:1 C420jpeg\n".to_vec(),
+        ContainerType::OGG => ogg(),
+        ContainerType::McPoodlesRaw => mcpoodles_raw(),
+        ContainerType::H264ES => h264_es(),
+        ContainerType::H265ES => h265_es(),
+        ContainerType::MPEG4ES => mpeg4_es(),
+        ContainerType::AV1OBU => av1_obu(),
+        ContainerType::AACADTS => aac_adts(),
+        ContainerType::AACLATM => aac_latm(),
+        ContainerType::MP3 => mp3(),
+        ContainerType::FLAC => flac(),
+        ContainerType::AC3 => ac3(),
+        ContainerType::EAC3 => eac3(),
+        ContainerType::DTS => dts(),
+        ContainerType::DTSHD => dtshd(),
+        ContainerType::AIFF => aiff(),
+        ContainerType::AU => au(),
+        ContainerType::CAF => caf(),
+        ContainerType::WavPack => wavpack(),
+        ContainerType::APE => ape(),
+        ContainerType::Musepack => musepack(),
+        ContainerType::TTA => tta(),
+        ContainerType::Shorten => shorten(),
+        ContainerType::MIDI => midi(),
+        ContainerType::DSF => dsf(),
+        ContainerType::DFF => dff(),
+        ContainerType::RF64 => rf64(),
+        ContainerType::W64 => w64(),
+        ContainerType::TrackerModule => tracker_module(),
+        ContainerType::WebVTT => webvtt(),
+        ContainerType::SCC => scc(),
+        ContainerType::MCC => mcc(),
+        ContainerType::TTML => ttml(),
+        ContainerType::EbuStl => ebu_stl(),
+        ContainerType::SAMI => sami(),
+        ContainerType::MicroDVD => microdvd(),
+        ContainerType::SubViewer => subviewer(),
+        ContainerType::LRC => lrc(),
+        ContainerType::PGS => pgs(),
+        ContainerType::VobSubIdx => vobsub_idx(),
+        ContainerType::VobSubPS => vobsub_ps(),
+        ContainerType::CheetahCAP => cheetah_cap(),
+        ContainerType::PAC => pac(),
+        ContainerType::SpruceSTL => spruce_stl(),
+        ContainerType::CDXA => cdxa(),
+        ContainerType::VOB => vob(),
+        ContainerType::AMR => b"#!AMR\n".to_vec(),
+        ContainerType::AMRWB => b"#!AMR-WB\n".to_vec(),
+        ContainerType::TrueHD => truehd(),
+    }
+}
+
+/// Packets in the TS and M2TS samples, enough for the default sync threshold.
+const TS_PACKETS: usize = 8;
+
+/// ASF Header Object with no child objects.
+fn asf() -> Vec<u8> {
+    let mut out = vec![
+        0x30, 0x26, 0xb2, 0x75, 0x8e, 0x66, 0xcf, 0x11, 0xa6, 0xd9, 0x00, 0xaa, 0x00, 0x62, 0xce,
+        0x6c,
+    ];
+    out.extend_from_slice(&30u64.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+    // Reserved1 and Reserved2.
+    out.extend_from_slice(&[0x01, 0x02]);
+    out
+}
+
+/// EBML header of a Matroska or WebM file.
+fn ebml_header(doc_type: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    // EBMLVersion, EBMLReadVersion, EBMLMaxIDLength, EBMLMaxSizeLength.
+    body.extend_from_slice(&[0x42, 0x86, 0x81, 0x01, 0x42, 0xF7, 0x81, 0x01]);
+    body.extend_from_slice(&[0x42, 0xF2, 0x81, 0x04, 0x42, 0xF3, 0x81, 0x08]);
+    // DocType, DocTypeVersion, DocTypeReadVersion.
+    body.extend_from_slice(&[0x42, 0x82, 0x80 | doc_type.len() as u8]);
+    body.extend_from_slice(doc_type);
+    body.extend_from_slice(&[0x42, 0x87, 0x81, 0x04, 0x42, 0x85, 0x81, 0x02]);
+
+    let mut out = vec![0x1a, 0x45, 0xdf, 0xa3, 0x80 | body.len() as u8];
+    out.extend(body);
+    out
+}
+
+/// Matroska EBML header, then a Segment of unknown size holding a single audio track.
+fn mka() -> Vec<u8> {
+    let mut out = ebml_header(b"matroska");
+    out.extend_from_slice(&[0x18, 0x53, 0x80, 0x67, 0xff]);
+    // Tracks, with a TrackEntry of TrackNumber 1 and TrackType 2 (audio).
+    out.extend_from_slice(&[0x16, 0x54, 0xae, 0x6b, 0x88]);
+    out.extend_from_slice(&[0xae, 0x86, 0xd7, 0x81, 0x01, 0x83, 0x81, 0x02]);
+    out
+}
+
+/// GXF packet header of a MAP packet with no payload.
+fn gxf() -> Vec<u8> {
+    let mut out = vec![0, 0, 0, 0, 1, 0xbc];
+    out.extend_from_slice(&16u32.to_be_bytes());
+    out.extend_from_slice(&[0, 0, 0, 0, 0xe1, 0xe2]);
+    out
+}
+
+/// WTV header GUID.
+fn wtv() -> Vec<u8> {
+    vec![
+        0xb7, 0xd8, 0x00, 0x20, 0x37, 0x49, 0xda, 0x11, 0xa6, 0x4e, 0x00, 0x07, 0xe9, 0x5e, 0xad,
+        0x8d,
+    ]
+}
+
+/// RCWT header written by CCExtractor 0.80, file format version 1.
+fn rcwt() -> Vec<u8> {
+    vec![0xCC, 0xCC, 0xED, 0xCC, 0x00, 0x50, 0x00, 0x01, 0, 0, 0]
+}
+
+/// `ftyp` box with the QuickTime major brand.
+fn mov() -> Vec<u8> {
+    let mut out = 20u32.to_be_bytes().to_vec();
+    out.extend_from_slice(b"ftypqt  ");
+    out.extend_from_slice(&0x2005_0300u32.to_be_bytes());
+    out.extend_from_slice(b"qt  ");
+    out
+}
+
+/// `ftyp` box with the `major` brand and `compatible` brands.
+fn ftyp(major: &[u8; 4], compatible: &[u8]) -> Vec<u8> {
+    let mut out = (16 + compatible.len() as u32).to_be_bytes().to_vec();
+    out.extend_from_slice(b"ftyp");
+    out.extend_from_slice(major);
+    out.extend_from_slice(&0x200u32.to_be_bytes());
+    out.extend_from_slice(compatible);
+    out
+}
+
+/// `styp` box of a DASH media segment, then a `moof` holding only its `mfhd`.
+fn fmp4() -> Vec<u8> {
+    let mut out = 24u32.to_be_bytes().to_vec();
+    out.extend_from_slice(b"stypmsdh");
+    out.extend_from_slice(&0u32.to_be_bytes());
+    out.extend_from_slice(b"msdhmsix");
+    out.extend_from_slice(&24u32.to_be_bytes());
+    out.extend_from_slice(b"moof");
+    out.extend_from_slice(&16u32.to_be_bytes());
+    out.extend_from_slice(b"mfhd");
+    // Version and flags, sequence number.
+    out.extend_from_slice(&0u32.to_be_bytes());
+    out.extend_from_slice(&1u32.to_be_bytes());
+    out
+}
+
+/// `count` TS packets carrying PID 0 (the PAT), each behind `prefix` bytes.
+fn ts_packets(prefix: usize, count: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    for i in 0..count {
+        out.resize(out.len() + prefix, 0);
+        // Payload only, continuity counter counting up.
+        out.extend_from_slice(&[0x47, 0x40, 0x00, 0x10 | i as u8]);
+        out.resize(out.len() + 184, 0xFF);
+    }
+    out
+}
+
+/// TS packets behind 4 byte arrival timestamps, ascending.
+fn m2ts() -> Vec<u8> {
+    let mut out = ts_packets(4, TS_PACKETS);
+    for i in 0..TS_PACKETS {
+        out[i * 192..i * 192 + 4].copy_from_slice(&(1000 * i as u32).to_be_bytes());
+    }
+    out
+}
+
+/// MPEG-2 pack header followed by the program end code.
+fn ps() -> Vec<u8> {
+    vec![
+        0x00, 0x00, 0x01, 0xBA, 0x44, 0x00, 0x04, 0x00, 0x04, 0x01, 0x01, 0x89, 0xC3, 0xF8, 0x00,
+        0x00, 0x01, 0xB9,
+    ]
+}
+
+/// Closed and complete header partition pack, OP1a, no essence containers.
+fn mxf() -> Vec<u8> {
+    let mut out = vec![
+        0x06, 0x0e, 0x2b, 0x34, 0x02, 0x05, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01, 0x02, 0x04,
+        0x00,
+    ];
+    let mut value = Vec::new();
+    // Major and minor version, KAG size.
+    value.extend_from_slice(&[0x00, 0x01, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+    // This, previous and footer partition, header and index byte count.
+    value.resize(value.len() + 8 * 5, 0);
+    // IndexSID, BodyOffset, BodySID.
+    value.resize(value.len() + 4 + 8 + 4, 0);
+    value.extend_from_slice(&[
+        0x06, 0x0e, 0x2b, 0x34, 0x04, 0x01, 0x01, 0x01, 0x0d, 0x01, 0x02, 0x01, 0x01, 0x01, 0x09,
+        0x00,
+    ]);
+    // Empty batch of essence container labels.
+    value.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 16]);
+
+    out.extend_from_slice(&[0x83, 0x00, 0x00, value.len() as u8]);
+    out.extend(value);
+    out
+}
+
+/// MPEG-2 video sequence header, 720x480 4:3 at 29.97 fps.
+/// RIFF AVI header, with a `hdrl` list holding an empty main AVI header.
+fn avi() -> Vec<u8> {
+    let mut hdrl = b"hdrlavih".to_vec();
+    hdrl.extend_from_slice(&56u32.to_le_bytes());
+    hdrl.extend_from_slice(&[0; 56]);
+    let mut body = b"AVI LIST".to_vec();
+    body.extend_from_slice(&(hdrl.len() as u32).to_le_bytes());
+    body.extend(hdrl);
+    let mut out = b"RIFF".to_vec();
+    out.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    out.extend(body);
+    out
+}
+
+/// RIFF WAVE header of an empty 16 bit stereo 48 kHz PCM file.
+fn wav() -> Vec<u8> {
+    let mut out = b"RIFF".to_vec();
+    out.extend_from_slice(&36u32.to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    // PCM, channels, sample rate, byte rate, block align, bits per sample.
+    out.extend_from_slice(&[0x01, 0x00, 0x02, 0x00]);
+    out.extend_from_slice(&48000u32.to_le_bytes());
+    out.extend_from_slice(&192000u32.to_le_bytes());
+    out.extend_from_slice(&[0x04, 0x00, 0x10, 0x00]);
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out
+}
+
+/// RF64 header with the `ds64` chunk of an empty file and no chunk table.
+fn rf64() -> Vec<u8> {
+    let mut out = b"RF64".to_vec();
+    out.extend_from_slice(&u32::MAX.to_le_bytes());
+    out.extend_from_slice(b"WAVEds64");
+    out.extend_from_slice(&28u32.to_le_bytes());
+    // RIFF size, data size, sample count, table length.
+    for x in [4u64 + 36, 0, 0] {
+        out.extend_from_slice(&x.to_le_bytes());
+    }
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out
+}
+
+/// Wave64 `riff` chunk of an empty file: GUID, size, then the `wave` GUID.
+fn w64() -> Vec<u8> {
+    let mut out = vec![
+        0x72, 0x69, 0x66, 0x66, 0x2E, 0x91, 0xCF, 0x11, 0xA5, 0xD6, 0x28, 0xDB, 0x04, 0xC1, 0x00,
+        0x00,
+    ];
+    out.extend_from_slice(&40u64.to_le_bytes());
+    out.extend_from_slice(&[
+        0x77, 0x61, 0x76, 0x65, 0xF3, 0xAC, 0xD3, 0x11, 0x8C, 0xD1, 0x00, 0xC0, 0x4F, 0x8E, 0xDB,
+        0x8A,
+    ]);
+    out
+}
+
+/// AIFF header with the COMM chunk of an empty 16 bit stereo stream at 44.1 kHz.
+fn aiff() -> Vec<u8> {
+    let mut out = b"FORM".to_vec();
+    out.extend_from_slice(&30u32.to_be_bytes());
+    out.extend_from_slice(b"AIFFCOMM");
+    out.extend_from_slice(&18u32.to_be_bytes());
+    // Channels, sample frames, sample size, then the sample rate as an 80 bit float.
+    out.extend_from_slice(&[0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10]);
+    out.extend_from_slice(&[0x40, 0x0E, 0xAC, 0x44, 0, 0, 0, 0, 0, 0]);
+    out
+}
+
+/// AU header of an 8 kHz mono μ-law stream of unknown length.
+fn au() -> Vec<u8> {
+    let mut out = b".snd".to_vec();
+    for x in [24, u32::MAX, 1, 8000, 1] {
+        out.extend_from_slice(&x.to_be_bytes());
+    }
+    out
+}
+
+/// CAF header and the `desc` chunk of 16 bit stereo PCM at 44.1 kHz.
+fn caf() -> Vec<u8> {
+    let mut out = b"caff\x00\x01\x00\x00desc".to_vec();
+    out.extend_from_slice(&32u64.to_be_bytes());
+    out.extend_from_slice(&44100f64.to_be_bytes());
+    out.extend_from_slice(b"lpcm");
+    // Flags (signed integer), bytes per packet, frames per packet, channels, bits.
+    for x in [0x0Cu32, 4, 1, 2, 16] {
+        out.extend_from_slice(&x.to_be_bytes());
+    }
+    out
+}
+
+/// Header of an empty WavPack 4.16 block, 16 bit stereo at 44.1 kHz.
+fn wavpack() -> Vec<u8> {
+    let mut out = b"wvpk".to_vec();
+    out.extend_from_slice(&24u32.to_le_bytes());
+    out.extend_from_slice(&0x410u16.to_le_bytes());
+    // Block index and total samples upper bytes, total samples, block index, block
+    // samples, flags, CRC.
+    out.extend_from_slice(&[0, 0]);
+    for x in [0u32, 0, 0, 0x0480_1801, 0xFFFF_FFFF] {
+        out.extend_from_slice(&x.to_le_bytes());
+    }
+    out
+}
+
+/// Monkey's Audio 3.99 descriptor, without the header following it.
+fn ape() -> Vec<u8> {
+    let mut out = b"MAC ".to_vec();
+    out.extend_from_slice(&3990u16.to_le_bytes());
+    out.extend_from_slice(&[0, 0]);
+    // Descriptor and header lengths, then empty seek table, WAV header, frame data and
+    // terminating data.
+    for x in [52u32, 24, 0, 0, 0, 0, 0, 0] {
+        out.extend_from_slice(&x.to_le_bytes());
+    }
+    out.extend_from_slice(&[0; 16]);
+    out
+}
+
+/// Musepack SV8 magic and stream header packet: key, size, CRC, stream version 8,
+/// no samples, no leading silence, 44.1 kHz with 32 bands, stereo with 64 frames per
+/// block.
+fn musepack() -> Vec<u8> {
+    let mut out = b"MPCKSH".to_vec();
+    out.push(12);
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(&[8, 0, 0, 0x1F, 0x1B]);
+    out
+}
+
+/// True Audio header: PCM, stereo, 16 bit, 44.1 kHz, no samples, CRC.
+fn tta() -> Vec<u8> {
+    let mut out = b"TTA1".to_vec();
+    for x in [1u16, 2, 16] {
+        out.extend_from_slice(&x.to_le_bytes());
+    }
+    for x in [44100u32, 0, 0] {
+        out.extend_from_slice(&x.to_le_bytes());
+    }
+    out
+}
+
+/// Shorten magic and version 2, without the bit stream following it.
+fn shorten() -> Vec<u8> {
+    let mut out = b"ajkg".to_vec();
+    out.push(2);
+    out
+}
+
+/// MIDI header chunk: format 0, one track, 96 ticks per quarter note, then an empty
+/// track chunk.
+fn midi() -> Vec<u8> {
+    let mut out = b"MThd".to_vec();
+    out.extend_from_slice(&6u32.to_be_bytes());
+    for x in [0u16, 1, 96] {
+        out.extend_from_slice(&x.to_be_bytes());
+    }
+    out.extend_from_slice(b"MTrk");
+    out.extend_from_slice(&4u32.to_be_bytes());
+    // End of track meta event.
+    out.extend_from_slice(&[0x00, 0xFF, 0x2F, 0x00]);
+    out
+}
+
+/// DSF `DSD ` chunk of an empty file and the start of its `fmt ` chunk.
+fn dsf() -> Vec<u8> {
+    let mut out = b"DSD ".to_vec();
+    // Chunk size, file size, no metadata.
+    for x in [28u64, 28 + 52 + 12, 0] {
+        out.extend_from_slice(&x.to_le_bytes());
+    }
+    out.extend_from_slice(b"fmt ");
+    out.extend_from_slice(&52u64.to_le_bytes());
+    out
+}
+
+/// DSDIFF form of an empty file, with its format version chunk.
+fn dff() -> Vec<u8> {
+    let mut out = b"FRM8".to_vec();
+    out.extend_from_slice(&(4u64 + 16).to_be_bytes());
+    out.extend_from_slice(b"DSD FVER");
+    out.extend_from_slice(&4u64.to_be_bytes());
+    // Version 1.5.0.0.
+    out.extend_from_slice(&[1, 5, 0, 0]);
+    out
+}
+
+/// Impulse Tracker header of an untitled module, without the counts and tables
+/// following the title.
+fn tracker_module() -> Vec<u8> {
+    let mut out = b"IMPM".to_vec();
+    out.extend_from_slice(&[0; 26]);
+    out
+}
+
+/// WebVTT file with one cue.
+fn webvtt() -> Vec<u8> {
+    b"WEBVTT\n\n00:00.000 --> 00:01.000\nmedia_infer\n".to_vec()
+}
+
+/// SCC file with one caption, erase displayed memory.
+fn scc() -> Vec<u8> {
+    b"Scenarist_SCC V1.0\r\n\r\n00:00:00;00\t942c 942c\r\n".to_vec()
+}
+
+/// MCC 2.0 header, without the caption lines following it.
+fn mcc() -> Vec<u8> {
+    b"File Format=MacCaption_MCC V2.0\r\n\r\nTime Code Rate=30DF\r\n\r\n".to_vec()
+}
+
+/// TTML document with an empty body.
+fn ttml() -> Vec<u8> {
+    let mut out = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n".to_vec();
+    out.extend_from_slice(b"<tt xmlns=\"http://www.w3.org/ns/ttml\" xml:lang=\"en\">");
+    out.extend_from_slice(b"<body/></tt>\n");
+    out
+}
+
+/// Start of the GSI block of an EBU STL file: code page 850, 25 fps, teletext level 1,
+/// Latin alphabet, English.
+fn ebu_stl() -> Vec<u8> {
+    b"850STL25.011000009".to_vec()
+}
+
+/// SAMI document with one English caption.
+fn sami() -> Vec<u8> {
+    let mut out = b"<SAMI>\r\n<HEAD>\r\n<STYLE TYPE=\"text/css\"><!--\r\n".to_vec();
+    out.extend_from_slice(b".ENUSCC { Name: English; lang: en-US; }\r\n--></STYLE>\r\n");
+    out.extend_from_slice(b"</HEAD>\r\n<BODY>\r\n<SYNC Start=0><P Class=ENUSCC>media_infer\r\n");
+    out.extend_from_slice(b"</BODY>\r\n</SAMI>\r\n");
+    out
+}
+
+/// MicroDVD subtitles: the frame rate line, then one cue.
+fn microdvd() -> Vec<u8> {
+    b"{1}{1}25.000\n{25}{75}media_infer\n".to_vec()
+}
+
+/// Google SBV subtitles with one cue.
+fn subviewer() -> Vec<u8> {
+    b"0:00:00.000,0:00:01.500\nmedia_infer\n".to_vec()
+}
+
+/// LRC lyrics with a title tag and one line.
+fn lrc() -> Vec<u8> {
+    b"[ti:media_infer]\n[00:00.00]media_infer\n".to_vec()
+}
+
+/// PGS presentation composition segment starting a 1920x1080 epoch without objects,
+/// then the end segment.
+fn pgs() -> Vec<u8> {
+    let mut pcs = b"PG\0\0\0\0\0\0\0\0\x16\0\x0B".to_vec();
+    pcs.extend_from_slice(&[
+        0x07, 0x80, 0x04, 0x38, 0x10, 0x00, 0x00, 0x80, 0x00, 0x00, 0x00,
+    ]);
+    [&pcs[..], b"PG\0\0\0\0\0\0\0\0\x80\0\0"].concat()
+}
+
+/// Start of a VobSub index.
+fn vobsub_idx() -> Vec<u8> {
+    b"# VobSub index file, v7 (do not modify this line!)\n".to_vec()
+}
+
+/// Pack header, then a private stream 1 PES packet with a PTS and subpicture
+/// substream 0x20.
+fn vobsub_ps() -> Vec<u8> {
+    let mut out = ps()[..14].to_vec();
+    out.extend_from_slice(&[0x00, 0x00, 0x01, 0xBD, 0x00, 0x0A, 0x81, 0x80, 0x05]);
+    out.extend_from_slice(&[0x21, 0x00, 0x01, 0x00, 0x01, 0x20, 0x00, 0x00]);
+    out
+}
+
+/// Signature and version of a Cheetah CAP file, the rest of the 128 byte header left
+/// empty.
+fn cheetah_cap() -> Vec<u8> {
+    let mut out = vec![0; 128];
+    out[..4].copy_from_slice(&[0xEA, 0x22, 0x01, 0x00]);
+    out
+}
+
+/// PAC header, then a subtitle block with its centered text.
+fn pac() -> Vec<u8> {
+    let mut out = vec![0; 21];
+    out[0] = 0x01;
+    // Subtitle 1 from 10:00:01:00 to 10:00:03:12.
+    out.extend_from_slice(&[
+        0x01, 0x00, 0x60, 0xE8, 0x03, 0x64, 0x00, 0xE8, 0x03, 0x38, 0x01,
+    ]);
+    out.extend_from_slice(&[0x13, 0x00, 0x00, 0x0A, 0x00, 0x00, 0xFE, 0x02, 0x03]);
+    out.extend_from_slice(b"media_infer\x00");
+    out
+}
+
+/// Spruce subtitle script with a font directive and one subtitle.
+fn spruce_stl() -> Vec<u8> {
+    b"$FontName = Arial\r\n00:00:01:00 , 00:00:02:00 , media_infer\r\n".to_vec()
+}
+
+/// DVD flavour McPoodle's raw file: the `CC` user data header and a pair of caption
+/// blocks holding a resume caption loading command.
+fn mcpoodles_raw() -> Vec<u8> {
+    vec![
+        0x00, 0x00, 0x01, 0xB2, 0x43, 0x43, 0x01, 0xF8, 0xFF, 0x94, 0x20, 0xFE, 0x94, 0x20,
+    ]
+}
+
+/// RIFF CDXA header with its 16 byte `fmt ` chunk (CD-XA attributes) and an empty
+/// `data` chunk.
+fn cdxa() -> Vec<u8> {
+    let mut out = b"RIFF".to_vec();
+    out.extend_from_slice(&36u32.to_le_bytes());
+    out.extend_from_slice(b"CDXAfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    // Owner IDs, attributes (Mode 2 Form 1 and 2, interleaved), signature `XA`, file
+    // number and reserved bytes.
+    out.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x15, 0x55, b'X', b'A', 0x01, 0x00]);
+    out.extend_from_slice(&[0; 6]);
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out
+}
+
+/// Start of a DVD navigation pack: pack header, a system header without stream
+/// entries and the header of the PCI packet.
+fn vob() -> Vec<u8> {
+    let mut out = ps()[..14].to_vec();
+    out.extend_from_slice(&[0x00, 0x00, 0x01, 0xBB, 0x00, 0x06]);
+    out.extend_from_slice(&[0x80, 0xC4, 0xE1, 0x04, 0xE1, 0xFF]);
+    out.extend_from_slice(&[0x00, 0x00, 0x01, 0xBF, 0x03, 0xD4, 0x00]);
+    out
+}
+
+/// FLV header of a file with audio and video, then the first (zero) PreviousTagSize.
+fn flv() -> Vec<u8> {
+    let mut out = b"FLV\x01\x05".to_vec();
+    out.extend_from_slice(&9u32.to_be_bytes());
+    out.extend_from_slice(&0u32.to_be_bytes());
+    out
+}
+
+/// RealMedia file header, version 0, announcing the usual six headers.
+fn rm() -> Vec<u8> {
+    let mut out = b".RMF".to_vec();
+    out.extend_from_slice(&18u32.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes());
+    // File version and number of headers.
+    out.extend_from_slice(&0u32.to_be_bytes());
+    out.extend_from_slice(&6u32.to_be_bytes());
+    out
+}
+
+/// IVF header of a one frame VP9 CIF stream at 30 fps.
+fn ivf() -> Vec<u8> {
+    let mut out = b"DKIF".to_vec();
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&32u16.to_le_bytes());
+    out.extend_from_slice(b"VP90");
+    out.extend_from_slice(&352u16.to_le_bytes());
+    out.extend_from_slice(&288u16.to_le_bytes());
+    // Frame rate, time scale, frame count and an unused field.
+    out.extend_from_slice(&30u32.to_le_bytes());
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&1u32.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out
+}
+
+/// First page of an Ogg stream, holding the start of a Vorbis identification header.
+fn ogg() -> Vec<u8> {
+    let packet = b"\x01vorbis";
+    let mut out = b"OggS\x00\x02".to_vec();
+    // Granule position, serial number, page sequence number, CRC (filled in below).
+    out.extend_from_slice(&0u64.to_le_bytes());
+    out.extend_from_slice(&0x1234u32.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out.extend_from_slice(&0u32.to_le_bytes());
+    out.extend_from_slice(&[1, packet.len() as u8]);
+    out.extend_from_slice(packet);
+    let crc = ogg_crc(&out);
+    out[22..26].copy_from_slice(&crc.to_le_bytes());
+    out
+}
+
+/// CRC-32 of an Ogg page (polynomial 0x04c11db7, not reflected, zero initial value).
+fn ogg_crc(page: &[u8]) -> u32 {
+    let mut crc = 0u32;
+    for byte in page {
+        crc ^= u32::from(*byte) << 24;
+        for _ in 0..8 {
+            crc = if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04c1_1db7
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// SPS and PPS of a 176x144 Baseline stream, then the start of an IDR slice.
+fn h264_es() -> Vec<u8> {
+    let mut out = vec![0, 0, 0, 1, 0x67, 0x42, 0xC0, 0x0B, 0xDA, 0x0B, 0x13, 0x20];
+    out.extend_from_slice(&[0, 0, 0, 1, 0x68, 0xCE, 0x0F, 0xC8]);
+    out.extend_from_slice(&[0, 0, 1, 0x65, 0x88, 0x84, 0x00, 0x33]);
+    out
+}
+
+/// VPS, SPS and PPS of a Main profile stream, cut short.
+fn h265_es() -> Vec<u8> {
+    let mut out = vec![0, 0, 0, 1, 0x40, 0x01, 0x0C, 0x01, 0xFF, 0xFF, 0x01, 0x60];
+    out.extend_from_slice(&[0, 0, 0, 1, 0x42, 0x01, 0x01, 0x01, 0x60, 0x00]);
+    out.extend_from_slice(&[0, 0, 0, 1, 0x44, 0x01, 0xC1, 0x72, 0xB4, 0x62]);
+    out
+}
+
+/// Visual object sequence (Advanced Simple, level 5), visual object, then the start
+/// of a video object layer.
+fn mpeg4_es() -> Vec<u8> {
+    let mut out = vec![0, 0, 1, 0xB0, 0xF5];
+    out.extend_from_slice(&[0, 0, 1, 0xB5, 0x09]);
+    out.extend_from_slice(&[0, 0, 1, 0x00, 0, 0, 1, 0x20, 0x08, 0xC8, 0x0D, 0x40]);
+    out
+}
+
+/// Temporal delimiter, then the sequence header of a 352x288 Main profile stream.
+fn av1_obu() -> Vec<u8> {
+    let mut out = vec![0x12, 0x00, 0x0A, 0x0B];
+    out.extend_from_slice(&[
+        0x00, 0x00, 0x00, 0x42, 0x22, 0xBF, 0x1F, 0x7F, 0xFE, 0x60, 0x10,
+    ]);
+    out
+}
+
+/// Two silent AAC LC frames, 44.1 kHz stereo.
+fn aac_adts() -> Vec<u8> {
+    let frame = [0xFF, 0xF1, 0x50, 0x80, 0x01, 0x3F, 0xFC, 0x21, 0x00];
+    [frame, frame].concat()
+}
+
+/// Two LOAS frames with 4 byte `AudioMuxElement`s.
+fn aac_latm() -> Vec<u8> {
+    let frame = [0x56, 0xE0, 0x04, 0x47, 0xFC, 0x00, 0x00];
+    [frame, frame].concat()
+}
+
+/// Two silent MPEG-1 Layer III frames, 32 kbit/s at 48 kHz.
+fn mp3() -> Vec<u8> {
+    let mut frame = vec![0; 96];
+    frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x14, 0xC4]);
+    [&frame[..], &frame].concat()
+}
+
+/// `fLaC` and the STREAMINFO block of a 44.1 kHz stereo 16 bit stream, the only
+/// metadata block.
+fn flac() -> Vec<u8> {
+    let mut out = b"fLaC".to_vec();
+    out.extend_from_slice(&[0x80, 0x00, 0x00, 0x22]);
+    // Block sizes, frame sizes unknown, then sample rate, channels and bits per sample
+    // in 20, 3 and 5 bits, with no sample count and MD5.
+    out.extend_from_slice(&[0x10, 0x00, 0x10, 0x00, 0, 0, 0, 0, 0, 0]);
+    out.extend_from_slice(&[0x0A, 0xC4, 0x42, 0xF0]);
+    out.extend_from_slice(&[0; 20]);
+    out
+}
+
+/// Two 128 byte AC-3 frames, 32 kbit/s at 48 kHz.
+fn ac3() -> Vec<u8> {
+    let mut frame = vec![0; 128];
+    frame[..6].copy_from_slice(&[0x0B, 0x77, 0x00, 0x00, 0x00, 0x40]);
+    [&frame[..], &frame].concat()
+}
+
+/// Two 128 byte E-AC-3 frames, 48 kHz with one audio block each.
+fn eac3() -> Vec<u8> {
+    let mut frame = vec![0; 128];
+    frame[..6].copy_from_slice(&[0x0B, 0x77, 0x00, 0x3F, 0x04, 0x80]);
+    [&frame[..], &frame].concat()
+}
+
+/// Sync word and start of the header of a DTS core frame: 512 samples, 1024 bytes,
+/// 3/2 channels at 48 kHz and 768 kbit/s.
+fn dts() -> Vec<u8> {
+    vec![0x7F, 0xFE, 0x80, 0x01, 0xFC, 0x3C, 0x3F, 0xF2, 0x75, 0xE0]
+}
+
+/// Header of the first TrueHD access unit and the start of its major sync, 48 kHz.
+fn truehd() -> Vec<u8> {
+    vec![
+        0xF0, 0x80, 0x00, 0x00, 0xF8, 0x72, 0x6F, 0xBA, 0x00, 0x00, 0x0F, 0x4F, 0xB7, 0x52, 0x00,
+        0x00,
+    ]
+}
+
+/// Sync word and header of a DTS-HD extension substream without a core.
+fn dtshd() -> Vec<u8> {
+    vec![0x64, 0x58, 0x20, 0x25, 0x00, 0x00, 0x00, 0x00]
+}
+
+fn es() -> Vec<u8> {
+    vec![
+        0x00, 0x00, 0x01, 0xB3, 0x2D, 0x01, 0xE0, 0x24, 0xFF, 0xFF, 0xE3, 0x80,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_PRIORITY;
+
+    #[test]
+    fn round_trip() {
+        for kind in DEFAULT_PRIORITY {
+            let sample = generate_sample(*kind);
+            assert_eq!(ContainerType::from_bytes(&sample), Ok(*kind), "{:?}", kind);
+            assert!(kind.matches(&sample), "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn lengths() {
+        assert_eq!(generate_sample(ContainerType::ASF).len(), 30);
+        assert_eq!(generate_sample(ContainerType::MKV).len(), 5 + 35);
+        assert_eq!(generate_sample(ContainerType::WEBM).len(), 5 + 31);
+        assert_eq!(
+            generate_sample(ContainerType::MKA).len(),
+            5 + 35 + 5 + 5 + 8
+        );
+        assert_eq!(generate_sample(ContainerType::AVI).len(), 12 + 12 + 64);
+        assert_eq!(generate_sample(ContainerType::WAV).len(), 44);
+        assert_eq!(generate_sample(ContainerType::TS).len(), 188 * TS_PACKETS);
+        assert_eq!(generate_sample(ContainerType::M2TS).len(), 192 * TS_PACKETS);
+        assert_eq!(generate_sample(ContainerType::MXF).len(), 16 + 4 + 88);
+    }
+}
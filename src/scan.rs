@@ -0,0 +1,59 @@
+/// Checks if `needle` occurs anywhere in `haystack`.
+/// Candidates are found with a fast single byte search for `needle[anchor]`, which
+/// should be the rarest byte of the needle, and only compared in full there.
+pub(crate) fn contains(haystack: &[u8], needle: &[u8], anchor: usize) -> bool {
+    find(haystack, needle, anchor).is_some()
+}
+
+/// Offset of the first `needle` in `haystack`, searched like `contains`.
+pub(crate) fn find(haystack: &[u8], needle: &[u8], anchor: usize) -> Option<usize> {
+    debug_assert!(anchor < needle.len());
+    if haystack.len() < needle.len() {
+        return None;
+    }
+
+    // A hit at `i` in the shifted slice means the needle starts at `i` in `haystack`.
+    let end = haystack.len() - (needle.len() - 1 - anchor);
+    positions(&haystack[anchor..end], needle[anchor]).find(|i| haystack[*i..].starts_with(needle))
+}
+
+/// Offsets of the bytes following each `00 00 01` start code in `haystack`.
+pub(crate) fn start_codes(haystack: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    positions(haystack, 0x01)
+        .filter(move |i| *i >= 2 && haystack[i - 2] == 0 && haystack[i - 1] == 0)
+        .map(|i| i + 1)
+}
+
+#[cfg(feature = "memchr")]
+fn positions(haystack: &[u8], byte: u8) -> impl Iterator<Item = usize> + '_ {
+    memchr::memchr_iter(byte, haystack)
+}
+
+#[cfg(not(feature = "memchr"))]
+fn positions(haystack: &[u8], byte: u8) -> impl Iterator<Item = usize> + '_ {
+    haystack
+        .iter()
+        .enumerate()
+        .filter(move |x| *x.1 == byte)
+        .map(|x| x.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_matches_naive() {
+        let needle = [0x00, 0x00, 0x01, 0xBA];
+        let mut haystack = [0xBA; 64];
+        for pos in 0..=60 {
+            haystack[pos..pos + 4].copy_from_slice(&needle);
+            for len in 0..=64 {
+                let naive = haystack[..len].windows(4).any(|x| x == needle);
+                assert_eq!(contains(&haystack[..len], &needle, 3), naive);
+                assert_eq!(contains(&haystack[..len], &needle, 0), naive);
+            }
+            haystack[pos..pos + 4].copy_from_slice(&[0xBA; 4]);
+        }
+    }
+}
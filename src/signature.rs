@@ -0,0 +1,376 @@
+use crate::{ContainerType, DEFAULT_PRIORITY};
+use std::fmt::Write;
+
+/// Fixed bytes every file of a container has.
+/// A file matches when all rules match. Used to export the signatures, the checks
+/// themselves can validate more than this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    /// Container the signature belongs to.
+    pub kind: ContainerType,
+    /// `(offset, bytes)` pairs.
+    pub rules: &'static [(usize, &'static [u8])],
+}
+
+/// Signatures of the containers with anchored checks. A container may have several
+/// (MP4 brands), any of them matching is enough.
+///
+/// TS, M2TS, MXF, PS, fragmented MP4, AV1 OBU, AAC LOAS, AC-3, E-AC-3, TrueHD and PGS
+/// streams are found by walking packets, boxes, OBUs or frames or by scanning, so they
+/// have none. Only the DVD flavour of McPoodle's raw files has one, the broadcast one
+/// is told by the parity of its caption bytes. VobSub subpictures and DVD VOBs start
+/// with the PS pack header, only the packets after it tell them apart.
+/// H.264, H.265 and MPEG-4 Part 2 ES, DTS and DTS-HD may start with zero padding, and AAC ADTS
+/// and MP3 with an ID3v2 tag, so they have none either.
+/// WebM and MKA files match the MKV signature, the DocType and tracks telling them
+/// apart have no fixed offsets. Only the `ftyp` form of MOV has one, and 3GP, 3G2, M4A,
+/// HEIF and AVIF only match by their major brand. The generic `mif1` and `msf1` brands
+/// are used by both HEIF and AVIF, so they have none.
+/// TTML and SAMI are markup, their root element may follow a declaration and comments,
+/// so they have none.
+/// MicroDVD and SubViewer subtitles, Spruce STL scripts and LRC lyrics are plain text
+/// without a magic, so they have none. PAC has none either, its header is a `01` and
+/// zeros and only the timecodes of its first block tell it apart.
+pub const SIGNATURES: &[Signature] = &[
+    Signature {
+        kind: ContainerType::ASF,
+        rules: &[(
+            0,
+            &[
+                0x30, 0x26, 0xb2, 0x75, 0x8e, 0x66, 0xcf, 0x11, 0xa6, 0xd9, 0x00, 0xaa, 0x00, 0x62,
+                0xce, 0x6c,
+            ],
+        )],
+    },
+    Signature {
+        kind: ContainerType::MKV,
+        rules: &[(0, &[0x1a, 0x45, 0xdf, 0xa3])],
+    },
+    Signature {
+        kind: ContainerType::GXF,
+        rules: &[(0, &[0, 0, 0, 0, 1, 0xbc])],
+    },
+    Signature {
+        kind: ContainerType::WTV,
+        rules: &[(
+            0,
+            &[
+                0xb7, 0xd8, 0x00, 0x20, 0x37, 0x49, 0xda, 0x11, 0xa6, 0x4e, 0x00, 0x07, 0xe9, 0x5e,
+                0xad, 0x8d,
+            ],
+        )],
+    },
+    Signature {
+        kind: ContainerType::RCWT,
+        // Magic, CCExtractor as the creating program, format version 1, reserved bytes.
+        rules: &[(0, &[0xCC, 0xCC, 0xED, 0xCC]), (6, &[0, 1, 0, 0, 0])],
+    },
+    Signature {
+        kind: ContainerType::AVI,
+        rules: &[(0, b"RIFF"), (8, b"AVI ")],
+    },
+    Signature {
+        kind: ContainerType::WAV,
+        rules: &[(0, b"RIFF"), (8, b"WAVE")],
+    },
+    Signature {
+        kind: ContainerType::CDXA,
+        rules: &[(0, b"RIFF"), (8, b"CDXA")],
+    },
+    Signature {
+        kind: ContainerType::RF64,
+        rules: &[(0, b"RF64"), (8, b"WAVEds64")],
+    },
+    Signature {
+        kind: ContainerType::W64,
+        rules: &[(
+            0,
+            &[
+                0x72, 0x69, 0x66, 0x66, 0x2E, 0x91, 0xCF, 0x11, 0xA5, 0xD6, 0x28, 0xDB, 0x04, 0xC1,
+                0x00, 0x00,
+            ],
+        )],
+    },
+    Signature {
+        kind: ContainerType::AIFF,
+        rules: &[(0, b"FORM"), (8, b"AIFF")],
+    },
+    Signature {
+        kind: ContainerType::AIFF,
+        rules: &[(0, b"FORM"), (8, b"AIFC")],
+    },
+    Signature {
+        kind: ContainerType::AU,
+        rules: &[(0, b".snd")],
+    },
+    Signature {
+        kind: ContainerType::AMRWB,
+        rules: &[(0, b"#!AMR-WB\n")],
+    },
+    Signature {
+        kind: ContainerType::AMR,
+        rules: &[(0, b"#!AMR\n")],
+    },
+    Signature {
+        kind: ContainerType::CAF,
+        rules: &[(0, b"caff\x00\x01\x00\x00desc")],
+    },
+    Signature {
+        kind: ContainerType::WavPack,
+        rules: &[(0, b"wvpk")],
+    },
+    Signature {
+        kind: ContainerType::APE,
+        rules: &[(0, b"MAC ")],
+    },
+    Signature {
+        kind: ContainerType::Musepack,
+        rules: &[(0, b"MPCK")],
+    },
+    Signature {
+        kind: ContainerType::Musepack,
+        rules: &[(0, b"MP+")],
+    },
+    Signature {
+        kind: ContainerType::TTA,
+        rules: &[(0, b"TTA1")],
+    },
+    Signature {
+        kind: ContainerType::Shorten,
+        rules: &[(0, b"ajkg")],
+    },
+    Signature {
+        kind: ContainerType::MIDI,
+        rules: &[(0, b"MThd\x00\x00\x00\x06")],
+    },
+    Signature {
+        kind: ContainerType::DSF,
+        rules: &[(0, b"DSD \x1c\x00\x00\x00\x00\x00\x00\x00"), (28, b"fmt ")],
+    },
+    Signature {
+        kind: ContainerType::DFF,
+        rules: &[(0, b"FRM8"), (12, b"DSD ")],
+    },
+    Signature {
+        kind: ContainerType::TrackerModule,
+        rules: &[(0, b"Extended Module: ")],
+    },
+    Signature {
+        kind: ContainerType::TrackerModule,
+        rules: &[(0, b"IMPM")],
+    },
+    Signature {
+        kind: ContainerType::TrackerModule,
+        rules: &[(44, b"SCRM")],
+    },
+    Signature {
+        kind: ContainerType::TrackerModule,
+        rules: &[(1080, b"M.K.")],
+    },
+    Signature {
+        kind: ContainerType::FLV,
+        rules: &[(0, b"FLV\x01")],
+    },
+    Signature {
+        kind: ContainerType::WebVTT,
+        rules: &[(0, b"WEBVTT")],
+    },
+    Signature {
+        kind: ContainerType::WebVTT,
+        rules: &[(0, b"\xEF\xBB\xBFWEBVTT")],
+    },
+    Signature {
+        kind: ContainerType::SCC,
+        rules: &[(0, b"Scenarist_SCC V1.0")],
+    },
+    Signature {
+        kind: ContainerType::MCC,
+        rules: &[(0, b"File Format=MacCaption_MCC V")],
+    },
+    Signature {
+        kind: ContainerType::EbuStl,
+        rules: &[(3, b"STL25.01")],
+    },
+    Signature {
+        kind: ContainerType::EbuStl,
+        rules: &[(3, b"STL30.01")],
+    },
+    Signature {
+        kind: ContainerType::McPoodlesRaw,
+        rules: &[(0, &[0x00, 0x00, 0x01, 0xB2, 0x43, 0x43, 0x01, 0xF8])],
+    },
+    Signature {
+        kind: ContainerType::CheetahCAP,
+        rules: &[(0, &[0xEA, 0x22, 0x01, 0x00])],
+    },
+    Signature {
+        kind: ContainerType::CheetahCAP,
+        rules: &[(0, &[0xEA, 0x22, 0x02, 0x00])],
+    },
+    Signature {
+        kind: ContainerType::CheetahCAP,
+        rules: &[(0, &[0xEA, 0x22, 0x03, 0x00])],
+    },
+    Signature {
+        kind: ContainerType::VobSubIdx,
+        rules: &[(0, b"# VobSub index file, v")],
+    },
+    Signature {
+        kind: ContainerType::OGG,
+        rules: &[(0, b"OggS\x00")],
+    },
+    Signature {
+        kind: ContainerType::RealMedia,
+        rules: &[(0, b".RMF")],
+    },
+    Signature {
+        kind: ContainerType::IVF,
+        rules: &[(0, b"DKIF\x00\x00")],
+    },
+    Signature {
+        kind: ContainerType::FLAC,
+        rules: &[(0, b"fLaC"), (5, &[0, 0, 34])],
+    },
+    Signature {
+        kind: ContainerType::Y4M,
+        rules: &[(0, b"YUV4MPEG2 ")],
+    },
+    Signature {
+        kind: ContainerType::MP4,
+        rules: &[(4, b"ftypisom")],
+    },
+    Signature {
+        kind: ContainerType::MP4,
+        rules: &[(4, b"ftypMSNV")],
+    },
+    Signature {
+        kind: ContainerType::MOV,
+        rules: &[(4, b"ftypqt  ")],
+    },
+    Signature {
+        kind: ContainerType::HEIF,
+        rules: &[(4, b"ftyphei")],
+    },
+    Signature {
+        kind: ContainerType::HEIF,
+        rules: &[(4, b"ftyphev")],
+    },
+    Signature {
+        kind: ContainerType::AVIF,
+        rules: &[(4, b"ftypavif")],
+    },
+    Signature {
+        kind: ContainerType::AVIFSequence,
+        rules: &[(4, b"ftypavis")],
+    },
+    Signature {
+        kind: ContainerType::ThreeGP,
+        rules: &[(4, b"ftyp3gp")],
+    },
+    Signature {
+        kind: ContainerType::ThreeG2,
+        rules: &[(4, b"ftyp3g2")],
+    },
+    Signature {
+        kind: ContainerType::M4A,
+        rules: &[(4, b"ftypM4A ")],
+    },
+    Signature {
+        kind: ContainerType::M4A,
+        rules: &[(4, b"ftypM4B ")],
+    },
+    Signature {
+        kind: ContainerType::M4A,
+        rules: &[(4, b"ftypM4P ")],
+    },
+    Signature {
+        kind: ContainerType::TivoPS,
+        rules: &[(0, b"TiVo")],
+    },
+    Signature {
+        kind: ContainerType::ES,
+        rules: &[(0, &[0, 0, 1, 0xB3])],
+    },
+];
+
+/// `SIGNATURES` in magic(5) source format, for `file(1)`.
+/// The description of a signature is the `Display` name of its container.
+/// Rules after the first become continuation lines, so all of them have to match.
+/// The header lists the containers without a signature by their short names.
+pub fn magic_definitions() -> String {
+    let mut out = String::from("# Generated by media_infer from its signature table.\n");
+    out.push_str("# Only fixed offset signatures are listed. Not listed:\n#");
+    let mut len = 1;
+    let unlisted = DEFAULT_PRIORITY
+        .iter()
+        .filter(|x| !SIGNATURES.iter().any(|y| y.kind == **x));
+    for kind in unlisted {
+        let name = kind.short_name();
+        if len + 1 + name.len() > 80 {
+            out.push_str("\n#");
+            len = 1;
+        }
+        write!(out, " {}", name).unwrap();
+        len += 1 + name.len();
+    }
+    out.push('\n');
+    for signature in SIGNATURES {
+        out.push('\n');
+        let last = signature.rules.len() - 1;
+        for (i, (offset, bytes)) in signature.rules.iter().enumerate() {
+            let description = if i == last {
+                signature.kind.to_string()
+            } else {
+                String::new()
+            };
+            let pattern: String = bytes.iter().map(|x| escape(*x)).collect();
+            let line = format!(
+                "{}{}\tstring\t{}\t{}",
+                ">".repeat(i),
+                offset,
+                pattern,
+                description
+            );
+            writeln!(out, "{}", line.trim_end()).unwrap();
+        }
+    }
+    out
+}
+
+/// Byte as it can appear in a magic(5) string.
+fn escape(byte: u8) -> String {
+    match byte {
+        b'\\' | b' ' | b'#' => format!("\\x{:02x}", byte),
+        x if x.is_ascii_graphic() => (x as char).to_string(),
+        x => format!("\\x{:02x}", x),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samples::generate_sample;
+
+    #[test]
+    fn samples_match() {
+        for signature in SIGNATURES {
+            let sample = generate_sample(signature.kind);
+            let matched = SIGNATURES
+                .iter()
+                .filter(|x| x.kind == signature.kind)
+                .any(|x| {
+                    x.rules.iter().all(|(offset, bytes)| {
+                        sample.get(*offset..offset + bytes.len()) == Some(*bytes)
+                    })
+                });
+            assert!(matched, "{:?}", signature.kind);
+        }
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(super::escape(b'T'), "T");
+        assert_eq!(super::escape(b' '), "\\x20");
+        assert_eq!(super::escape(0xCC), "\\xcc");
+    }
+}
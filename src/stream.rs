@@ -0,0 +1,130 @@
+use crate::{ContainerType, Detector, Error};
+
+/// Result of detecting a stream.
+#[derive(Debug)]
+pub struct Detection {
+    /// What `Detector::detect` returns for the probed bytes.
+    pub result: Result<ContainerType, Error>,
+    /// Number of starting bytes the result is based on.
+    pub probed: usize,
+}
+
+/// Detects the container of a stream that arrives in chunks.
+///
+/// Gives the same result as `Detector::detect` on the first `cap` bytes of the
+/// stream (all of it if shorter), however it is split. Anchored matches, like an
+/// Ogg page header, are reported as soon as the first 16 bytes are there; the rest
+/// needs `cap` bytes or the end of the stream.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{ContainerType, Detector, StreamDetector};
+///
+/// let mut stream = StreamDetector::new(Detector::default());
+/// assert!(stream.push(b"OggS\x00\x02").is_none());
+/// let detection = stream.push(&[0; 10]).unwrap();
+/// assert_eq!(detection.result.as_ref().ok(), Some(&ContainerType::OGG));
+/// ```
+#[derive(Debug)]
+pub struct StreamDetector {
+    detector: Detector,
+    cap: usize,
+    buffer: Vec<u8>,
+    detection: Option<Detection>,
+}
+
+impl StreamDetector {
+    /// Buffers up to `detector.recommended_probe_len()` bytes.
+    pub fn new(detector: Detector) -> Self {
+        let cap = detector.recommended_probe_len();
+        Self {
+            detector,
+            cap,
+            buffer: Vec::new(),
+            detection: None,
+        }
+    }
+
+    /// Buffer at most `cap` bytes, and decide once they are there.
+    pub fn with_cap(mut self, cap: usize) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Feeds the next chunk, returns the detection once it is known.
+    /// Bytes past the cap, or pushed after the detection, are ignored.
+    pub fn push(&mut self, chunk: &[u8]) -> Option<&Detection> {
+        if self.detection.is_none() {
+            let take = chunk.len().min(self.cap - self.buffer.len().min(self.cap));
+            self.buffer.extend_from_slice(&chunk[..take]);
+            self.decide(false);
+        }
+        self.detection.as_ref()
+    }
+
+    /// Ends the stream, detecting on whatever was pushed if that was not enough before.
+    pub fn finish(&mut self) -> &Detection {
+        self.decide(true);
+        self.detection.as_ref().unwrap()
+    }
+
+    /// The detection, if it is known.
+    pub fn detection(&self) -> Option<&Detection> {
+        self.detection.as_ref()
+    }
+
+    /// Bytes buffered so far.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    fn decide(&mut self, end: bool) {
+        if self.detection.is_some() {
+            return;
+        }
+        if let Some(result) = self.detector.detect_prefix(&self.buffer, self.cap, end) {
+            self.detection = Some(Detection {
+                result,
+                probed: self.buffer.len(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samples::generate_sample;
+    use crate::DEFAULT_PRIORITY;
+
+    #[test]
+    fn chunking() {
+        for kind in DEFAULT_PRIORITY {
+            let sample = generate_sample(*kind);
+            for size in [1, 7, 100] {
+                let mut stream = StreamDetector::new(Detector::default());
+                for chunk in sample.chunks(size) {
+                    stream.push(chunk);
+                }
+                let t = stream.finish();
+                assert_eq!(t.result.as_ref().ok(), Some(kind), "{:?} {}", kind, size);
+            }
+        }
+    }
+
+    #[test]
+    fn early() {
+        let mut stream = StreamDetector::new(Detector::default());
+        let t = stream.push(&generate_sample(ContainerType::ASF)).unwrap();
+        assert_eq!(t.probed, 30);
+
+        // TS needs the whole cap.
+        let mut stream = StreamDetector::new(Detector::default()).with_cap(4096);
+        let sample = generate_sample(ContainerType::TS);
+        assert!(stream.push(&sample).is_none());
+        let t = stream.push(&vec![0; 4096]).unwrap();
+        assert_eq!(t.probed, 4096);
+        assert_eq!(t.result.as_ref().ok(), Some(&ContainerType::TS));
+        assert_eq!(stream.buffered().len(), 4096);
+    }
+}
@@ -0,0 +1,75 @@
+//! Lines of plain text formats (subtitles, lyrics) without a magic.
+
+/// Most bytes looked through for lines.
+const SEARCH_LEN: usize = 4096;
+
+/// UTF-8 byte order mark.
+const BOM: &[u8; 3] = b"\xEF\xBB\xBF";
+
+/// Non-blank lines in the first 4 KiB of `buffer`, after an optional UTF-8 BOM,
+/// without their line break (`\n` or `\r\n`). The last line may be cut off by the end
+/// of the buffer or of those 4 KiB.
+pub(crate) fn lines(buffer: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let buffer = &buffer[..buffer.len().min(SEARCH_LEN)];
+    let buffer = buffer.strip_prefix(BOM).unwrap_or(buffer);
+    buffer
+        .split(|x| *x == b'\n')
+        .map(|x| x.strip_suffix(b"\r").unwrap_or(x))
+        .filter(|x| !x.iter().all(u8::is_ascii_whitespace))
+}
+
+/// `line` without its trailing ASCII whitespace.
+pub(crate) fn trim_end(line: &[u8]) -> &[u8] {
+    let end = line
+        .iter()
+        .rposition(|x| !x.is_ascii_whitespace())
+        .map_or(0, |x| x + 1);
+    &line[..end]
+}
+
+/// Rest of `line` after a run of one or more ASCII digits at its start.
+pub(crate) fn skip_digits(line: &[u8]) -> Option<&[u8]> {
+    let end = line
+        .iter()
+        .position(|x| !x.is_ascii_digit())
+        .unwrap_or(line.len());
+    if end == 0 {
+        return None;
+    }
+    Some(&line[end..])
+}
+
+/// Rest of `line` after two ASCII digits at its start.
+pub(crate) fn two_digits(line: &[u8]) -> Option<&[u8]> {
+    match line {
+        [a, b, rest @ ..] if a.is_ascii_digit() && b.is_ascii_digit() => Some(rest),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blank_lines() {
+        let buffer = b"\xEF\xBB\xBFone\r\n\r\n \t\ntwo\nthr";
+        let t: Vec<_> = lines(buffer).collect();
+        assert_eq!(t, [&b"one"[..], b"two", b"thr"]);
+        assert_eq!(lines(b"\n\r\n").count(), 0);
+        let mut buffer = vec![b'a'; SEARCH_LEN - 1];
+        buffer.extend_from_slice(b"\nb");
+        assert_eq!(lines(&buffer).count(), 1);
+        assert_eq!(trim_end(b" a b \t"), b" a b");
+        assert_eq!(trim_end(b" \t"), b"");
+    }
+
+    #[test]
+    fn digits() {
+        assert_eq!(skip_digits(b"123}"), Some(&b"}"[..]));
+        assert_eq!(skip_digits(b"7"), Some(&b""[..]));
+        assert_eq!(skip_digits(b"}"), None);
+        assert_eq!(two_digits(b"059"), Some(&b"9"[..]));
+        assert_eq!(two_digits(b"5:"), None);
+    }
+}
@@ -0,0 +1,105 @@
+//! Format of a tracker module, see [`tracker_format`].
+
+use crate::bytes::slice_at;
+
+/// Format of a tracker module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackerFormat {
+    /// ProTracker and compatible MOD, `M.K.` and friends at offset 1080
+    Mod,
+    /// FastTracker 2 Extended Module, `Extended Module: `
+    Xm,
+    /// Scream Tracker 3 module, `SCRM` at offset 44
+    S3m,
+    /// Impulse Tracker module, `IMPM`
+    It,
+}
+
+/// Offset of the channel tag of a MOD file, after the title, 31 sample headers, the
+/// song length, the restart byte and the 128 entry pattern table.
+const MOD_TAG_OFFSET: usize = 20 + 31 * 30 + 2 + 128;
+
+/// Offset of the song length of a MOD file.
+const MOD_SONG_LEN_OFFSET: usize = 950;
+
+/// Channel tags of 4, 6 and 8 channel MOD files.
+const MOD_TAGS: [&[u8; 4]; 7] = [
+    b"M.K.", b"M!K!", b"FLT4", b"FLT8", b"4CHN", b"6CHN", b"8CHN",
+];
+
+/// Offset of `SCRM` in an S3M header, after the title, `1A`, the file type and two
+/// reserved bytes and the order, instrument and pattern counts, flags, tracker
+/// version and sample format.
+const S3M_TAG_OFFSET: usize = 44;
+
+/// Format of the tracker module `buffer` starts with. `None` if `buffer` does not start
+/// with a module header. MOD files are only told from the tag at offset 1080, so
+/// shorter buffers are never MOD.
+///
+/// # Example
+/// ```rust
+/// use media_infer::{tracker_format, TrackerFormat};
+///
+/// assert_eq!(tracker_format(b"IMPMsong\x00"), Some(TrackerFormat::It));
+/// ```
+pub fn tracker_format(buffer: &[u8]) -> Option<TrackerFormat> {
+    if buffer.starts_with(b"Extended Module: ") {
+        Some(TrackerFormat::Xm)
+    } else if buffer.starts_with(b"IMPM") {
+        Some(TrackerFormat::It)
+    } else if is_s3m(buffer) {
+        Some(TrackerFormat::S3m)
+    } else if is_mod(buffer) {
+        Some(TrackerFormat::Mod)
+    } else {
+        None
+    }
+}
+
+/// `SCRM`, with the `1A` end of title marker and the module file type (16) before it.
+fn is_s3m(buffer: &[u8]) -> bool {
+    slice_at(buffer, S3M_TAG_OFFSET, 4) == Some(b"SCRM") && buffer[28..30] == [0x1A, 0x10]
+}
+
+/// One of the channel tags, with a song length of 1 to 128 patterns.
+fn is_mod(buffer: &[u8]) -> bool {
+    match slice_at(buffer, MOD_TAG_OFFSET, 4) {
+        Some(tag) => {
+            MOD_TAGS.iter().any(|x| &x[..] == tag)
+                && (1..=128).contains(&buffer[MOD_SONG_LEN_OFFSET])
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mod_tag() {
+        let mut buffer = vec![0; MOD_TAG_OFFSET + 4];
+        buffer[MOD_SONG_LEN_OFFSET] = 1;
+        buffer[MOD_TAG_OFFSET..].copy_from_slice(b"8CHN");
+        assert_eq!(tracker_format(&buffer), Some(TrackerFormat::Mod));
+        buffer[MOD_SONG_LEN_OFFSET] = 0;
+        assert_eq!(tracker_format(&buffer), None);
+        buffer[MOD_SONG_LEN_OFFSET] = 129;
+        assert_eq!(tracker_format(&buffer), None);
+        buffer[MOD_SONG_LEN_OFFSET] = 1;
+        buffer[MOD_TAG_OFFSET..].copy_from_slice(b"3CHN");
+        assert_eq!(tracker_format(&buffer), None);
+        assert_eq!(tracker_format(&buffer[..MOD_TAG_OFFSET + 3]), None);
+    }
+
+    #[test]
+    fn s3m_type() {
+        let mut buffer = [0; 48];
+        buffer[28..30].copy_from_slice(&[0x1A, 0x10]);
+        buffer[44..].copy_from_slice(b"SCRM");
+        assert_eq!(tracker_format(&buffer), Some(TrackerFormat::S3m));
+        // Sample file type.
+        buffer[29] = 0x01;
+        assert_eq!(tracker_format(&buffer), None);
+    }
+}
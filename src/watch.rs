@@ -0,0 +1,189 @@
+//! Hot folder helper: classify files once they stop growing.
+
+use crate::{ContainerType, Error};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Options of [`watch_dir`].
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// How long the size of a file has to stay the same before it is probed.
+    /// Default is 1 s.
+    pub stable_for: Duration,
+    /// How often pending files are looked at. Default is 100 ms.
+    pub poll_interval: Duration,
+    /// How many more times a file is probed when detection fails with
+    /// `Error::FileTooSmall`, in case it was only partially written. Every retry waits
+    /// for the size to be stable again. Default is 3.
+    pub too_small_retries: usize,
+    /// Watch subdirectories too. Default is `false`.
+    pub recursive: bool,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            stable_for: Duration::from_secs(1),
+            poll_interval: Duration::from_millis(100),
+            too_small_retries: 3,
+            recursive: false,
+        }
+    }
+}
+
+/// Stops the watch when dropped.
+#[derive(Debug)]
+pub struct WatchHandle {
+    // Dropping the watcher closes the channel, which ends the thread.
+    _watcher: RecommendedWatcher,
+}
+
+/// A file waiting to be probed.
+struct Pending {
+    len: u64,
+    since: Instant,
+    retries: usize,
+}
+
+/// Watches `path` and calls `callback` for every file created or written in it, once
+/// its size has been stable for `options.stable_for`.
+///
+/// A file is reported again if it is written to after it was reported. Files that
+/// disappear before they are stable are dropped silently.
+/// The callback runs on a background thread, the watch lasts as long as the handle.
+pub fn watch_dir<F>(
+    path: &Path,
+    options: WatchOptions,
+    mut callback: F,
+) -> notify::Result<WatchHandle>
+where
+    F: FnMut(PathBuf, Result<ContainerType, Error>) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mode = if options.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher.watch(path, mode)?;
+
+    thread::spawn(move || {
+        let mut pending: HashMap<PathBuf, Pending> = HashMap::new();
+        loop {
+            match rx.recv_timeout(options.poll_interval) {
+                Ok(Ok(event)) if event.kind.is_create() || event.kind.is_modify() => {
+                    for path in event.paths {
+                        let retries = pending.get(&path).map_or(0, |x| x.retries);
+                        pending.insert(
+                            path,
+                            Pending {
+                                len: u64::MAX,
+                                since: Instant::now(),
+                                retries,
+                            },
+                        );
+                    }
+                }
+                Ok(_) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+
+            let now = Instant::now();
+            pending.retain(|path, file| {
+                let len = match fs::metadata(path) {
+                    Ok(x) if x.is_file() => x.len(),
+                    _ => return false,
+                };
+                if len != file.len {
+                    file.len = len;
+                    file.since = now;
+                    return true;
+                }
+                if now.duration_since(file.since) < options.stable_for {
+                    return true;
+                }
+                let result = ContainerType::from_file_path(path);
+                if let Err(Error::FileTooSmall { .. }) = result {
+                    if file.retries < options.too_small_retries {
+                        file.retries += 1;
+                        file.since = now;
+                        return true;
+                    }
+                }
+                callback(path.clone(), result);
+                false
+            });
+        }
+    });
+
+    Ok(WatchHandle { _watcher: watcher })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::samples::generate_sample;
+    use std::io::Write;
+    use std::sync::mpsc::Receiver;
+
+    fn options() -> WatchOptions {
+        WatchOptions {
+            stable_for: Duration::from_millis(200),
+            poll_interval: Duration::from_millis(20),
+            too_small_retries: 3,
+            recursive: false,
+        }
+    }
+
+    type Reports = Receiver<(PathBuf, Result<ContainerType, Error>)>;
+
+    fn watch(dir: &Path, options: WatchOptions) -> (WatchHandle, Reports) {
+        let (tx, rx) = mpsc::channel();
+        let handle = watch_dir(dir, options, move |path, result| {
+            tx.send((path, result)).unwrap();
+        })
+        .unwrap();
+        (handle, rx)
+    }
+
+    #[test]
+    fn two_stages() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_handle, rx) = watch(dir.path(), options());
+
+        let sample = generate_sample(ContainerType::TS);
+        let path = dir.path().join("upload.ts");
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(&sample[..40]).unwrap();
+        file.flush().unwrap();
+        // Stable long enough to be probed, it is too small and gets retried.
+        thread::sleep(Duration::from_millis(500));
+        file.write_all(&sample[40..]).unwrap();
+        file.flush().unwrap();
+
+        let (t_path, t) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(t_path.file_name(), path.file_name());
+        assert_eq!(t.ok(), Some(ContainerType::TS));
+        assert!(rx.recv_timeout(Duration::from_millis(500)).is_err());
+    }
+
+    #[test]
+    fn stays_too_small() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = WatchOptions {
+            too_small_retries: 1,
+            ..options()
+        };
+        let (_handle, rx) = watch(dir.path(), options);
+
+        fs::write(dir.path().join("cut.ts"), [0x47, 0x40, 0x00, 0x10, 0, 0]).unwrap();
+        let (_, t) = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(t, Err(Error::FileTooSmall { len: 6, .. })));
+    }
+}
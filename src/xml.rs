@@ -0,0 +1,116 @@
+//! Just enough XML (and SGML-ish markup) to find the root element of a document.
+
+use crate::scan;
+
+/// Most bytes looked through for the root element.
+const ROOT_SEARCH_LEN: usize = 4096;
+
+/// UTF-8 byte order mark.
+const BOM: &[u8; 3] = b"\xEF\xBB\xBF";
+
+/// Opening tag of the root element, from its `<` to its `>`, or to the end of the
+/// buffer or search window if it is cut off. A UTF-8 BOM, whitespace, the XML
+/// declaration and other processing instructions, comments and a doctype may come
+/// before it. `None` if anything else does, or if the buffer ends first.
+pub(crate) fn root_tag(buffer: &[u8]) -> Option<&[u8]> {
+    let buffer = &buffer[..buffer.len().min(ROOT_SEARCH_LEN)];
+    let mut rest = buffer.strip_prefix(BOM).unwrap_or(buffer);
+    loop {
+        let start = rest.iter().position(|x| !x.is_ascii_whitespace())?;
+        rest = &rest[start..];
+        rest = if let Some(x) = rest.strip_prefix(b"<?") {
+            after(x, b"?>")?
+        } else if let Some(x) = rest.strip_prefix(b"<!--") {
+            after(x, b"-->")?
+        } else if let Some(x) = rest.strip_prefix(b"<!") {
+            after(x, b">")?
+        } else if rest[0] == b'<' {
+            let end = rest
+                .iter()
+                .position(|x| *x == b'>')
+                .map_or(rest.len(), |x| x + 1);
+            return Some(&rest[..end]);
+        } else {
+            return None;
+        };
+    }
+}
+
+/// Element name of an opening tag, prefix included.
+pub(crate) fn name(tag: &[u8]) -> &[u8] {
+    let tag = tag.strip_prefix(b"<").unwrap_or(tag);
+    let end = tag
+        .iter()
+        .position(|x| x.is_ascii_whitespace() || matches!(x, b'>' | b'/'))
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+/// Values of the namespace declarations (`xmlns` and `xmlns:prefix` attributes) of an
+/// opening tag. Values cut off by the end of the tag are left out.
+pub(crate) fn namespaces(tag: &[u8]) -> impl Iterator<Item = &[u8]> {
+    (1..tag.len()).filter_map(move |i| {
+        if !tag[i - 1].is_ascii_whitespace() || !tag[i..].starts_with(b"xmlns") {
+            return None;
+        }
+        let rest = &tag[i + 5..];
+        let rest = match rest.strip_prefix(b":") {
+            Some(x) => {
+                let end = x.iter().position(|x| {
+                    !(x.is_ascii_alphanumeric() || matches!(x, b'-' | b'_' | b'.'))
+                })?;
+                &x[end..]
+            }
+            None => rest,
+        };
+        let rest = trim_start(trim_start(rest).strip_prefix(b"=")?);
+        let quote = *rest.first().filter(|x| matches!(x, b'"' | b'\''))?;
+        let value = &rest[1..];
+        value
+            .iter()
+            .position(|x| *x == quote)
+            .map(|end| &value[..end])
+    })
+}
+
+/// Rest of `buffer` after the first `end`.
+fn after<'a>(buffer: &'a [u8], end: &[u8]) -> Option<&'a [u8]> {
+    scan::find(buffer, end, 0).map(|x| &buffer[x + end.len()..])
+}
+
+/// `buffer` without its leading whitespace.
+fn trim_start(buffer: &[u8]) -> &[u8] {
+    let start = buffer
+        .iter()
+        .position(|x| !x.is_ascii_whitespace())
+        .unwrap_or(buffer.len());
+    &buffer[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prolog() {
+        let doc = b"\xEF\xBB\xBF<?xml version=\"1.0\"?>\n<!-- <tt> -->\n<!DOCTYPE x>\n<x a='1'>";
+        assert_eq!(root_tag(doc), Some(&b"<x a='1'>"[..]));
+        assert_eq!(root_tag(b"  <x"), Some(&b"<x"[..]));
+        assert_eq!(root_tag(b"<!-- cut"), None);
+        assert_eq!(root_tag(b"text <x>"), None);
+        assert_eq!(root_tag(b" \n"), None);
+        let mut doc = vec![b' '; ROOT_SEARCH_LEN];
+        doc.extend_from_slice(b"<x>");
+        assert_eq!(root_tag(&doc), None);
+    }
+
+    #[test]
+    fn tag_parts() {
+        let tag = b"<tt:tt xmlns:tt = \"urn:a\" xml:lang='en' xmlns='urn:b' myxmlns=\"urn:c\">";
+        assert_eq!(name(tag), b"tt:tt");
+        assert_eq!(name(b"<x/>"), b"x");
+        let values: Vec<_> = namespaces(tag).collect();
+        assert_eq!(values, [&b"urn:a"[..], b"urn:b"]);
+        assert_eq!(namespaces(b"<x xmlns=\"urn:cut").count(), 0);
+    }
+}
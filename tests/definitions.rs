@@ -0,0 +1,64 @@
+#![cfg(feature = "definitions")]
+
+use media_infer::{ContainerType, Detected, Detector, Error};
+use std::fs::File;
+use std::path::Path;
+
+fn detector() -> Detector {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/definitions/cameras.toml");
+    let mut detector = Detector::default();
+    detector
+        .load_definitions(File::open(path).unwrap())
+        .unwrap();
+    detector
+}
+
+#[test]
+fn test_load_definitions() {
+    let detector = detector();
+    let names: Vec<_> = detector.custom_formats().iter().map(|x| x.name()).collect();
+    assert_eq!(names, ["acme-raw", "zeta-cam"]);
+    assert_eq!(detector.custom_formats()[0].payload_start(), None);
+    assert_eq!(detector.custom_formats()[1].payload_start(), Some(64));
+
+    // Any revision of the masked nibble.
+    for revision in [0x10, 0x13, 0x1F] {
+        let buf = [b'A', b'C', b'M', b'E', revision, 0, 0, 0];
+        let t = detector.detect_named(&buf).unwrap();
+        assert_eq!(t.name(), "acme-raw");
+    }
+    let t = detector.detect_named(b"ACME\x20\x00\x00\x00");
+    assert!(matches!(t, Err(Error::Unidentified)));
+
+    let mut buf = [0; 64];
+    buf[8..15].copy_from_slice(b"ZETACAM");
+    assert_eq!(detector.detect_named(&buf).unwrap().name(), "zeta-cam");
+    // Rules match, but shorter than min_len.
+    assert!(detector.detect_named(&buf[..32]).is_err());
+    // Only `detect_named` reports custom formats.
+    assert!(detector.detect(&buf).is_err());
+}
+
+#[test]
+fn test_definitions_alongside_builtin() {
+    let detector = detector();
+    let buf = [0x1a, 0x45, 0xdf, 0xa3, 0, 1];
+    let t = detector.detect_named(&buf).unwrap();
+    assert_eq!(t, Detected::Container(ContainerType::MKV));
+    assert_eq!(t.name(), "mkv");
+}
+
+#[test]
+fn test_invalid_definitions() {
+    let mut detector = detector();
+    let text = "[[format]]\nname = \"x\"\n\n[[format.rule]]\noffset = 0\npattern = \"41 4\"\n";
+    let t = detector.load_definitions(text.as_bytes());
+    assert!(matches!(t, Err(Error::InvalidDefinitions { line: 6, .. })));
+    assert_eq!(
+        t.unwrap_err().to_string(),
+        "Invalid definitions, line 6, format[0].rule[0].pattern: `4` is not a whole number of hex bytes"
+    );
+    // Nothing registered.
+    assert_eq!(detector.custom_formats().len(), 2);
+}
@@ -0,0 +1,49 @@
+mod support;
+
+use media_infer::ContainerType;
+use std::env;
+use std::path::{Path, PathBuf};
+use support::Report;
+
+/// Directory of media to compare against ffprobe.
+const DIR_VAR: &str = "MEDIA_INFER_FFPROBE_DIR";
+
+#[test]
+#[ignore = "needs ffprobe and MEDIA_INFER_FFPROBE_DIR, run with --ignored"]
+fn test_ffprobe() {
+    let dir = env::var_os(DIR_VAR)
+        .unwrap_or_else(|| panic!("{} must name a directory of media", DIR_VAR));
+    let report = Report::run(Path::new(&dir)).unwrap();
+    println!("{}", report);
+}
+
+#[test]
+fn test_ffprobe_report() {
+    let mut report = Report::default();
+    report.add(
+        PathBuf::from("a.m2ts"),
+        Some("mpegts".to_string()),
+        Ok(ContainerType::M2TS),
+    );
+    report.add(
+        PathBuf::from("b.txt"),
+        None,
+        Err("Could Not Identify".to_string()),
+    );
+    report.add(
+        PathBuf::from("c.nut"),
+        Some("nut".to_string()),
+        Err("Could Not Identify".to_string()),
+    );
+    report.add(
+        PathBuf::from("d.mkv"),
+        Some("matroska,webm".to_string()),
+        Ok(ContainerType::MP4),
+    );
+
+    assert_eq!(report.total, 4);
+    assert_eq!(report.agreed, 2);
+    assert_eq!(report.unmapped.len(), 1);
+    assert_eq!(report.disagreements.len(), 1);
+    assert_eq!(report.disagreements[0].path, PathBuf::from("d.mkv"));
+}
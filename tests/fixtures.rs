@@ -0,0 +1,78 @@
+use media_infer::{ContainerType, Detector};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const GOLDEN: &str = "expected.toml";
+
+#[test]
+fn test_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let expected: toml::Table = fs::read_to_string(dir.join(GOLDEN))
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let files: BTreeSet<String> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|x| x.unwrap().path())
+        .filter(|x| x.is_file())
+        .map(|x| x.file_name().unwrap().to_string_lossy().into_owned())
+        .filter(|x| x != GOLDEN)
+        .collect();
+
+    let mut failures = Vec::new();
+    for name in &files {
+        let kind = match expected.get(name).and_then(|x| x.as_str()) {
+            Some(x) => x,
+            None => {
+                failures.push(format!("{}: no entry in {}", name, GOLDEN));
+                continue;
+            }
+        };
+        let kind: ContainerType = kind.parse().unwrap();
+        match ContainerType::from_file_path(&dir.join(name)) {
+            Ok(t) if t == kind => {}
+            t => failures.push(format!("{}: expected {:?}, got {:?}", name, kind, t)),
+        }
+    }
+    for name in expected.keys() {
+        if !files.contains(name) {
+            failures.push(format!("{}: listed in {} but missing", name, GOLDEN));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Samples written by `cargo run --example generate_samples --features samples`,
+/// named after what `ContainerType::from_str` accepts.
+#[test]
+fn test_generated_fixtures() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/generated");
+    let mut count = 0;
+    for path in fs::read_dir(&dir).unwrap().map(|x| x.unwrap().path()) {
+        let kind: ContainerType = path.file_stem().unwrap().to_str().unwrap().parse().unwrap();
+        let t = ContainerType::from_file_path(&path);
+        assert_eq!(t.ok(), Some(kind), "{}", path.display());
+        count += 1;
+    }
+    assert_eq!(count, media_infer::DEFAULT_PRIORITY.len());
+}
+
+/// Refinements of PS also match the PS check, strict mode still reports only them.
+#[test]
+fn test_strict_refinements() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let strict = Detector::builder().strict_ambiguity(true).build();
+    for (name, kind) in [
+        ("vob.vob", ContainerType::VOB),
+        ("cdxa.dat", ContainerType::CDXA),
+        ("vobsub.sub", ContainerType::VobSubPS),
+        ("tivo.ty", ContainerType::TivoPS),
+        ("ps.mpg", ContainerType::PS),
+    ] {
+        let buffer = fs::read(dir.join(name)).unwrap();
+        assert_eq!(strict.detect(&buffer).ok(), Some(kind), "{}", name);
+    }
+}
@@ -0,0 +1,127 @@
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use media_infer::{Confidence, ContainerType, Detector, Error};
+use std::io::Write;
+
+fn heuristic_detector() -> Detector {
+    Detector::builder()
+        .lenient(true)
+        .es_heuristics(true)
+        .build()
+}
+
+/// Deterministic pseudo random bytes.
+fn noise(len: usize, mut seed: u64) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as u8
+        })
+        .collect()
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// MPEG-2 video dump starting in the middle of a picture: no sequence header, and the
+/// first slice is row 12. Then 3 full pictures of 30 slice rows.
+fn mpeg2_mid_gop() -> Vec<u8> {
+    // Slice data without zero bytes, so it holds no start codes.
+    let payload = |seed| -> Vec<u8> { noise(200, seed).iter().map(|x| x | 0x01).collect() };
+    let mut buffer = payload(1)[..57].to_vec();
+    for picture in 0..4u8 {
+        if picture > 0 {
+            // Picture header (P picture), then the picture coding extension.
+            buffer.extend_from_slice(&[0, 0, 1, 0x00, (picture * 2) << 2, 0x51, 0xFF, 0xF8]);
+            buffer.extend_from_slice(&[0, 0, 1, 0xB5, 0x81, 0x1F, 0xF3, 0x41, 0x80]);
+        }
+        let first_row = if picture == 0 { 12 } else { 1 };
+        for row in first_row..=30u8 {
+            buffer.extend_from_slice(&[0, 0, 1, row]);
+            buffer.extend(payload(u64::from(picture) * 100 + u64::from(row)));
+        }
+    }
+    buffer
+}
+
+/// ZIP archive holding one deflated text file.
+fn zip() -> Vec<u8> {
+    let text: Vec<u8> = (0..20000)
+        .flat_map(|x| format!("line {} of the report, value {}\n", x, x * 7 % 13).into_bytes())
+        .collect();
+    let data = deflate(&text);
+    let name = b"report.txt";
+    let mut local = b"PK\x03\x04\x14\x00\x00\x00\x08\x00\x00\x00\x00\x00".to_vec();
+    local.extend_from_slice(&[0; 4]); // CRC-32, not checked here
+    local.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    local.extend_from_slice(&(text.len() as u32).to_le_bytes());
+    local.extend_from_slice(&(name.len() as u16).to_le_bytes());
+    local.extend_from_slice(&[0, 0]);
+    local.extend_from_slice(name);
+    local.extend_from_slice(&data);
+    let mut end = b"PK\x05\x06".to_vec();
+    end.extend_from_slice(&[0; 18]);
+    local.extend(end);
+    local
+}
+
+#[test]
+fn test_heuristic_mpeg2_mid_gop() {
+    let buffer = mpeg2_mid_gop();
+    let t = heuristic_detector().detect_with_confidence(&buffer);
+    assert_eq!(t.ok(), Some((ContainerType::ES, Confidence::Weak)));
+    let t = heuristic_detector().detect(&buffer);
+    assert_eq!(t.ok(), Some(ContainerType::ES));
+
+    // Opt in, and lenient mode only.
+    assert!(matches!(
+        Detector::default().detect(&buffer),
+        Err(Error::Unidentified)
+    ));
+    let t = Detector::builder()
+        .es_heuristics(true)
+        .build()
+        .detect(&buffer);
+    assert!(matches!(t, Err(Error::Unidentified)));
+    let t = Detector::builder()
+        .lenient(true)
+        .es_heuristics(true)
+        .formats(&[ContainerType::H264ES])
+        .build()
+        .detect(&buffer);
+    assert!(matches!(t, Err(Error::Unidentified)));
+}
+
+#[test]
+fn test_heuristic_strong() {
+    let buffer = [0, 0, 1, 0xB3, 0x2D, 0x01, 0xE0, 0x24];
+    let t = heuristic_detector().detect_with_confidence(&buffer);
+    assert_eq!(t.ok(), Some((ContainerType::ES, Confidence::Strong)));
+}
+
+#[test]
+fn test_heuristic_compressed_random() {
+    for seed in 0..8 {
+        let buffer = deflate(&noise(256 * 1024, seed));
+        let t = heuristic_detector().detect(&buffer);
+        assert!(matches!(t, Err(Error::Unidentified)), "seed {}", seed);
+    }
+}
+
+#[test]
+fn test_heuristic_zip() {
+    let buffer = zip();
+    let t = heuristic_detector().detect(&buffer);
+    assert!(matches!(t, Err(Error::NotMedia("ZIP archive"))));
+    let detector = Detector::builder()
+        .lenient(true)
+        .es_heuristics(true)
+        .non_media_check(false)
+        .build();
+    assert!(matches!(detector.detect(&buffer), Err(Error::Unidentified)));
+}
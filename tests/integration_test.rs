@@ -1,4 +1,12 @@
-use media_infer::ContainerType;
+use media_infer::{
+    dts_sync, es_version, ivf_codec, m2ts_profile, mcc_version, ogg_codec, tracker_format,
+    ContainerType, Detector, DtsSync, Error, EsVersion, IvfCodec, M2tsProfile, MccVersion,
+    OggCodec, TrackerFormat,
+};
+use std::fs;
+use std::io::{ErrorKind, Seek, SeekFrom, Write};
+use std::sync::Arc;
+use std::thread;
 
 #[test]
 fn test_empty() {
@@ -14,9 +22,20 @@ fn test_garbage() {
 
 #[test]
 fn test_asf_bytes() {
-    let buffer = [0x30, 0x26, 0xb2, 0x75, 0x34, 0];
+    // ASF_Header_Object GUID, object size and number of header objects.
+    let buffer = [
+        0x30, 0x26, 0xb2, 0x75, 0x8e, 0x66, 0xcf, 0x11, 0xa6, 0xd9, 0x00, 0xaa, 0x00, 0x62, 0xce,
+        0x6c, 0x9e, 0x13, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x01, 0x02,
+    ];
     let t = ContainerType::from_bytes(&buffer);
     assert_eq!(t, Ok(ContainerType::ASF));
+
+    let buffer = [
+        0x30, 0x26, 0xb2, 0x75, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
 }
 
 #[test]
@@ -25,9 +44,88 @@ fn test_mkv_bytes() {
     let t = ContainerType::from_bytes(&buffer);
     assert_eq!(t, Ok(ContainerType::MKV));
 
-    let buffer = [0x18, 0x53, 0x80, 0x67, 10];
+    // EBML header with DocType "matroska".
+    let buffer = [
+        0x1a, 0x45, 0xdf, 0xa3, 0x9f, 0x42, 0x86, 0x81, 0x01, 0x42, 0xf7, 0x81, 0x01, 0x42, 0xf2,
+        0x81, 0x04, 0x42, 0xf3, 0x81, 0x08, 0x42, 0x82, 0x88, b'm', b'a', b't', b'r', b'o', b's',
+        b'k', b'a',
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MKV));
+}
+
+#[test]
+fn test_mkv_fragment() {
+    let lenient = Detector::builder().lenient(true).build();
+
+    // Segment with unknown size followed by a SeekHead.
+    let buffer = [
+        0x18, 0x53, 0x80, 0x67, 0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x11, 0x4d, 0x9b,
+        0x74, 0x40, 0x3b,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
+    let t = lenient.detect(&buffer);
+    assert_eq!(t.ok(), Some(ContainerType::MKV));
+
+    // Segment ID followed by random bytes.
+    let buffer = [
+        0x18, 0x53, 0x80, 0x67, 0x5a, 0x13, 0xc7, 0x92, 0x04, 0xee, 0x71, 0x3d,
+    ];
     let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
+    let t = lenient.detect(&buffer);
+    assert!(t.is_err());
+}
+
+#[test]
+fn test_webm_file() {
+    for name in ["webm.webm", "webm_late_doctype.webm"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let t = ContainerType::from_file_path(path.as_ref());
+        assert_eq!(t.ok(), Some(ContainerType::WEBM), "{}", name);
+    }
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mkv.mkv");
+    let t = ContainerType::from_file_path(path.as_ref());
+    assert_eq!(t.ok(), Some(ContainerType::MKV));
+}
+
+#[test]
+fn test_webm_truncated() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/webm_late_doctype.webm"
+    );
+    let buffer = fs::read(path).unwrap();
+    // DocType cut off, only the EBML header magic tells.
+    let t = ContainerType::from_bytes(&buffer[..38]);
+    assert_eq!(t, Ok(ContainerType::MKV));
+    let t = ContainerType::from_bytes(&buffer[..40]);
+    assert_eq!(t, Ok(ContainerType::WEBM));
+    assert!(!ContainerType::MKV.matches(&buffer));
+    assert_eq!(
+        Detector::default().detect_all(&buffer),
+        [ContainerType::WEBM]
+    );
+}
+
+#[test]
+fn test_mka_file() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mka.mka");
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::MKA));
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::MKA]);
+    // Cut before Tracks, only the EBML header tells.
+    let t = ContainerType::from_bytes(&buffer[..0x5c]);
     assert_eq!(t, Ok(ContainerType::MKV));
+
+    // Audio track followed by a video track.
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/mixed_tracks.mkv"
+    );
+    let t = ContainerType::from_file_path(path.as_ref());
+    assert_eq!(t.ok(), Some(ContainerType::MKV));
 }
 
 #[test]
@@ -39,17 +137,40 @@ fn test_gxf_bytes() {
 
 #[test]
 fn test_wtv_bytes() {
-    let buffer = [0xb7, 0xd8, 0x00, 0x20, 0];
+    // Start of a Windows Media Center recording.
+    let buffer = [
+        0xb7, 0xd8, 0x00, 0x20, 0x37, 0x49, 0xda, 0x11, 0xa6, 0x4e, 0x00, 0x07, 0xe9, 0x5e, 0xad,
+        0x8d, 0x00, 0x10, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00,
+    ];
     let t = ContainerType::from_bytes(&buffer);
     assert_eq!(t, Ok(ContainerType::WTV));
+
+    let buffer = [
+        0xb7, 0xd8, 0x00, 0x20, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b,
+        0x0c, 0x0d,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
 }
 #[test]
 fn test_rcwt_bytes() {
-    let buffer = [0xCC, 0xCC, 0xED, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+    // Header as written by CCExtractor 0.50+, followed by a caption block.
+    let buffer = [
+        0xCC, 0xCC, 0xED, 0xCC, 0x00, 0x50, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
     let t = ContainerType::from_bytes(&buffer);
     assert_eq!(t, Ok(ContainerType::RCWT));
 }
 
+#[test]
+fn test_rcwt_future_version() {
+    let buffer = [
+        0xCC, 0xCC, 0xED, 0xCC, 0x00, 0x50, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
+}
+
 #[test]
 fn test_mp4_bytes() {
     let buffer = [
@@ -65,6 +186,215 @@ fn test_mp4_bytes() {
     assert_eq!(t, Ok(ContainerType::MP4));
 }
 
+#[test]
+fn test_3gp_bytes() {
+    // Start of a 3GPP recording from a phone: ftyp, then moov.
+    let buffer = [
+        0x00, 0x00, 0x00, 0x18, 0x66, 0x74, 0x79, 0x70, 0x33, 0x67, 0x70, 0x34, 0x00, 0x00, 0x02,
+        0x00, 0x69, 0x73, 0x6f, 0x6d, 0x33, 0x67, 0x70, 0x34, 0x00, 0x00, 0x1c, 0x6e, 0x6d, 0x6f,
+        0x6f, 0x76,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::ThreeGP));
+
+    let mut buffer = buffer;
+    buffer[8..12].copy_from_slice(b"3g2a");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::ThreeG2));
+}
+
+#[test]
+fn test_3gp_compatible_brand() {
+    // mp42 major brand, 3GPP only in the compatible brands.
+    let mut buffer = 28u32.to_be_bytes().to_vec();
+    buffer.extend_from_slice(b"ftypmp42");
+    buffer.extend_from_slice(&0u32.to_be_bytes());
+    buffer.extend_from_slice(b"mp42isom3gp6");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::ThreeGP));
+
+    // An isom major brand stays MP4.
+    buffer[8..12].copy_from_slice(b"isom");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MP4));
+}
+
+#[test]
+fn test_m4a_bytes() {
+    // iTunes audio: M4A major brand.
+    let mut buffer = 32u32.to_be_bytes().to_vec();
+    buffer.extend_from_slice(b"ftypM4A ");
+    buffer.extend_from_slice(&0x200u32.to_be_bytes());
+    buffer.extend_from_slice(b"M4A mp42isom\x00\x00\x00\x00");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::M4A));
+
+    buffer[8..12].copy_from_slice(b"M4B ");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::M4A));
+}
+
+#[test]
+fn test_m4a_compatible_brand() {
+    let mut buffer = 28u32.to_be_bytes().to_vec();
+    buffer.extend_from_slice(b"ftypisom");
+    buffer.extend_from_slice(&0x200u32.to_be_bytes());
+    buffer.extend_from_slice(b"isomiso2M4A ");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::M4A));
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::M4A]);
+
+    // Generic brands only.
+    buffer[24..28].copy_from_slice(b"mp41");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MP4));
+}
+
+/// ISO BMFF box of `kind` holding `body`.
+fn mp4_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = (8 + body.len() as u32).to_be_bytes().to_vec();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(body);
+    out
+}
+
+/// `moof` with its `mfhd` and an empty `traf`, then the start of the `mdat`.
+fn fragment() -> Vec<u8> {
+    let mfhd = mp4_box(b"mfhd", &[0, 0, 0, 0, 0, 0, 0, 7]);
+    let traf = mp4_box(b"traf", &mp4_box(b"tfhd", &[0, 0x02, 0, 0, 0, 0, 0, 1]));
+    let mut out = mp4_box(b"moof", &[mfhd, traf].concat());
+    out.extend_from_slice(&4096u32.to_be_bytes());
+    out.extend_from_slice(b"mdat");
+    out.resize(out.len() + 64, 0x5A);
+    out
+}
+
+#[test]
+fn test_fmp4_styp_first() {
+    let mut buffer = mp4_box(b"styp", b"msdh\x00\x00\x00\x00msdhmsix");
+    // Segment index with the reference count cut short.
+    buffer.extend(mp4_box(b"sidx", &[0; 24]));
+    buffer.extend(fragment());
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::FragmentedMP4));
+    assert_eq!("m4s".parse(), Ok(ContainerType::FragmentedMP4));
+}
+
+#[test]
+fn test_fmp4_moof_first() {
+    let buffer = fragment();
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::FragmentedMP4));
+
+    // moof without its mfhd.
+    let mut buffer = buffer;
+    buffer[12..16].copy_from_slice(b"traf");
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
+}
+
+#[test]
+fn test_fmp4_progressive() {
+    let mut buffer = mp4_box(b"ftyp", b"isom\x00\x00\x02\x00isomiso2mp41");
+    buffer.extend(mp4_box(b"moov", &mp4_box(b"mvhd", &[0; 100])));
+    buffer.extend(mp4_box(b"mdat", &[0x5A; 64]));
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::MP4]);
+}
+
+#[test]
+fn test_heif_brands() {
+    // iPhone photo: heic major brand, then meta.
+    let mut buffer = mp4_box(b"ftyp", b"heic\x00\x00\x00\x00mif1heicmiaf");
+    buffer.extend(mp4_box(b"meta", &[0; 32]));
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::HEIF]
+    );
+
+    buffer[8..12].copy_from_slice(b"avif");
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::AVIF]
+    );
+
+    buffer[8..12].copy_from_slice(b"avis");
+    let t = ContainerType::from_bytes_all(&buffer);
+    assert_eq!(t, [ContainerType::AVIFSequence]);
+}
+
+#[test]
+fn test_heif_generic_brand() {
+    // mif1 major brand, the compatible ones tell HEIF from AVIF.
+    let buffer = mp4_box(b"ftyp", b"mif1\x00\x00\x00\x00mif1miafavif");
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::AVIF]
+    );
+    let buffer = mp4_box(b"ftyp", b"msf1\x00\x00\x00\x00msf1miafavis");
+    let t = ContainerType::from_bytes_all(&buffer);
+    assert_eq!(t, [ContainerType::AVIFSequence]);
+    let buffer = mp4_box(b"ftyp", b"mif1\x00\x00\x00\x00mif1heic");
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::HEIF]
+    );
+}
+
+#[test]
+fn test_heif_not_mp4() {
+    // An isom video listing image brands stays MP4.
+    let buffer = mp4_box(b"ftyp", b"isom\x00\x00\x02\x00isomavifheic");
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::MP4]);
+
+    // Image brands win over a 3GPP compatible brand.
+    let buffer = mp4_box(b"ftyp", b"heic\x00\x00\x00\x00heic3gp6");
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::HEIF]
+    );
+}
+
+#[test]
+fn test_mov_brand() {
+    // ftyp with the qt major brand, minor version and compatible brand.
+    let mut buffer = b"\x00\x00\x00\x14ftypqt  \x20\x05\x03\x00qt  ".to_vec();
+    buffer.extend_from_slice(b"\x00\x00\x00\x08wide");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MOV));
+}
+
+#[test]
+fn test_mov_mdat_first() {
+    let mut buffer = 64u32.to_be_bytes().to_vec();
+    buffer.extend_from_slice(b"mdat");
+    buffer.resize(64, 0xA5);
+    buffer.extend_from_slice(&1000u32.to_be_bytes());
+    buffer.extend_from_slice(b"moov");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MOV));
+
+    // The atom after mdat is not an atom.
+    buffer[68..72].copy_from_slice(&[0xFF, 0x00, 0x13, 0x80]);
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
+}
+
+#[test]
+fn test_mov_extended_size() {
+    // mdat with a 64 bit size, larger than 4 GiB.
+    let mut buffer = 1u32.to_be_bytes().to_vec();
+    buffer.extend_from_slice(b"mdat");
+    buffer.extend_from_slice(&0x1_2345_6789u64.to_be_bytes());
+    buffer.resize(256, 0);
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MOV));
+
+    // Extended size smaller than the extended header.
+    buffer[8..16].copy_from_slice(&8u64.to_be_bytes());
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
+}
+
 #[test]
 fn test_mxf_bytes() {
     let buffer = [
@@ -80,6 +410,7 @@ fn test_ts_bytes() {
     let mut buffer = [0; 192 * 9];
     for i in 0..8 {
         buffer[2 + i * 188] = 0x47;
+        buffer[2 + i * 188 + 3] = 0x10;
     }
     let t = ContainerType::from_bytes(&buffer);
     assert_eq!(t, Ok(ContainerType::TS));
@@ -90,11 +421,74 @@ fn test_m2ts_bytes() {
     let mut buffer = [0; 192 * 9];
     for i in 0..8 {
         buffer[2 + 4 + i * 192] = 0x47;
+        buffer[2 + 4 + i * 192 + 3] = 0x10;
     }
     let t = ContainerType::from_bytes(&buffer);
     assert_eq!(t, Ok(ContainerType::M2TS));
 }
 
+#[test]
+fn test_m2ts_profile() {
+    // AVCHD camcorder clip, copy permission 01 and a PAT first.
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/m2ts.m2ts");
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Bdav));
+
+    // Blu-ray stream file, copy permission 00, PAT and PMT then video packets.
+    let mut buffer = vec![0xFF; 192 * 16];
+    for (i, packet) in buffer.chunks_mut(192).enumerate() {
+        let ats = 0x0123_4567 + 1800 * i as u32;
+        packet[..4].copy_from_slice(&ats.to_be_bytes());
+        let pid: u16 = match i {
+            0 => 0x0000,
+            1 => 0x0100,
+            _ => 0x1011,
+        };
+        packet[4..8].copy_from_slice(&[0x47, 0x40 | (pid >> 8) as u8, pid as u8, 0x10]);
+    }
+    buffer[8..11].copy_from_slice(&[0x00, 0x00, 0xB0]);
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::M2TS));
+    assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Bdav));
+
+    // Same packets with random arrival timestamps.
+    let mut state = 0x2545_F491_u32;
+    for packet in buffer.chunks_mut(192) {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        packet[..4].copy_from_slice(&state.to_be_bytes());
+    }
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::M2TS));
+    assert_eq!(m2ts_profile(&buffer), Some(M2tsProfile::Generic));
+
+    let mut buffer = [0; 188 * 9];
+    for i in 0..8 {
+        buffer[i * 188..i * 188 + 4].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+    }
+    assert_eq!(m2ts_profile(&buffer), None);
+}
+
+#[test]
+fn test_ts_m2ts_both_strides() {
+    // Packets line up on both the 188 and 192 byte strides, but only the 192 byte
+    // stride carries ascending arrival timestamps.
+    let mut buffer = vec![0; 192 * 8 + 8];
+    for i in 0..8 {
+        let ats = 0x4000_0000 | (27_000 * i as u32);
+        buffer[i * 192..i * 192 + 4].copy_from_slice(&ats.to_be_bytes());
+        buffer[i * 192 + 4..i * 192 + 8].copy_from_slice(&[0x47, 0x40, 0x00, 0x10]);
+        buffer[100 + i * 188..100 + i * 188 + 4].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+    }
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::M2TS));
+}
+
+#[test]
+fn test_ts_text() {
+    let t = ContainerType::from_bytes(&[b'G'; 2048]);
+    assert!(t.is_err());
+}
+
 #[test]
 fn test_ps_bytes() {
     let buffer = [0, 0, 0x00, 0x00, 0x01, 0xBA, 0, 0];
@@ -115,9 +509,1763 @@ fn test_tivo_ps() {
     assert_eq!(t, Ok(ContainerType::TivoPS));
 }
 
+#[test]
+fn test_tivo_ps_with_pack_header() {
+    // TiVo header followed by the MPEG-2 program stream it wraps.
+    let mut buffer = vec![0; 4096];
+    buffer[0..4].copy_from_slice(b"TiVo");
+    buffer[4..8].copy_from_slice(&[0x00, 0x04, 0x00, 0x07]);
+    buffer[2048..2052].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::TivoPS));
+}
+
+#[test]
+fn test_gxf_with_pack_header() {
+    let mut buffer = vec![0; 4096];
+    buffer[0..6].copy_from_slice(&[0, 0, 0, 0, 1, 0xbc]);
+    buffer[1024..1028].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::GXF));
+}
+
 #[test]
 fn test_es_bytes() {
     let buffer = [0, 0, 1, 0xB3, 0, 0];
     let t = ContainerType::from_bytes(&buffer);
     assert_eq!(t, Ok(ContainerType::ES));
+    // Too short to tell the generation.
+    assert_eq!(es_version(&buffer), None);
+}
+
+#[test]
+fn test_es_version() {
+    // VCD: 352x240 sequence header, then a GOP header and the first picture.
+    let mpeg1 = [
+        0x00, 0x00, 0x01, 0xb3, 0x16, 0x00, 0xf0, 0xc4, 0x02, 0xce, 0xe0, 0xa4, 0x00, 0x00, 0x01,
+        0xb8, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x0f, 0xff, 0xf8,
+    ];
+    let t = ContainerType::from_bytes(&mpeg1);
+    assert_eq!(t, Ok(ContainerType::ES));
+    assert_eq!(es_version(&mpeg1), Some(EsVersion::Mpeg1));
+
+    // DVD: 720x576 sequence header, then its sequence extension (Main@Main).
+    let mpeg2 = [
+        0x00, 0x00, 0x01, 0xb3, 0x2d, 0x02, 0x40, 0x33, 0x17, 0xed, 0x23, 0x80, 0x00, 0x00, 0x01,
+        0xb5, 0x14, 0x8a, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0xb8, 0x00, 0x08, 0x00, 0x00,
+    ];
+    let t = ContainerType::from_bytes(&mpeg2);
+    assert_eq!(t, Ok(ContainerType::ES));
+    assert_eq!(es_version(&mpeg2), Some(EsVersion::Mpeg2));
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/es.m2v");
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(es_version(&buffer), Some(EsVersion::Mpeg2));
+}
+
+#[test]
+fn test_h264_es_bytes() {
+    // SPS, PPS, then an IDR slice.
+    let mut buffer = vec![
+        0x00, 0x00, 0x00, 0x01, 0x67, 0x42, 0xc0, 0x1e, 0xd9, 0x00, 0xb0, 0x4b, 0x10, 0x00, 0x00,
+        0x03, 0x00, 0x10, 0x00, 0x00, 0x03, 0x03, 0xc8, 0xf1, 0x62, 0xe4, 0x80, 0x00, 0x00, 0x00,
+        0x01, 0x68, 0xcb, 0x83, 0xcb, 0x20, 0x00, 0x00, 0x01, 0x65, 0x88, 0x84, 0x00, 0x2b, 0xff,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::H264ES));
+
+    // Zero padding in front.
+    buffer.splice(0..0, vec![0; 2000]);
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::H264ES));
+
+    // Access unit delimiter first.
+    let t = ContainerType::from_bytes(&[0, 0, 0, 1, 0x09, 0xf0, 0, 0, 0, 1, 0x67, 0x42]);
+    assert_eq!(t, Ok(ContainerType::H264ES));
+}
+
+#[test]
+fn test_h265_es_bytes() {
+    // VPS, SPS and PPS of a Main profile stream, behind zero padding.
+    let mut buffer = vec![0; 300];
+    buffer.extend_from_slice(&[
+        0x00, 0x00, 0x00, 0x01, 0x40, 0x01, 0x0c, 0x01, 0xff, 0xff, 0x01, 0x60, 0x00, 0x00, 0x03,
+        0x00, 0x90, 0x00, 0x00, 0x01, 0x42, 0x01, 0x01, 0x01, 0x60, 0x00, 0x00, 0x03, 0x00, 0x90,
+        0x00, 0x00, 0x01, 0x44, 0x01, 0xc1, 0x72, 0xb4, 0x62, 0x40,
+    ]);
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::H265ES));
+    assert_eq!("h265".parse(), Ok(ContainerType::H265ES));
+}
+
+#[test]
+fn test_mpeg4_es_bytes() {
+    // Xvid: VOS (Advanced Simple, level 5), VO, VO 0, VOL, then user data.
+    let buffer = [
+        0x00, 0x00, 0x01, 0xb0, 0xf5, 0x00, 0x00, 0x01, 0xb5, 0x89, 0x13, 0x00, 0x00, 0x01, 0x00,
+        0x00, 0x00, 0x01, 0x20, 0x00, 0xc4, 0x8d, 0x88, 0x00, 0xcd, 0x16, 0x04, 0x1e, 0x14, 0x43,
+        0x00, 0x00, 0x01, 0xb2, 0x58, 0x76, 0x69, 0x44,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MPEG4ES));
+    assert_eq!("m4v-es".parse(), Ok(ContainerType::MPEG4ES));
+}
+
+#[test]
+fn test_mpeg4_es_vop_only() {
+    // Dump starting at a P-VOP, zero padded, then the next VOP.
+    let mut buffer = vec![0; 16];
+    buffer.extend_from_slice(&[0x00, 0x00, 0x01, 0xb6, 0x51, 0x8e, 0x2f, 0x91, 0x40, 0x33]);
+    buffer.extend_from_slice(&[0x00, 0x00, 0x01, 0xb6, 0x52, 0x0c, 0x7f, 0x11, 0x20, 0x81]);
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::MPEG4ES));
+}
+
+#[test]
+fn test_mpeg4_es_not_mpeg2() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/es.m2v");
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::ES]);
+}
+
+#[test]
+fn test_av1_obu_bytes() {
+    // ffmpeg -f obu: temporal delimiter, sequence header, then a frame OBU.
+    let buffer = [
+        0x12, 0x00, 0x0a, 0x0b, 0x00, 0x00, 0x00, 0x42, 0x22, 0xbf, 0x1f, 0x7f, 0xfe, 0x60, 0x10,
+        0x32, 0xe0, 0x12, 0x10, 0x00, 0x80, 0x04,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::AV1OBU));
+    assert_eq!("obu".parse(), Ok(ContainerType::AV1OBU));
+
+    // Cut inside the temporal delimiter.
+    assert!(ContainerType::from_bytes(&buffer[..2]).is_err());
+}
+
+#[test]
+fn test_av1_obu_random() {
+    for seed in 1..64u64 {
+        let mut state = seed;
+        let noise: Vec<u8> = (0..1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        let t = ContainerType::from_bytes_all(&noise);
+        assert!(!t.contains(&ContainerType::AV1OBU), "seed {}", seed);
+        // Behind a temporal delimiter.
+        let buffer = [&[0x12, 0x00][..], &noise].concat();
+        let t = ContainerType::from_bytes_all(&buffer);
+        assert!(!t.contains(&ContainerType::AV1OBU), "seed {}", seed);
+    }
+}
+
+/// ADTS header of an AAC LC frame, 44.1 kHz stereo, `len` bytes long.
+fn adts_header(len: usize) -> [u8; 7] {
+    [
+        0xFF,
+        0xF1,
+        0x50,
+        0x80 | (len >> 11) as u8 & 0x03,
+        (len >> 3) as u8,
+        (len as u8 & 0x07) << 5 | 0x1F,
+        0xFC,
+    ]
+}
+
+#[test]
+fn test_aac_adts_bytes() {
+    let frame = [&adts_header(13)[..], &[0x21, 0x10, 0x04, 0x60, 0x8C, 0x1C]].concat();
+    let buffer = [&frame[..], &frame].concat();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::AACADTS)
+    );
+    // Behind an ID3v2 tag with 32 bytes of padding.
+    let tagged = [&b"ID3\x04\x00\x00\x00\x00\x00\x20"[..], &[0; 32], &buffer].concat();
+    assert_eq!(
+        ContainerType::from_bytes(&tagged),
+        Ok(ContainerType::AACADTS)
+    );
+    // A single frame is not enough.
+    assert!(ContainerType::from_bytes(&frame).is_err());
+    // Nor is a second sync word at the wrong distance.
+    let buffer = [&frame[..], &[0], &frame].concat();
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_aac_adts_not_mp3() {
+    // MPEG-1 Layer III, 128 kbit/s at 44.1 kHz: 417 byte frames with the same sync word.
+    let mut frame = vec![0; 417];
+    frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+    let buffer = [&b"ID3\x03\x00\x00\x00\x00\x00\x00"[..], &frame, &frame].concat();
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::MP3]);
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer[10..]),
+        [ContainerType::MP3]
+    );
+}
+
+#[test]
+fn test_aac_latm_bytes() {
+    let frame = [0x56, 0xE0, 0x07, 0x8C, 0x42, 0x20, 0x08, 0xC1, 0x18, 0x38];
+    let buffer = [frame, frame].concat();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::AACLATM)
+    );
+    assert!(ContainerType::from_bytes(&frame).is_err());
+    let buffer = [&frame[..], &[0x56], &frame].concat();
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_aac_adts_latm_distinct() {
+    for (name, kind) in [
+        ("aac.aac", ContainerType::AACADTS),
+        ("aac_id3.aac", ContainerType::AACADTS),
+        ("aac_latm.loas", ContainerType::AACLATM),
+    ] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(ContainerType::from_bytes_all(&buffer), [kind], "{}", name);
+    }
+}
+
+#[test]
+fn test_mp3_bytes() {
+    // MPEG-1 Layer III, 128 kbit/s at 44.1 kHz: 417 byte frames.
+    let mut frame = vec![0; 417];
+    frame[..4].copy_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+    let buffer = [&frame[..], &frame].concat();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::MP3));
+    // A lone frame sync is not enough.
+    assert!(ContainerType::from_bytes(&frame).is_err());
+    let buffer = [&frame[..], &[0xFF], &frame].concat();
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_mp3_id3_only() {
+    // A tag with 4 KiB of padding, longer than what is read. AAC is tagged too, so
+    // nothing is known before the first frame.
+    let tag = [&b"ID3\x04\x00\x00\x00\x00\x20\x00"[..], &[0; 1024]].concat();
+    assert!(matches!(ContainerType::try_from_bytes(&tag), Ok(None)));
+    let path = format!("{}/tests/fixtures/mp3_id3.mp3", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::MP3]);
+}
+
+#[test]
+fn test_mp3_not_aac() {
+    for name in ["aac.aac", "aac_id3.aac"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes_all(&buffer),
+            [ContainerType::AACADTS],
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_h264_h265_es_distinct() {
+    for (name, kind) in [
+        ("h264.264", ContainerType::H264ES),
+        ("h264_padded.h264", ContainerType::H264ES),
+        ("h265.hevc", ContainerType::H265ES),
+    ] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(ContainerType::from_bytes_all(&buffer), [kind], "{}", name);
+    }
+}
+
+#[test]
+fn test_h264_es_no_start_code() {
+    // Pseudo random bytes without zeros, so without start codes.
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    let buffer: Vec<u8> = (0..8192)
+        .map(|_| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed as u8).max(1)
+        })
+        .collect();
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+
+    let mut padded = vec![0; 64];
+    padded.extend(buffer);
+    assert!(ContainerType::from_bytes(&padded).is_err());
+}
+
+#[test]
+fn test_avi_bytes() {
+    // Start of an AVI written by ffmpeg: RIFF header, then the hdrl list and avih chunk.
+    let buffer = [
+        0x52, 0x49, 0x46, 0x46, 0x2c, 0x1f, 0x05, 0x00, 0x41, 0x56, 0x49, 0x20, 0x4c, 0x49, 0x53,
+        0x54, 0xec, 0x11, 0x00, 0x00, 0x68, 0x64, 0x72, 0x6c, 0x61, 0x76, 0x69, 0x68, 0x38, 0x00,
+        0x00, 0x00, 0x40, 0x9c, 0x00, 0x00,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::AVI));
+}
+
+#[test]
+fn test_avi_truncated() {
+    let buffer = [0x52, 0x49, 0x46, 0x46, 0x2c, 0x1f, 0x05, 0x00];
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&buffer).unwrap();
+    let t = ContainerType::from_file(&mut file);
+    assert!(matches!(
+        t,
+        Err(Error::FileTooSmall {
+            len: 8,
+            min_needed: 12
+        })
+    ));
+}
+
+#[test]
+fn test_riff_not_avi() {
+    // CDXA header of a Video CD track.
+    let mut buffer = [
+        0x52, 0x49, 0x46, 0x46, 0x24, 0x08, 0x00, 0x00, 0x43, 0x44, 0x58, 0x41, 0x66, 0x6d, 0x74,
+        0x20, 0x10, 0x00, 0x00, 0x00,
+    ];
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::CDXA]
+    );
+    // WebP image.
+    buffer[8..16].copy_from_slice(b"WEBPVP8 ");
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_wav_bytes() {
+    let buffer = [
+        0x52, 0x49, 0x46, 0x46, 0x24, 0x08, 0x00, 0x00, 0x57, 0x41, 0x56, 0x45, 0x66, 0x6d, 0x74,
+        0x20, 0x10, 0x00, 0x00, 0x00,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::WAV));
+    assert_eq!("wav".parse(), Ok(ContainerType::WAV));
+    assert_eq!(ContainerType::WAV.to_string(), "Waveform Audio (WAV)");
+
+    // AVI form type.
+    let mut buffer = buffer;
+    buffer[8..12].copy_from_slice(b"AVI ");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::AVI));
+    assert!(!ContainerType::WAV.matches(&buffer));
+    assert!(ContainerType::from_bytes(&buffer[..11]).is_err());
+}
+
+#[test]
+fn test_rf64_w64_bytes() {
+    let path = format!("{}/tests/fixtures/rf64.wav", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::RF64]
+    );
+    assert_eq!("rf64".parse(), Ok(ContainerType::RF64));
+    // Without the `ds64` chunk.
+    let mut t = buffer.clone();
+    t[12..16].copy_from_slice(b"fmt ");
+    assert!(ContainerType::from_bytes(&t).is_err());
+    // A classic RIFF header in front of the same chunks is WAV.
+    let mut t = buffer;
+    t[0..4].copy_from_slice(b"RIFF");
+    t[4..8].copy_from_slice(&1000u32.to_le_bytes());
+    assert_eq!(ContainerType::from_bytes_all(&t), [ContainerType::WAV]);
+
+    let path = format!("{}/tests/fixtures/w64.w64", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::W64]);
+    assert_eq!("w64".parse(), Ok(ContainerType::W64));
+    // Only the fourcc part of the GUID.
+    for pos in [4, 15] {
+        let mut t = buffer.clone();
+        t[pos] ^= 0x01;
+        assert!(ContainerType::from_bytes(&t).is_err(), "{}", pos);
+    }
+    assert!(ContainerType::from_bytes(&buffer[..15]).is_err());
+}
+
+#[test]
+fn test_aiff_bytes() {
+    // FORM header of an AIFF file, then the start of its COMM chunk.
+    let mut buffer = *b"FORM\x00\x00\x08\x2eAIFFCOMM\x00\x00\x00\x12";
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::AIFF));
+    for name in ["aiff", "aif"] {
+        assert_eq!(name.parse(), Ok(ContainerType::AIFF));
+    }
+    assert_eq!(
+        ContainerType::AIFF.to_string(),
+        "Audio Interchange File Format (AIFF)"
+    );
+
+    buffer[8..12].copy_from_slice(b"AIFC");
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::AIFF));
+
+    // Other IFF forms, DFF and a size too small for a chunk.
+    for (pos, bytes) in [(8, b"8SVX"), (8, b"\x00\x01\x02\x03"), (0, b"FRM8")] {
+        let mut t = buffer;
+        t[pos..pos + 4].copy_from_slice(bytes);
+        assert!(ContainerType::from_bytes(&t).is_err(), "{:?}", t);
+    }
+    buffer[4..8].copy_from_slice(&4u32.to_be_bytes());
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_au_bytes() {
+    let path = format!("{}/tests/fixtures/mulaw.au", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::AU));
+    for name in ["au", "snd"] {
+        assert_eq!(name.parse(), Ok(ContainerType::AU));
+    }
+
+    // Encoding 0 and 28 are not defined.
+    for encoding in [0u32, 28] {
+        let mut t = buffer.clone();
+        t[12..16].copy_from_slice(&encoding.to_be_bytes());
+        assert!(ContainerType::from_bytes(&t).is_err(), "{}", encoding);
+    }
+    // Text.
+    assert!(ContainerType::from_bytes(b".snd files are Sun audio").is_err());
+}
+
+#[test]
+fn test_amr_bytes() {
+    // Magic, then a 12.2 kbit/s narrowband frame header.
+    let t = ContainerType::from_bytes(b"#!AMR\n\x3c\x00");
+    assert_eq!(t, Ok(ContainerType::AMR));
+    // Magic, then a 23.85 kbit/s wideband frame header.
+    let buffer = b"#!AMR-WB\n\x44\x00";
+    assert_eq!(ContainerType::from_bytes(buffer), Ok(ContainerType::AMRWB));
+    assert_eq!(
+        ContainerType::from_bytes_all(buffer),
+        [ContainerType::AMRWB]
+    );
+    assert_eq!("amr".parse(), Ok(ContainerType::AMR));
+    assert_eq!("awb".parse(), Ok(ContainerType::AMRWB));
+
+    // Cut inside the shared prefix, or between the two magics.
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cut.amr");
+    for (buffer, min_needed) in [(&b"#!AMR"[..], 6), (b"#!AMR-W", 9)] {
+        assert!(matches!(ContainerType::try_from_bytes(buffer), Ok(None)));
+        fs::write(&path, buffer).unwrap();
+        let t = ContainerType::from_file_path(&path);
+        let len = buffer.len() as u64;
+        assert!(
+            matches!(t, Err(Error::FileTooSmall { len: x, min_needed: y }) if x == len && y == min_needed),
+            "{:?}",
+            t
+        );
+    }
+    // Multichannel and other suffixes.
+    assert!(ContainerType::from_bytes(b"#!AMR_MC1.0\n").is_err());
+}
+
+#[test]
+fn test_caf_bytes() {
+    let path = format!("{}/tests/fixtures/caf.caf", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::CAF));
+    assert_eq!("caf".parse(), Ok(ContainerType::CAF));
+    assert_eq!(ContainerType::CAF.to_string(), "Core Audio Format (CAF)");
+
+    // File version 2, nonzero flags, another first chunk.
+    for (pos, byte) in [(5, 2), (7, 1), (8, b'd' + 1)] {
+        let mut t = buffer.clone();
+        t[pos] = byte;
+        assert!(ContainerType::from_bytes(&t).is_err(), "{}", pos);
+    }
+}
+
+#[test]
+fn test_wavpack_bytes() {
+    let path = format!("{}/tests/fixtures/wavpack.wv", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::WavPack)
+    );
+    assert_eq!("wv".parse(), Ok(ContainerType::WavPack));
+
+    // Versions before 4.02 and after 4.16.
+    for version in [0x401u16, 0x411, 0x0100] {
+        let mut t = buffer.clone();
+        t[8..10].copy_from_slice(&version.to_le_bytes());
+        assert!(ContainerType::from_bytes(&t).is_err(), "{:x}", version);
+    }
+    assert!(ContainerType::from_bytes(b"wvpk is the WavPack block id").is_err());
+}
+
+#[test]
+fn test_ape_bytes() {
+    let path = format!("{}/tests/fixtures/ape.ape", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::APE));
+    assert_eq!("ape".parse(), Ok(ContainerType::APE));
+
+    // Versions before 3.80 and past 4.00.
+    for version in [3790u16, 4010, 0] {
+        let mut t = buffer.clone();
+        t[4..6].copy_from_slice(&version.to_le_bytes());
+        assert!(ContainerType::from_bytes(&t).is_err(), "{}", version);
+    }
+}
+
+#[test]
+fn test_ape_mac_address() {
+    for text in [
+        &b"MAC address: 00:1a:2b:3c:4d:5e"[..],
+        b"MAC Address Table",
+        b"MAC 3990",
+    ] {
+        assert!(ContainerType::from_bytes(text).is_err(), "{:?}", text);
+    }
+}
+
+#[test]
+fn test_musepack_bytes() {
+    for name in ["musepack_sv7.mpc", "musepack_sv8.mpc"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::Musepack),
+            "{}",
+            name
+        );
+    }
+    assert_eq!("mpc".parse(), Ok(ContainerType::Musepack));
+    assert_eq!(
+        ContainerType::from_bytes(b"MP+\x07"),
+        Ok(ContainerType::Musepack)
+    );
+}
+
+#[test]
+fn test_musepack_bad_version() {
+    // SV4 to SV6 and SV8 have no `MP+` header, the high nibble is not the major version.
+    for byte in [0x06u8, 0x08, 0x70, 0x0F] {
+        let buffer = [b'M', b'P', b'+', byte, 0, 0, 0, 0];
+        assert!(ContainerType::from_bytes(&buffer).is_err(), "{:x}", byte);
+    }
+    assert!(ContainerType::from_bytes(b"MPCK is no packet key").is_err());
+}
+
+#[test]
+fn test_tta_bytes() {
+    let path = format!("{}/tests/fixtures/tta.tta", env!("CARGO_MANIFEST_DIR"));
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::TTA));
+    assert_eq!("tta".parse(), Ok(ContainerType::TTA));
+
+    // Encrypted.
+    buffer[4] = 2;
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::TTA));
+    for format in [0u16, 3, 0x0100] {
+        buffer[4..6].copy_from_slice(&format.to_le_bytes());
+        assert!(ContainerType::from_bytes(&buffer).is_err(), "{}", format);
+    }
+}
+
+#[test]
+fn test_shorten_bytes() {
+    let path = format!("{}/tests/fixtures/shorten.shn", env!("CARGO_MANIFEST_DIR"));
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::Shorten)
+    );
+    assert_eq!("shn".parse(), Ok(ContainerType::Shorten));
+
+    for version in [4u8, 200] {
+        buffer[4] = version;
+        assert!(ContainerType::from_bytes(&buffer).is_err(), "{}", version);
+    }
+}
+
+#[test]
+fn test_midi_bytes() {
+    let path = format!("{}/tests/fixtures/midi.mid", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::MIDI));
+    assert_eq!("mid".parse(), Ok(ContainerType::MIDI));
+    assert_eq!("midi".parse(), Ok(ContainerType::MIDI));
+
+    // Header chunk longer than 6 bytes, format 3.
+    let mut t = buffer.clone();
+    t[7] = 7;
+    assert!(ContainerType::from_bytes(&t).is_err());
+    let mut t = buffer.clone();
+    t[9] = 3;
+    assert!(ContainerType::from_bytes(&t).is_err());
+    assert!(ContainerType::from_bytes(b"MThd is a chunk id").is_err());
+
+    // Only the chunk id.
+    assert!(matches!(ContainerType::try_from_bytes(b"MThd"), Ok(None)));
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("cut.mid");
+    fs::write(&path, b"MThd").unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert!(
+        matches!(
+            t,
+            Err(Error::FileTooSmall {
+                len: 4,
+                min_needed: 10
+            })
+        ),
+        "{:?}",
+        t
+    );
+}
+
+#[test]
+fn test_dsf_bytes() {
+    let path = format!("{}/tests/fixtures/dsf.dsf", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::DSF));
+    assert_eq!("dsf".parse(), Ok(ContainerType::DSF));
+
+    let mut t = buffer.clone();
+    t[4] = 52;
+    assert!(ContainerType::from_bytes(&t).is_err());
+    let mut t = buffer;
+    t[28..32].copy_from_slice(b"data");
+    assert!(ContainerType::from_bytes(&t).is_err());
+}
+
+#[test]
+fn test_dsf_not_dff() {
+    // DSDIFF: `FRM8`, a big endian size, then the `DSD ` form type and `FVER` chunk.
+    let mut buffer = b"FRM8".to_vec();
+    buffer.extend_from_slice(&4096u64.to_be_bytes());
+    buffer.extend_from_slice(b"DSD FVER");
+    buffer.extend_from_slice(&4u64.to_be_bytes());
+    buffer.extend_from_slice(&[0x01, 0x05, 0x00, 0x00]);
+    buffer.extend_from_slice(b"PROP");
+    buffer.extend_from_slice(&[0; 12]);
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::DFF]);
+    // And the DSF check does not look past offset 0 for its chunk.
+    assert!(!ContainerType::from_bytes_all(&buffer[12..]).contains(&ContainerType::DSF));
+}
+
+#[test]
+fn test_dff_bytes() {
+    let path = format!("{}/tests/fixtures/dff.dff", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::DFF));
+    assert_eq!("dff".parse(), Ok(ContainerType::DFF));
+
+    // Other form types, and an AIFF form type behind `FRM8`.
+    for form in [b"DST ", b"AIFF"] {
+        let mut t = buffer.clone();
+        t[12..16].copy_from_slice(form);
+        assert!(ContainerType::from_bytes(&t).is_err());
+    }
+    // The DSD form type behind `FORM` is not AIFF either.
+    let mut t = buffer;
+    t[0..4].copy_from_slice(b"FORM");
+    assert!(ContainerType::from_bytes(&t).is_err());
+}
+
+#[test]
+fn test_tracker_module_bytes() {
+    for (name, format) in [
+        ("mod.mod", TrackerFormat::Mod),
+        ("xm.xm", TrackerFormat::Xm),
+        ("s3m.s3m", TrackerFormat::S3m),
+        ("it.it", TrackerFormat::It),
+    ] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::TrackerModule),
+            "{}",
+            name
+        );
+        assert_eq!(tracker_format(&buffer), Some(format), "{}", name);
+    }
+    for name in ["mod", "xm", "s3m", "it"] {
+        assert_eq!(name.parse(), Ok(ContainerType::TrackerModule));
+    }
+}
+
+#[test]
+fn test_tracker_module_short_mod() {
+    // The MOD tag is at offset 1080, a 500 byte buffer cannot reach it.
+    let path = format!("{}/tests/fixtures/mod.mod", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(tracker_format(&buffer[..500]), None);
+    assert!(!ContainerType::TrackerModule.matches(&buffer[..500]));
+    assert!(ContainerType::from_bytes(&buffer[..500]).is_err());
+    assert!(!ContainerType::TrackerModule.matches(&buffer[..1083]));
+    assert!(ContainerType::TrackerModule.matches(&buffer[..1084]));
+}
+
+#[test]
+fn test_webvtt_bytes() {
+    for buffer in [
+        &b"WEBVTT"[..],
+        b"WEBVTT\n\n00:00.000 --> 00:01.000\nHi\n",
+        b"WEBVTT\r\n",
+        b"WEBVTT\tKind: captions\n",
+        b"\xEF\xBB\xBFWEBVTT - Title\n",
+    ] {
+        assert_eq!(
+            ContainerType::from_bytes(buffer),
+            Ok(ContainerType::WebVTT),
+            "{:?}",
+            buffer
+        );
+    }
+    assert_eq!("vtt".parse(), Ok(ContainerType::WebVTT));
+
+    for buffer in [
+        &b"WEBVTTX\n"[..],
+        b"\xEF\xBBWEBVTT\n",
+        b"1\n00:00:00,000 --> 00:00:01,000\nWEBVTT\n",
+        b"NOTE WEBVTT\n",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+    }
+}
+
+#[test]
+fn test_scc_bytes() {
+    for name in ["scc_df.scc", "scc_ndf.scc"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::SCC),
+            "{}",
+            name
+        );
+    }
+    assert_eq!("scc".parse(), Ok(ContainerType::SCC));
+    // Cut in the header line's line break, or in the timecode.
+    for buffer in [
+        &b"Scenarist_SCC V1.0\r"[..],
+        b"Scenarist_SCC V1.0\n\n00:00:0",
+    ] {
+        assert_eq!(ContainerType::from_bytes(buffer), Ok(ContainerType::SCC));
+    }
+
+    for buffer in [
+        &b"Scenarist is a DVD authoring tool, its caption files are SCC files.\n"[..],
+        b"Scenarist_SCC V1.0\n\nThis file was renamed.\n",
+        b"Scenarist_SCC V1.0\n\n00-00-00-00\t942c\n",
+        b"Scenarist_SCC V1.01\n",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+    }
+}
+
+#[test]
+fn test_mcc_bytes() {
+    for (name, version) in [
+        ("mcc_v1.mcc", MccVersion::V1),
+        ("mcc_v2.mcc", MccVersion::V2),
+    ] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::MCC),
+            "{}",
+            name
+        );
+        assert_eq!(mcc_version(&buffer), Some(version), "{}", name);
+    }
+    assert_eq!("mcc".parse(), Ok(ContainerType::MCC));
+
+    for buffer in [
+        &b"File Format=\r\n\r\nTime Code Rate=30DF\r\n"[..],
+        b"File Format=MacCaption_MCC\r\n",
+        b"File Format=SubRip V1.0\r\n\r\n",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+        assert_eq!(mcc_version(buffer), None);
+    }
+}
+
+#[test]
+fn test_ttml_bytes() {
+    for name in ["dfxp.dfxp", "ttml2.ttml"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::TTML),
+            "{}",
+            name
+        );
+    }
+    assert_eq!("ttml".parse(), Ok(ContainerType::TTML));
+    assert_eq!("dfxp".parse(), Ok(ContainerType::TTML));
+
+    for buffer in [
+        // SMIL, SVG and a `tt` root of some other vocabulary.
+        &b"<?xml version=\"1.0\"?>\n<smil xmlns=\"http://www.w3.org/ns/SMIL\"><body/></smil>"[..],
+        b"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\" height=\"10\"/>",
+        b"<tt xmlns=\"urn:example:teletype\"><line/></tt>",
+        // The namespace is only declared further down.
+        b"<tt><body xmlns=\"http://www.w3.org/ns/ttml\"/></tt>",
+        b"Some notes about <tt xmlns=\"http://www.w3.org/ns/ttml\">",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+    }
+}
+
+#[test]
+fn test_ebu_stl_bytes() {
+    let path = format!("{}/tests/fixtures/ebu_stl.stl", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::EbuStl)
+    );
+    assert_eq!("ebustl".parse(), Ok(ContainerType::EbuStl));
+
+    // 30 fps, and each display standard code.
+    let mut t = buffer.clone();
+    t[3..11].copy_from_slice(b"STL30.01");
+    for dsc in [b' ', b'0', b'1', b'2'] {
+        t[11] = dsc;
+        assert_eq!(ContainerType::from_bytes(&t), Ok(ContainerType::EbuStl));
+    }
+
+    for (pos, bytes) in [
+        (3, &b"STL24.01"[..]),
+        (3, b"XYZ12345"),
+        (11, b"3"),
+        (0, b"8 5"),
+    ] {
+        let mut t = buffer.clone();
+        t[pos..pos + bytes.len()].copy_from_slice(bytes);
+        assert!(ContainerType::from_bytes(&t).is_err(), "{:?}", bytes);
+    }
+}
+
+#[test]
+fn test_sami_bytes() {
+    let path = format!("{}/tests/fixtures/sami.smi", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::SAMI));
+    assert_eq!(
+        ContainerType::from_bytes(b"<SAMI>\r\n<HEAD>"),
+        Ok(ContainerType::SAMI)
+    );
+    assert_eq!("smi".parse(), Ok(ContainerType::SAMI));
+    assert_eq!("sami".parse(), Ok(ContainerType::SAMI));
+
+    // SMIL presentations are `.smi` files too.
+    for buffer in [
+        &b"<smil>\n<body><video src=\"a.mpg\"/></body>\n</smil>"[..],
+        b"<?xml version=\"1.0\"?>\n<smil xmlns=\"http://www.w3.org/ns/SMIL\">",
+        b"<SAMIX>",
+        b"Notes on <SAMI> files",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+    }
+}
+
+#[test]
+fn test_microdvd_bytes() {
+    let path = format!("{}/tests/fixtures/microdvd.sub", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::MicroDVD)
+    );
+    assert_eq!("microdvd".parse(), Ok(ContainerType::MicroDVD));
+    assert_eq!(
+        ContainerType::from_bytes(b"\xEF\xBB\xBF\n{0}{25}a\n\n{30}{60}b"),
+        Ok(ContainerType::MicroDVD)
+    );
+
+    // A single cue is not enough, cut off or not.
+    assert!(ContainerType::from_bytes(b"{24}{72}Hello world\n").is_err());
+    assert!(ContainerType::from_bytes(b"{24}{72}Hello world\n{96}{1").is_err());
+
+    for buffer in [
+        &b"{\"a\": 1}\n{\"b\": 2}\n"[..],
+        b"{}{}\n{}{}\n",
+        b"{1}{2}a\nplain text\n",
+        b"[24][72]Hello\n[96][150]world\n",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+    }
+}
+
+#[test]
+fn test_subviewer_bytes() {
+    for name in ["subviewer.sub", "google.sbv"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::SubViewer),
+            "{}",
+            name
+        );
+    }
+    assert_eq!("sbv".parse(), Ok(ContainerType::SubViewer));
+
+    for buffer in [
+        // SRT.
+        &b"1\n00:00:01,000 --> 00:00:02,500\nHello world\n"[..],
+        b"00:00:01,000 --> 00:00:02,500\nHello world\n",
+        b"0:00:01.000,0:00:02.5\nHello\n",
+        b"0:00:01.000,0:00:02.500 Hello\n",
+        b"0:0:01.000,0:00:02.500\n",
+        b"[INFORMATION] about this file\n",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+    }
+}
+
+#[test]
+fn test_lrc_bytes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/lyrics.lrc");
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::LRC));
+    let buffer = b"\xEF\xBB\xBF[00:12.00]Line one\n[00:17.20]Line two\n";
+    assert_eq!(ContainerType::from_bytes(buffer), Ok(ContainerType::LRC));
+    let buffer = b"\n[ti:Song]\n[01:02]Line\n";
+    assert_eq!(ContainerType::from_bytes(buffer), Ok(ContainerType::LRC));
+    assert_eq!("lrc".parse(), Ok(ContainerType::LRC));
+
+    for buffer in [
+        // INI.
+        &b"[general]\nname=media_infer\n\n[paths]\nroot=/\n"[..],
+        b"[ti:Song]\nplain text\n",
+        b"[00:12.00]Line one\n",
+        b"[artist:Someone]\n[title:Song]\n",
+        b"[00:1.00]Line one\n[00:17.20]Line two\n",
+    ] {
+        assert!(ContainerType::from_bytes(buffer).is_err(), "{:?}", buffer);
+    }
+}
+
+#[test]
+fn test_pgs_bytes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/pgs.sup");
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::PGS));
+    assert_eq!("sup".parse(), Ok(ContainerType::PGS));
+
+    // First segment one byte longer than it is.
+    buffer[12] += 1;
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+    buffer[12] -= 1;
+    // Unknown segment type.
+    buffer[10] = 0x18;
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_vobsub_bytes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/vobsub.idx");
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::VobSubIdx)
+    );
+    assert_eq!("idx".parse(), Ok(ContainerType::VobSubIdx));
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/vobsub.sub");
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::VobSubPS)
+    );
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::VobSubPS, ContainerType::PS]
+    );
+    assert_eq!("vobsub".parse(), Ok(ContainerType::VobSubPS));
+
+    // A pack header and a PES packet of private stream 1, substream 0x20.
+    let mut pack = vec![
+        0x00, 0x00, 0x01, 0xBA, 0x44, 0x00, 0x04, 0x00, 0x04, 0x01, 0x01, 0x89, 0xC3, 0xF8,
+    ];
+    pack.extend_from_slice(&[0x00, 0x00, 0x01, 0xBD, 0x00, 0x04, 0x81, 0x00, 0x00, 0x20]);
+    assert_eq!(
+        ContainerType::from_bytes(&pack),
+        Ok(ContainerType::VobSubPS)
+    );
+    // AC-3 audio substream.
+    pack[23] = 0x80;
+    assert_eq!(ContainerType::from_bytes(&pack), Ok(ContainerType::PS));
+
+    // DVD navigation packet.
+    buffer[17] = 0xBF;
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::PS));
+}
+
+#[test]
+fn test_cheetah_cap_bytes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/cheetah.cap");
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::CheetahCAP)
+    );
+    assert_eq!("cap".parse(), Ok(ContainerType::CheetahCAP));
+
+    buffer[2..4].copy_from_slice(&[0x10, 0x27]);
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+    buffer[2..4].copy_from_slice(&[0x04, 0x00]);
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_pac_bytes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/pac.pac");
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::PAC));
+    assert_eq!("pac".parse(), Ok(ContainerType::PAC));
+
+    // The header without a text block.
+    let header = &buffer[..buffer.iter().position(|x| *x == 0xFE).unwrap()];
+    assert!(!ContainerType::PAC.matches(header));
+    // Seconds of the out timecode past 59.
+    let mut timecode = buffer.clone();
+    timecode[30..32].copy_from_slice(&6012u16.to_le_bytes());
+    assert!(!ContainerType::PAC.matches(&timecode));
+    buffer[7] = 0x01;
+    assert!(!ContainerType::PAC.matches(&buffer));
+
+    // A zero padded header with an `FE`, at the start of the text and further on.
+    for pos in [38, 300] {
+        let mut padded = vec![0; 1024];
+        padded[0] = 0x01;
+        padded[pos] = 0xFE;
+        assert!(!ContainerType::PAC.matches(&padded));
+    }
+
+    // Random data, xorshift so the buffers are the same every run.
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    for _ in 0..1000 {
+        let buffer: Vec<u8> = (0..1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        assert!(!ContainerType::PAC.matches(&buffer));
+    }
+}
+
+#[test]
+fn test_spruce_stl_bytes() {
+    for name in ["spruce.stl", "spruce_comments.stl"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::SpruceSTL),
+            "{}",
+            name
+        );
+    }
+    assert_eq!("spruce-stl".parse(), Ok(ContainerType::SpruceSTL));
+    assert_eq!("stl".parse(), Ok(ContainerType::EbuStl));
+
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/ebu_stl.stl");
+    let buffer = fs::read(path).unwrap();
+    assert!(!ContainerType::SpruceSTL.matches(&buffer));
+    assert!(!ContainerType::SpruceSTL.matches(b"// $FontName = Arial\n$Title = x\n"));
+    assert!(!ContainerType::SpruceSTL.matches(b"$FontNames = Arial\n"));
+}
+
+#[test]
+fn test_mcpoodles_raw_bytes() {
+    for name in ["mcpoodle_broadcast.bin", "mcpoodle_dvd.bin"] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(
+            ContainerType::from_bytes(&buffer),
+            Ok(ContainerType::McPoodlesRaw),
+            "{}",
+            name
+        );
+    }
+    assert_eq!("raw".parse(), Ok(ContainerType::McPoodlesRaw));
+
+    // Erase display memory twice, then padding.
+    let mut buffer = [0xFF, 0xFF, 0x94, 0x2C, 0x94, 0x2C, 0x80, 0x80];
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::McPoodlesRaw)
+    );
+    // Even parity.
+    buffer[7] = 0x00;
+    assert!(!ContainerType::McPoodlesRaw.matches(&buffer));
+
+    // Other MPEG user data is not taken for the DVD flavour, nor is an ES shadowed.
+    assert!(!ContainerType::McPoodlesRaw.matches(&[0, 0, 1, 0xB2, 0x47, 0x41, 0x39, 0x34]));
+    let buffer = [0, 0, 1, 0xB3, 0x2D, 0x01, 0xE0, 0x24];
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::ES));
+}
+
+#[test]
+fn test_cdxa_bytes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/cdxa.dat");
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::CDXA));
+    assert_eq!("dat".parse(), Ok(ContainerType::CDXA));
+    assert_eq!("cdxa".parse(), Ok(ContainerType::CDXA));
+
+    // The MPEG-1 PS in the sectors is found too, but has a lower priority.
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::CDXA, ContainerType::PS]
+    );
+
+    // RIFF, but another form.
+    buffer[8..12].copy_from_slice(b"CDDA");
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::PS));
+    assert!(!ContainerType::CDXA.matches(&buffer));
+    buffer[8..12].copy_from_slice(b"WAVE");
+    assert!(!ContainerType::CDXA.matches(&buffer));
+}
+
+#[test]
+fn test_vob_bytes() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/vob.vob");
+    let mut buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::VOB));
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::VOB, ContainerType::PS]
+    );
+    assert_eq!("vob".parse(), Ok(ContainerType::VOB));
+
+    // DSI packet where the PCI packet goes.
+    let pci = 14 + 24;
+    buffer[pci + 6] = 0x01;
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::PS));
+
+    // MPEG-1 PS of a `.mpg` file: pack header, system header, then video.
+    let mut mpg = vec![
+        0x00, 0x00, 0x01, 0xBA, 0x21, 0x00, 0x01, 0x00, 0x01, 0x80, 0x1B, 0x91,
+    ];
+    mpg.extend_from_slice(&[
+        0x00, 0x00, 0x01, 0xBB, 0x00, 0x09, 0x80, 0x1B, 0x91, 0x04, 0xE1,
+    ]);
+    mpg.extend_from_slice(&[0xFF, 0xE0, 0xE0, 0xE8]);
+    mpg.extend_from_slice(&[
+        0x00, 0x00, 0x01, 0xE0, 0x00, 0x0C, 0x0F, 0x00, 0x00, 0x01, 0xB3,
+    ]);
+    mpg.resize(64, 0);
+    assert_eq!(ContainerType::from_bytes(&mpg), Ok(ContainerType::PS));
+
+    // MPEG-2 PS with a system header and video first.
+    buffer[pci + 3] = 0xE0;
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::PS));
+}
+
+#[test]
+fn test_flv_bytes() {
+    // FLV header with audio and video, PreviousTagSize0, then the first tag.
+    let buffer = [
+        0x46, 0x4c, 0x56, 0x01, 0x05, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x00, 0x12, 0x00,
+        0x01, 0x2c,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::FLV));
+
+    let mut bogus = buffer;
+    bogus[3] = 0x02;
+    assert!(ContainerType::from_bytes(&bogus).is_err());
+
+    assert!(ContainerType::from_bytes(&buffer[..3]).is_err());
+    let t = ContainerType::try_from_bytes(&buffer[..3]);
+    assert!(matches!(t, Err(Error::FileTooSmall { len: 3, .. })));
+}
+
+#[test]
+fn test_ogg_bytes() {
+    // First page of a Vorbis file: BOS page with the 30 byte identification header.
+    let mut buffer = vec![
+        0x4f, 0x67, 0x67, 0x53, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x6c,
+        0x3f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4e, 0x1b, 0x2a, 0x7d, 0x01, 0x1e, 0x01, 0x76,
+        0x6f, 0x72, 0x62, 0x69, 0x73,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::OGG));
+    for name in ["ogg", "ogv", "oga"] {
+        assert_eq!(name.parse(), Ok(ContainerType::OGG));
+    }
+
+    // Capture pattern only at a nonzero offset.
+    buffer.insert(0, 0x00);
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_ac3_bytes() {
+    // 48 kHz, 192 kbit/s: 768 byte frames.
+    let mut frame = vec![0; 768];
+    frame[..7].copy_from_slice(&[0x0B, 0x77, 0x00, 0x00, 0x14, 0x40, 0x43]);
+    let buffer = [&frame[..], &frame].concat();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::AC3));
+    assert_eq!("ac3".parse(), Ok(ContainerType::AC3));
+    assert_eq!(ContainerType::AC3.to_string(), "Dolby Digital (AC-3)");
+
+    // Second sync word missing, or where a 44.1 kHz frame would end.
+    assert!(ContainerType::from_bytes(&frame).is_err());
+    let buffer = [&frame[..], &[0; 768]].concat();
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+    let mut buffer = [&frame[..], &frame, &frame].concat();
+    buffer[4] = 0x54;
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+}
+
+#[test]
+fn test_ac3_eac3_distinct() {
+    for (name, kind) in [
+        ("ac3.ac3", ContainerType::AC3),
+        ("eac3.ec3", ContainerType::EAC3),
+    ] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(ContainerType::from_bytes_all(&buffer), [kind], "{}", name);
+    }
+    assert_eq!("ec3".parse(), Ok(ContainerType::EAC3));
+
+    // An AC-3 frame followed by an E-AC-3 one is neither.
+    let mut ac3 = vec![0; 768];
+    ac3[..7].copy_from_slice(&[0x0B, 0x77, 0x00, 0x00, 0x14, 0x40, 0x43]);
+    let mut eac3 = vec![0; 768];
+    eac3[..6].copy_from_slice(&[0x0B, 0x77, 0x01, 0x7F, 0x34, 0x80]);
+    for buffer in [[&ac3[..], &eac3].concat(), [&eac3[..], &ac3].concat()] {
+        assert!(ContainerType::from_bytes(&buffer).is_err());
+    }
+}
+
+#[test]
+fn test_dts_bytes() {
+    // Core frame header: 512 samples, 1024 bytes, 3/2 channels at 48 kHz.
+    let header = [0x7F, 0xFE, 0x80, 0x01, 0xFC, 0x3C, 0x3F, 0xF2, 0x75, 0xE0];
+    assert_eq!(ContainerType::from_bytes(&header), Ok(ContainerType::DTS));
+    assert_eq!(dts_sync(&header), Some(DtsSync::Be16));
+    assert_eq!("dts".parse(), Ok(ContainerType::DTS));
+
+    // After zero padding.
+    let buffer = [&[0; 16][..], &header].concat();
+    assert_eq!(ContainerType::from_bytes(&buffer), Ok(ContainerType::DTS));
+
+    // The same header with its 16 bit words byte swapped.
+    let swapped: Vec<u8> = header.chunks(2).flat_map(|x| [x[1], x[0]]).collect();
+    assert_eq!(ContainerType::from_bytes(&swapped), Ok(ContainerType::DTS));
+    assert_eq!(dts_sync(&swapped), Some(DtsSync::Le16));
+}
+
+#[test]
+fn test_dtshd_distinct() {
+    for (name, kind, sync) in [
+        ("dts.dts", ContainerType::DTS, Some(DtsSync::Be16)),
+        ("dtshd_core.dts", ContainerType::DTSHD, Some(DtsSync::Be16)),
+        ("dtshd_ext.dtshd", ContainerType::DTSHD, None),
+    ] {
+        let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let buffer = fs::read(path).unwrap();
+        assert_eq!(ContainerType::from_bytes_all(&buffer), [kind], "{}", name);
+        assert_eq!(dts_sync(&buffer), sync, "{}", name);
+    }
+    assert_eq!("dts-hd".parse(), Ok(ContainerType::DTSHD));
+}
+
+#[test]
+fn test_dts_deep_sync() {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    let mut buffer: Vec<u8> = (0..4096)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8 | 1
+        })
+        .collect();
+    buffer[2048..2052].copy_from_slice(&[0x7F, 0xFE, 0x80, 0x01]);
+    assert!(!ContainerType::from_bytes_all(&buffer).contains(&ContainerType::DTS));
+    assert_eq!(dts_sync(&buffer), None);
+    // Past the zero padding allowed.
+    let buffer = [&[0; 64][..], &buffer[2048..]].concat();
+    assert!(!ContainerType::from_bytes_all(&buffer).contains(&ContainerType::DTS));
+}
+
+#[test]
+fn test_truehd_bytes() {
+    let path = format!("{}/tests/fixtures/truehd.thd", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    // Major sync after the header of the first access unit and 4 more bytes.
+    let buffer = [&buffer[..4], &[0; 4], &buffer[4..]].concat();
+    assert_eq!(&buffer[8..12], b"\xF8\x72\x6F\xBA");
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::TrueHD)
+    );
+    for name in ["truehd", "thd", "mlp"] {
+        assert_eq!(name.parse(), Ok(ContainerType::TrueHD));
+    }
+
+    // MLP major sync.
+    let path = format!("{}/tests/fixtures/mlp.mlp", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(buffer[7], 0xBB);
+    assert_eq!(
+        ContainerType::from_bytes(&buffer),
+        Ok(ContainerType::TrueHD)
+    );
+}
+
+#[test]
+fn test_truehd_not_ac3() {
+    let path = format!("{}/tests/fixtures/ac3.ac3", env!("CARGO_MANIFEST_DIR"));
+    let buffer = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::AC3]);
+    let path = format!("{}/tests/fixtures/truehd.thd", env!("CARGO_MANIFEST_DIR"));
+    let thd = fs::read(path).unwrap();
+    assert_eq!(ContainerType::from_bytes_all(&thd), [ContainerType::TrueHD]);
+
+    // Blu-ray streams interleave an AC-3 core, here a 32 kbit/s frame at 48 kHz.
+    let mut frame = [0; 128];
+    frame[..6].copy_from_slice(&[0x0B, 0x77, 0x00, 0x00, 0x00, 0x40]);
+    let buffer = [&frame[..], &thd].concat();
+    assert_eq!(
+        ContainerType::from_bytes_all(&buffer),
+        [ContainerType::TrueHD]
+    );
+}
+
+#[test]
+fn test_flac_bytes() {
+    // fLaC and the header of a STREAMINFO block, the last metadata block.
+    let buffer = b"fLaC\x80\x00\x00\x22\x10\x00\x10\x00\x00\x00\x0e\x00\x30\x75";
+    let t = ContainerType::from_bytes(buffer);
+    assert_eq!(t, Ok(ContainerType::FLAC));
+    assert_eq!("flac".parse(), Ok(ContainerType::FLAC));
+    assert_eq!(
+        ContainerType::FLAC.to_string(),
+        "Free Lossless Audio Codec (FLAC)"
+    );
+
+    // SEEKTABLE as the first block.
+    assert!(ContainerType::from_bytes(b"fLaC\x03\x00\x00\x12\x00\x00").is_err());
+}
+
+#[test]
+fn test_flac_in_ogg() {
+    // BOS page of the Ogg FLAC mapping: one 51 byte packet with `\x7FFLAC`, the mapping
+    // version, the header count, then `fLaC` and the STREAMINFO block.
+    let mut buffer = b"OggS\x00\x02".to_vec();
+    buffer.extend_from_slice(&[0; 20]);
+    buffer.extend_from_slice(&[1, 51]);
+    buffer.extend_from_slice(b"\x7FFLAC\x01\x00\x00\x01fLaC\x00\x00\x00\x22");
+    buffer.extend_from_slice(&[0; 34]);
+    assert_eq!(ContainerType::from_bytes_all(&buffer), [ContainerType::OGG]);
+    assert_eq!(ogg_codec(&buffer), Some(OggCodec::Flac));
+}
+
+#[test]
+fn test_rm_bytes() {
+    // .RMF header, version 1, then the start of the PROP chunk.
+    let buffer = [
+        0x2e, 0x52, 0x4d, 0x46, 0x00, 0x00, 0x00, 0x12, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x05, 0x50, 0x52, 0x4f, 0x50,
+    ];
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::RealMedia));
+    for name in ["rm", "rmvb"] {
+        assert_eq!(name.parse(), Ok(ContainerType::RealMedia));
+    }
+    assert_eq!(ContainerType::RealMedia.to_string(), "RealMedia (RM)");
+
+    assert!(ContainerType::from_bytes(b".RM").is_err());
+}
+
+#[test]
+fn test_y4m_bytes() {
+    // Stream header, then the first frame header.
+    let buffer = b"YUV4MPEG2 W176 H144 F25:1 It A128:117 C420mpeg2 XYSCSS=420MPEG2\nFRAME\n";
+    let t = ContainerType::from_bytes(buffer);
+    assert_eq!(t, Ok(ContainerType::Y4M));
+    assert_eq!("y4m".parse(), Ok(ContainerType::Y4M));
+
+    // No space after the signature.
+    assert!(ContainerType::from_bytes(b"YUV4MPEG2\nFRAME\n").is_err());
+    assert!(ContainerType::from_bytes(&buffer[..5]).is_err());
+}
+
+#[test]
+fn test_ivf_bytes() {
+    // IVF header of a VP8 test vector: 176x144, 30 fps, 29 frames.
+    let mut buffer = [
+        0x44, 0x4b, 0x49, 0x46, 0x00, 0x00, 0x20, 0x00, 0x56, 0x50, 0x38, 0x30, 0xb0, 0x00, 0x90,
+        0x00, 0x1e, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x1d, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00,
+    ];
+    let codecs = [
+        (b"VP80", IvfCodec::Vp8),
+        (b"VP90", IvfCodec::Vp9),
+        (b"AV01", IvfCodec::Av1),
+    ];
+    for (fourcc, codec) in codecs {
+        buffer[8..12].copy_from_slice(fourcc);
+        let t = ContainerType::from_bytes(&buffer);
+        assert_eq!(t, Ok(ContainerType::IVF));
+        assert_eq!(ivf_codec(&buffer), Some(codec));
+    }
+    assert_eq!("ivf".parse(), Ok(ContainerType::IVF));
+
+    // Unknown fourcc, still IVF.
+    buffer[8..12].copy_from_slice(b"H264");
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::IVF));
+    assert_eq!(ivf_codec(&buffer), Some(IvfCodec::Other(*b"H264")));
+
+    // Not a fourcc.
+    buffer[8..12].copy_from_slice(&[0, 0, 0, 0]);
+    assert!(ContainerType::from_bytes(&buffer).is_err());
+    assert_eq!(ivf_codec(&buffer), None);
+}
+
+#[test]
+fn test_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let t = ContainerType::from_file_path(dir.path());
+    match t {
+        Err(Error::IsADirectory { path }) => assert_eq!(path.as_deref(), Some(dir.path())),
+        t => panic!("{:?}", t),
+    }
+    let t = ContainerType::from_file(&mut fs::File::open(dir.path()).unwrap());
+    assert!(matches!(t, Err(Error::IsADirectory { path: None })));
+}
+
+#[test]
+fn test_empty_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("empty.ts");
+    fs::write(&path, []).unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert!(matches!(t, Err(Error::EmptyFile)));
+}
+
+#[test]
+fn test_tiny_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("tiny.mkv");
+    fs::write(&path, [0x1a, 0x45]).unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert!(matches!(
+        t,
+        Err(Error::FileTooSmall {
+            len: 2,
+            min_needed: 4
+        })
+    ));
+}
+
+#[test]
+fn test_truncated_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("truncated.ts");
+    let mut buffer = [0; 40];
+    buffer[0..4].copy_from_slice(&[0x47, 0x40, 0x11, 0x10]);
+    fs::write(&path, buffer).unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert!(matches!(
+        t,
+        Err(Error::FileTooSmall {
+            len: 40,
+            min_needed: 1132
+        })
+    ));
+
+    // Leading bytes of no check.
+    fs::write(&path, [0x42; 40]).unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert!(matches!(t, Err(Error::Unidentified)));
+}
+
+#[test]
+fn test_small_mkv_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("small.mkv");
+    fs::write(&path, [0x1a, 0x45, 0xdf, 0xa3]).unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert_eq!(t.ok(), Some(ContainerType::MKV));
+}
+
+#[test]
+fn test_missing_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing.ts");
+    let t = ContainerType::from_file_path(&path);
+    match t {
+        Err(Error::Open { path: p, source }) => {
+            assert_eq!(p, path);
+            assert_eq!(source.kind(), ErrorKind::NotFound);
+        }
+        _ => panic!("expected open error, got {:?}", t),
+    }
+}
+
+#[test]
+fn test_open_error_display() {
+    let path = std::path::Path::new("/nonexistent/x.ts");
+    let t = ContainerType::from_file_path(path).unwrap_err();
+    assert!(t
+        .to_string()
+        .starts_with("failed to open /nonexistent/x.ts: "));
+}
+
+#[test]
+fn test_small_file_not_padded() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("small.bin");
+
+    fs::write(&path, [0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0]).unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert!(matches!(t, Err(Error::Unidentified)));
+
+    // First 8 bytes of an RCWT header, the zeros at 8-10 must come from the file.
+    fs::write(&path, [0xCC, 0xCC, 0xED, 0xCC, 0x00, 0x50, 0x00, 0x01]).unwrap();
+    let t = ContainerType::from_file_path(&path);
+    assert!(matches!(
+        t,
+        Err(Error::FileTooSmall {
+            len: 8,
+            min_needed: 11
+        })
+    ));
+}
+
+#[test]
+fn test_ambiguous() {
+    let mut buffer = vec![0; 4096];
+    buffer[0..4].copy_from_slice(b"TiVo");
+    buffer[2048..2052].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::TivoPS));
+    let t = ContainerType::from_bytes_all(&buffer);
+    assert_eq!(t, vec![ContainerType::TivoPS, ContainerType::PS]);
+
+    // PS only matches because TiVo files hold a PS, that is not ambiguous.
+    let strict = Detector::builder().strict_ambiguity(true).build();
+    assert_eq!(strict.detect(&buffer).ok(), Some(ContainerType::TivoPS));
+
+    // A TrueHD major sync on top is.
+    buffer[256..272].copy_from_slice(&[
+        0xF0, 0x80, 0x00, 0x00, 0xF8, 0x72, 0x6F, 0xBA, 0x00, 0x00, 0x0F, 0x4F, 0xB7, 0x52, 0x00,
+        0x00,
+    ]);
+    match strict.detect(&buffer) {
+        Err(Error::Ambiguous(kinds)) => assert_eq!(
+            kinds,
+            vec![
+                ContainerType::TivoPS,
+                ContainerType::TrueHD,
+                ContainerType::PS
+            ]
+        ),
+        t => panic!("expected ambiguity, got {:?}", t),
+    }
+
+    // A single match is still fine in strict mode.
+    let t = strict.detect(&[0x1a, 0x45, 0xdf, 0xa3, 0, 1]);
+    assert_eq!(t.ok(), Some(ContainerType::MKV));
+}
+
+#[test]
+fn test_shared_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("shared.mkv");
+    let mut buffer = vec![0; 4096];
+    buffer[0..4].copy_from_slice(&[0x1a, 0x45, 0xdf, 0xa3]);
+    fs::write(&path, &buffer).unwrap();
+
+    let mut file = fs::File::open(&path).unwrap();
+    file.seek(SeekFrom::Start(100)).unwrap();
+    let file = Arc::new(file);
+
+    let threads: Vec<_> = (0..2)
+        .map(|_| {
+            let file = Arc::clone(&file);
+            thread::spawn(move || ContainerType::from_file_ref(&file))
+        })
+        .collect();
+    for t in threads {
+        assert_eq!(t.join().unwrap().ok(), Some(ContainerType::MKV));
+    }
+
+    #[cfg(unix)]
+    assert_eq!((&*file).stream_position().unwrap(), 100);
+}
+
+#[test]
+fn test_priority() {
+    let mut buffer = vec![0; 4096];
+    buffer[0..4].copy_from_slice(b"TiVo");
+    buffer[2048..2052].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+
+    let t = Detector::default().detect(&buffer);
+    assert_eq!(t.ok(), Some(ContainerType::TivoPS));
+
+    let dvd = Detector::builder()
+        .priority(&[ContainerType::PS])
+        .unwrap()
+        .build();
+    let t = dvd.detect(&buffer);
+    assert_eq!(t.ok(), Some(ContainerType::PS));
+}
+
+#[test]
+fn test_ts_corrupted_sync() {
+    let mut buffer = vec![0; 188 * 12];
+    for i in 0..12 {
+        buffer[i * 188..i * 188 + 4].copy_from_slice(&[0x47, 0x01, 0x00, 0x10]);
+    }
+    // Flipped bit in the sync byte of the second packet.
+    buffer[188] = 0x07;
+    let t = ContainerType::from_bytes(&buffer);
+    assert_eq!(t, Ok(ContainerType::TS));
+
+    let strict = Detector::builder().ts_sync_threshold(10).build();
+    assert!(strict.detect(&buffer).is_err());
+}
+
+#[test]
+fn test_random_not_ts() {
+    let mut state: u32 = 0x1234_5678;
+    let buffer: Vec<u8> = (0..1024 * 1024)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+    let relaxed = Detector::builder()
+        .ts_sync_threshold(5)
+        .formats(&[ContainerType::TS, ContainerType::M2TS])
+        .build();
+    assert!(relaxed.detect(&buffer).is_err());
+}
+
+#[test]
+fn test_ps_window() {
+    let mut buffer = vec![0; 70000];
+    buffer[60000..60004].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+
+    let t = ContainerType::from_bytes(&buffer);
+    assert!(t.is_err());
+
+    let wide = Detector::builder().ps_window(64 * 1024).build();
+    assert_eq!(wide.detect(&buffer).ok(), Some(ContainerType::PS));
+    assert!(wide.recommended_probe_len() >= 64 * 1024);
+}
+
+#[test]
+fn test_not_media() {
+    let files: [(&[u8], &str); 8] = [
+        (b"PK\x03\x04\x14\x00\x00\x00", "ZIP archive"),
+        (b"%PDF-1.7\n", "PDF document"),
+        (b"\x89PNG\r\n\x1a\n", "PNG image"),
+        (b"\xFF\xD8\xFF\xE0\x00\x10JFIF", "JPEG image"),
+        (b"\x7FELF\x02\x01\x01\x00", "ELF binary"),
+        (b"\x1F\x8B\x08\x00\x00\x00", "gzip archive"),
+        (b"\x28\xB5\x2F\xFD\x04\x58", "zstd archive"),
+        (b"\xFD7zXZ\x00\x00\x04", "xz archive"),
+    ];
+
+    for (buffer, name) in files.iter() {
+        match Detector::default().detect(buffer) {
+            Err(Error::NotMedia(t)) => assert_eq!(t, *name),
+            t => panic!("expected {}, got {:?}", name, t),
+        }
+        let t = Detector::builder()
+            .non_media_check(false)
+            .build()
+            .detect(buffer);
+        assert!(matches!(t, Err(Error::Unidentified)));
+    }
+}
+
+#[test]
+fn test_try_from_bytes() {
+    let t = ContainerType::try_from_bytes(&[0x1a, 0x45, 0xdf, 0xa3, 0, 1]);
+    assert_eq!(t.ok(), Some(Some(ContainerType::MKV)));
+
+    let t = ContainerType::try_from_bytes(&[0x42; 64]);
+    assert_eq!(t.ok(), Some(None));
+    let t = ContainerType::try_from_bytes(b"PK\x03\x04\x14\x00\x00\x00");
+    assert_eq!(t.ok(), Some(None));
+
+    let t = ContainerType::try_from_bytes(&[]);
+    assert!(matches!(t, Err(Error::EmptyFile)));
+    let t = ContainerType::try_from_bytes(&[0x1a, 0x45]);
+    assert!(matches!(t, Err(Error::FileTooSmall { len: 2, .. })));
+}
+
+#[test]
+fn test_try_from_file_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file.bin");
+
+    fs::write(&path, [0x1a, 0x45, 0xdf, 0xa3, 0, 1]).unwrap();
+    let t = ContainerType::try_from_file_path(&path);
+    assert_eq!(t.ok(), Some(Some(ContainerType::MKV)));
+
+    fs::write(&path, [0x42; 64]).unwrap();
+    let t = ContainerType::try_from_file_path(&path);
+    assert_eq!(t.ok(), Some(None));
+
+    let t = ContainerType::try_from_file_path(&dir.path().join("missing.bin"));
+    assert!(matches!(t, Err(Error::Open { .. })));
+}
+
+#[test]
+fn test_matches() {
+    let buffer = [0x1a, 0x45, 0xdf, 0xa3, 0, 0, 0, 0];
+    assert!(ContainerType::MKV.matches(&buffer));
+    assert!(!ContainerType::ASF.matches(&buffer));
+    assert!(!ContainerType::McPoodlesRaw.matches(&buffer));
+
+    // Both match, from_bytes picks TiVo by priority.
+    let mut buffer = [0; 64];
+    buffer[0..4].copy_from_slice(b"TiVo");
+    buffer[32..36].copy_from_slice(&[0x00, 0x00, 0x01, 0xBA]);
+    assert!(ContainerType::TivoPS.matches(&buffer));
+    assert!(ContainerType::PS.matches(&buffer));
 }
@@ -0,0 +1,11 @@
+use std::fs;
+use std::path::Path;
+
+/// Locks the generated magic file, so signature table changes show up in review.
+/// Update with `cargo run --example magic > tests/snapshots/media_infer.magic`.
+#[test]
+fn test_magic_snapshot() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/media_infer.magic");
+    let snapshot = fs::read_to_string(path).unwrap();
+    assert_eq!(media_infer::magic_definitions(), snapshot);
+}
@@ -0,0 +1,102 @@
+//! Property tests: arbitrary, truncated and corrupted inputs.
+//!
+//! Case counts are kept low so `cargo test` stays fast, raise them with
+//! `PROPTEST_CASES` when looking for trouble.
+
+use media_infer::{ContainerType, Error};
+use proptest::prelude::*;
+use proptest::sample::Index;
+use std::fs;
+use std::path::Path;
+
+/// `default` cases unless `PROPTEST_CASES` is set.
+fn cases(default: u32) -> u32 {
+    std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(default)
+}
+
+/// The golden fixtures and what they are.
+fn fixtures() -> Vec<(ContainerType, Vec<u8>)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let expected: toml::Table = fs::read_to_string(dir.join("expected.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    expected
+        .iter()
+        .map(|(name, kind)| {
+            let kind = kind.as_str().unwrap().parse().unwrap();
+            (kind, fs::read(dir.join(name)).unwrap())
+        })
+        .collect()
+}
+
+/// What a file of `kind` cut before the bytes telling them apart is taken for.
+fn cut_short(kind: ContainerType) -> Option<ContainerType> {
+    match kind {
+        ContainerType::WEBM | ContainerType::MKA => Some(ContainerType::MKV),
+        ContainerType::DTSHD => Some(ContainerType::DTS),
+        ContainerType::VobSubPS
+        | ContainerType::CDXA
+        | ContainerType::VOB
+        | ContainerType::TivoPS => Some(ContainerType::PS),
+        _ => None,
+    }
+}
+
+/// Whatever `from_bytes` and `from_bytes_all` return must match on its own.
+fn check_consistent(buffer: &[u8]) {
+    if let Ok(kind) = ContainerType::from_bytes(buffer) {
+        assert!(
+            kind.matches(buffer),
+            "{:?} returned but does not match",
+            kind
+        );
+    }
+    for kind in ContainerType::from_bytes_all(buffer) {
+        assert!(kind.matches(buffer), "{:?} listed but does not match", kind);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(cases(16)))]
+
+    #[test]
+    fn arbitrary_bytes(buffer in proptest::collection::vec(any::<u8>(), 0..2 * 1024 * 1024)) {
+        check_consistent(&buffer);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(cases(256)))]
+
+    #[test]
+    fn short_arbitrary_bytes(buffer in proptest::collection::vec(any::<u8>(), 0..4096)) {
+        check_consistent(&buffer);
+    }
+
+    #[test]
+    fn truncated_fixture(fixture in any::<Index>(), cut in any::<Index>()) {
+        let fixtures = fixtures();
+        let (kind, buffer) = fixture.get(&fixtures);
+        let buffer = &buffer[..cut.index(buffer.len() + 1)];
+        match ContainerType::try_from_bytes(buffer) {
+            Ok(Some(t)) if cut_short(*kind) == Some(t) => {}
+            Ok(Some(t)) => prop_assert_eq!(t, *kind),
+            Ok(None) | Err(Error::EmptyFile) | Err(Error::FileTooSmall { .. }) => {}
+            Err(e) => prop_assert!(false, "unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn corrupted_fixture(fixture in any::<Index>(), pos in any::<Index>(), byte in any::<u8>()) {
+        let fixtures = fixtures();
+        let (_, buffer) = fixture.get(&fixtures);
+        let mut buffer = buffer.clone();
+        let pos = pos.index(buffer.len());
+        buffer[pos] = byte;
+        check_consistent(&buffer);
+    }
+}
@@ -0,0 +1,235 @@
+//! Differential testing against ffprobe.
+//!
+//! Shared by `tests/ffprobe.rs` and `examples/ffprobe_diff.rs`.
+
+// Each user only needs part of it.
+#![allow(dead_code)]
+
+use media_infer::ContainerType;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// ffprobe `format_name` to the containers that agree with it.
+/// ffprobe reports M2TS as plain `mpegts`, so both count.
+pub const FFPROBE_FORMATS: &[(&str, &[ContainerType])] = &[
+    (
+        "matroska,webm",
+        &[ContainerType::MKV, ContainerType::WEBM, ContainerType::MKA],
+    ),
+    ("asf", &[ContainerType::ASF]),
+    ("gxf", &[ContainerType::GXF]),
+    ("wtv", &[ContainerType::WTV]),
+    (
+        "mov,mp4,m4a,3gp,3g2,mj2",
+        &[
+            ContainerType::MP4,
+            ContainerType::MOV,
+            ContainerType::ThreeGP,
+            ContainerType::ThreeG2,
+            ContainerType::M4A,
+            ContainerType::FragmentedMP4,
+            ContainerType::HEIF,
+            ContainerType::AVIF,
+            ContainerType::AVIFSequence,
+        ],
+    ),
+    ("mpegts", &[ContainerType::TS, ContainerType::M2TS]),
+    (
+        "mpeg",
+        &[
+            ContainerType::PS,
+            ContainerType::VobSubPS,
+            ContainerType::CDXA,
+            ContainerType::VOB,
+        ],
+    ),
+    ("mxf", &[ContainerType::MXF]),
+    ("ty", &[ContainerType::TivoPS]),
+    ("mpegvideo", &[ContainerType::ES]),
+    ("h264", &[ContainerType::H264ES]),
+    ("hevc", &[ContainerType::H265ES]),
+    ("m4v", &[ContainerType::MPEG4ES]),
+    ("obu", &[ContainerType::AV1OBU]),
+    ("aac", &[ContainerType::AACADTS]),
+    ("loas", &[ContainerType::AACLATM]),
+    ("mp3", &[ContainerType::MP3]),
+    ("flac", &[ContainerType::FLAC]),
+    ("ac3", &[ContainerType::AC3]),
+    ("eac3", &[ContainerType::EAC3]),
+    ("dts", &[ContainerType::DTS, ContainerType::DTSHD]),
+    ("truehd", &[ContainerType::TrueHD]),
+    ("mlp", &[ContainerType::TrueHD]),
+    ("avi", &[ContainerType::AVI]),
+    ("wav", &[ContainerType::WAV, ContainerType::RF64]),
+    ("w64", &[ContainerType::W64]),
+    ("libopenmpt", &[ContainerType::TrackerModule]),
+    ("webvtt", &[ContainerType::WebVTT]),
+    ("scc", &[ContainerType::SCC]),
+    ("mcc", &[ContainerType::MCC]),
+    ("sami", &[ContainerType::SAMI]),
+    ("microdvd", &[ContainerType::MicroDVD]),
+    ("subviewer", &[ContainerType::SubViewer]),
+    ("lrc", &[ContainerType::LRC]),
+    ("sup", &[ContainerType::PGS]),
+    ("vobsub", &[ContainerType::VobSubIdx]),
+    ("stl", &[ContainerType::SpruceSTL]),
+    ("aiff", &[ContainerType::AIFF]),
+    ("au", &[ContainerType::AU]),
+    ("amr", &[ContainerType::AMR, ContainerType::AMRWB]),
+    ("caf", &[ContainerType::CAF]),
+    ("wv", &[ContainerType::WavPack]),
+    ("ape", &[ContainerType::APE]),
+    ("mpc", &[ContainerType::Musepack]),
+    ("mpc8", &[ContainerType::Musepack]),
+    ("tta", &[ContainerType::TTA]),
+    ("shn", &[ContainerType::Shorten]),
+    ("dsf", &[ContainerType::DSF]),
+    ("iff", &[ContainerType::DFF]),
+    ("flv", &[ContainerType::FLV]),
+    ("rm", &[ContainerType::RealMedia]),
+    ("ivf", &[ContainerType::IVF]),
+    ("yuv4mpegpipe", &[ContainerType::Y4M]),
+    ("ogg", &[ContainerType::OGG]),
+];
+
+/// Containers that agree with an ffprobe `format_name`, `None` if it is not in the table.
+pub fn expected(format_name: &str) -> Option<&'static [ContainerType]> {
+    FFPROBE_FORMATS
+        .iter()
+        .find(|x| x.0 == format_name)
+        .map(|x| x.1)
+}
+
+/// Runs `ffprobe -show_format` on `path`.
+/// `Ok(None)` if ffprobe ran but could not read the file.
+pub fn ffprobe_format(path: &Path) -> io::Result<Option<String>> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_format",
+            "-of",
+            "default=noprint_wrappers=1",
+        ])
+        .arg(path)
+        .output()?;
+    if !output.status.success() {
+        return Ok(None);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .find_map(|x| x.strip_prefix("format_name="))
+        .map(|x| x.to_string()))
+}
+
+/// A file where ffprobe and `from_file_path` disagree.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub path: PathBuf,
+    /// `format_name` from ffprobe, `None` if it could not read the file.
+    pub ffprobe: Option<String>,
+    pub ours: Result<ContainerType, String>,
+}
+
+/// Agreement statistics over a directory.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Files looked at.
+    pub total: usize,
+    /// Both agree, including both failing to identify the file.
+    pub agreed: usize,
+    /// ffprobe found a format missing from `FFPROBE_FORMATS` and we found nothing.
+    pub unmapped: Vec<(PathBuf, String)>,
+    pub disagreements: Vec<Disagreement>,
+}
+
+impl Report {
+    /// Compares every regular file under `dir`, recursively.
+    pub fn run(dir: &Path) -> io::Result<Self> {
+        let mut report = Self::default();
+        let mut dirs = vec![dir.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let mut entries = fs::read_dir(&dir)?
+                .map(|x| x.map(|x| x.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.sort();
+            for path in entries {
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.is_file() {
+                    let ffprobe = ffprobe_format(&path)?;
+                    let ours = ContainerType::from_file_path(&path).map_err(|e| e.to_string());
+                    report.add(path, ffprobe, ours);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Records the result for one file.
+    pub fn add(
+        &mut self,
+        path: PathBuf,
+        ffprobe: Option<String>,
+        ours: Result<ContainerType, String>,
+    ) {
+        self.total += 1;
+        let agreed = match (&ffprobe, &ours) {
+            (None, Err(_)) => true,
+            (Some(name), Ok(kind)) => expected(name).is_some_and(|x| x.contains(kind)),
+            (Some(name), Err(_)) if expected(name).is_none() => {
+                self.unmapped.push((path, name.clone()));
+                return;
+            }
+            _ => false,
+        };
+        if agreed {
+            self.agreed += 1;
+        } else {
+            self.disagreements.push(Disagreement {
+                path,
+                ffprobe,
+                ours,
+            });
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let percent = match self.total {
+            0 => 100.0,
+            total => self.agreed as f64 * 100.0 / total as f64,
+        };
+        writeln!(
+            f,
+            "{} files, {} agreed ({:.1}%), {} unmapped, {} disagreed",
+            self.total,
+            self.agreed,
+            percent,
+            self.unmapped.len(),
+            self.disagreements.len()
+        )?;
+        for (path, name) in &self.unmapped {
+            writeln!(f, "unmapped {}: ffprobe {}", path.display(), name)?;
+        }
+        for x in &self.disagreements {
+            let ffprobe = x.ffprobe.as_deref().unwrap_or("<unreadable>");
+            match &x.ours {
+                Ok(kind) => writeln!(
+                    f,
+                    "{}: ffprobe {}, ours {:?}",
+                    x.path.display(),
+                    ffprobe,
+                    kind
+                )?,
+                Err(e) => writeln!(f, "{}: ffprobe {}, ours {}", x.path.display(), ffprobe, e)?,
+            }
+        }
+        Ok(())
+    }
+}
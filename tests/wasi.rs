@@ -0,0 +1,32 @@
+//! Smoke test for WASI, where the file APIs fall back to seek + read.
+//!
+//! ```sh
+//! cargo test --target wasm32-wasip1 --test wasi --no-run
+//! wasmtime --dir . target/wasm32-wasip1/debug/deps/wasi-*.wasm
+//! ```
+//! Run from the crate root, so the fixtures are in the preopened directory.
+#![cfg(target_os = "wasi")]
+
+use media_infer::ContainerType;
+use std::fs::File;
+use std::path::Path;
+
+fn fixture(name: &str) -> &'static Path {
+    Box::leak(Path::new("tests/fixtures").join(name).into_boxed_path())
+}
+
+#[test]
+fn test_wasi_file_path() {
+    let t = ContainerType::from_file_path(fixture("mkv.mkv"));
+    assert_eq!(t.ok(), Some(ContainerType::MKV));
+    // Scans the whole file.
+    let t = ContainerType::from_file_path(fixture("ps.mpg"));
+    assert_eq!(t.ok(), Some(ContainerType::PS));
+}
+
+#[test]
+fn test_wasi_file() {
+    let mut file = File::open(fixture("ts.ts")).unwrap();
+    let t = ContainerType::from_file(&mut file);
+    assert_eq!(t.ok(), Some(ContainerType::TS));
+}